@@ -23,7 +23,7 @@ pub macro array_split
 				pub $name:
 
 				$( &'a [T; $arr_size], )?
-				$( &'a T, #[cfg(invalid)] __field: [u8; $val_size], )?
+				$( &'a T, #[cfg(any())] __field: [u8; $val_size], )?
 			)*
 		}
 
@@ -77,7 +77,7 @@ pub macro array_split_mut(
 				$( &'a mut [T; $arr_size], )?
 				// Note: This `cfg` is simply done so that `__field` never appears.
 				//       The `__field` serves to identify when this part should be written.
-				$( &'a mut T, #[cfg(invalid)] __field: [u8; $val_size], )?
+				$( &'a mut T, #[cfg(any())] __field: [u8; $val_size], )?
 			)*
 		}
 
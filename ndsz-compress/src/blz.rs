@@ -0,0 +1,253 @@
+//! Backwards LZ compression.
+//!
+//! Used by the arm9 binary and overlays, as it may be decompressed in-place.
+//!
+//! The data is split into an uncompressed head and a compressed tail, followed by a footer.
+//! The tail is LZ10-like, but both it and the decompressed data are stored reversed,
+//! with matches of `3..=0x12` bytes at offsets of `3..=0x1002`.
+
+// Modules
+mod error;
+#[cfg(test)]
+mod test;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::lz::{self, MatchEncoding, Mode, Token},
+	byteorder::{ByteOrder, LittleEndian, ReadBytesExt},
+	std::{convert::TryFrom, io},
+};
+
+/// Minimum footer size
+const MIN_FOOTER_SIZE: usize = 0x8;
+
+/// Maximum footer size
+const MAX_FOOTER_SIZE: usize = 0xb;
+
+/// Footer
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Footer {
+	/// Size of the compressed tail, including the footer
+	pub compressed_size: u32,
+
+	/// Size of the footer, including any padding before it
+	pub footer_size: u8,
+
+	/// Size added by decompressing
+	pub extra_size: u32,
+}
+
+impl Footer {
+	/// Reads the footer from the end of `data`.
+	///
+	/// Returns `None` if `data` is too small or it's a footer of uncompressed data.
+	#[must_use]
+	pub fn from_data(data: &[u8]) -> Option<Self> {
+		let bytes = data.len().checked_sub(MIN_FOOTER_SIZE).map(|start| &data[start..])?;
+		let footer = Self {
+			compressed_size: LittleEndian::read_u24(&bytes[..0x3]),
+			footer_size:     bytes[0x3],
+			extra_size:      LittleEndian::read_u32(&bytes[0x4..]),
+		};
+
+		match footer.extra_size {
+			0 => None,
+			_ => Some(footer),
+		}
+	}
+
+	/// Writes this footer to bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 8] {
+		let mut bytes = [0; 8];
+		LittleEndian::write_u24(&mut bytes[..0x3], self.compressed_size);
+		bytes[0x3] = self.footer_size;
+		LittleEndian::write_u32(&mut bytes[0x4..], self.extra_size);
+
+		bytes
+	}
+}
+
+/// Decompresses data.
+///
+/// Returns `None` if the data isn't compressed.
+pub fn decompress(data: &[u8]) -> Result<Option<Vec<u8>>, DecompressError> {
+	let footer = match Footer::from_data(data) {
+		Some(footer) => footer,
+		None => return Ok(None),
+	};
+
+	// Check the footer
+	let footer_size = usize::from(footer.footer_size);
+	if !(MIN_FOOTER_SIZE..=MAX_FOOTER_SIZE).contains(&footer_size) {
+		return Err(DecompressError::InvalidFooterSize { footer_size });
+	}
+	let compressed_size = footer.compressed_size as usize;
+	if !(footer_size..=data.len()).contains(&compressed_size) {
+		return Err(DecompressError::InvalidCompressedSize { compressed_size });
+	}
+	let decompressed_size = u32::try_from(data.len())
+		.ok()
+		.and_then(|size| size.checked_add(footer.extra_size))
+		.ok_or(DecompressError::TooBig)? as usize;
+
+	// Then decompress the tail, from the end
+	let head_size = data.len() - compressed_size;
+	let tail = data[head_size..data.len() - footer_size]
+		.iter()
+		.rev()
+		.copied()
+		.collect::<Vec<_>>();
+	let mut reader = tail.as_slice();
+	let mut output = vec![0; decompressed_size];
+	output[..head_size].copy_from_slice(&data[..head_size]);
+	let mut pos = decompressed_size;
+	'outer: while let Ok(flags) = reader.read_u8() {
+		for bit in (0..8).rev() {
+			// Note: The last flags may have unused bits, so we stop once the tail is over
+			if reader.is_empty() {
+				break 'outer;
+			}
+
+			match (flags >> bit) & 0x1 != 0 {
+				true => {
+					let first = reader.read_u8().map_err(DecompressError::ReadData)?;
+					let (offset, len) = Blz::read_match(first, &mut reader).map_err(DecompressError::ReadData)?;
+					if pos + offset > decompressed_size || pos < head_size + len {
+						return Err(DecompressError::InvalidMatch { offset, len, pos });
+					}
+
+					for _ in 0..len {
+						pos -= 1;
+						output[pos] = output[pos + offset];
+					}
+				},
+				false => {
+					let byte = reader.read_u8().map_err(DecompressError::ReadData)?;
+					if pos == head_size {
+						return Err(DecompressError::TooMuchData);
+					}
+
+					pos -= 1;
+					output[pos] = byte;
+				},
+			}
+		}
+	}
+
+	if pos != head_size {
+		return Err(DecompressError::TooLittleData {
+			missing: pos - head_size,
+		});
+	}
+
+	Ok(Some(output))
+}
+
+/// Compresses data.
+///
+/// The first `min_head_size` bytes are always left uncompressed, and the head is expanded
+/// as necessary so that the data may be decompressed in-place.
+///
+/// Returns `None` if the data isn't compressible.
+pub fn compress(data: &[u8], min_head_size: usize) -> Result<Option<Vec<u8>>, CompressError> {
+	let tail = match data.get(min_head_size..) {
+		Some(tail) => tail.iter().rev().copied().collect::<Vec<_>>(),
+		None => return Ok(None),
+	};
+	let tokens = lz::parse::<Blz>(&tail, Mode::Optimal);
+
+	// Find the number of tokens that saves the most space.
+	// Note: When decompressing in-place, the tail is read and written from the end, and so
+	//       at any point the remaining compressed size must not be more than the remaining
+	//       decompressed size, else the unread data would be overwritten. This is only
+	//       guaranteed when the tail ends at the maximum savings, which is what we want anyway.
+	let mut compressed_size = 0;
+	let mut decompressed_size = 0;
+	let mut best_savings = 0;
+	let mut best_tokens_len = 0;
+	for (idx, &token) in tokens.iter().enumerate() {
+		if idx % 8 == 0 {
+			compressed_size += 1;
+		}
+		match token {
+			Token::Literal(_) => {
+				compressed_size += 1;
+				decompressed_size += 1;
+			},
+			Token::Match { len, .. } => {
+				compressed_size += Blz::match_size(len);
+				decompressed_size += len;
+			},
+		}
+
+		let savings = decompressed_size as isize - compressed_size as isize;
+		if savings > best_savings {
+			best_savings = savings;
+			best_tokens_len = idx + 1;
+		}
+	}
+
+	// Then write the head and reversed tail
+	let tokens = &tokens[..best_tokens_len];
+	let tail_size = tokens
+		.iter()
+		.map(|token| match *token {
+			Token::Literal(_) => 1,
+			Token::Match { len, .. } => len,
+		})
+		.sum::<usize>();
+	let mut compressed_tail = vec![];
+	lz::write_tokens::<Blz, _>(tokens, &mut compressed_tail).expect("Writing to a `Vec` can't fail");
+	compressed_tail.reverse();
+
+	let head_size = data.len() - tail_size;
+	let mut output = Vec::with_capacity(head_size + compressed_tail.len() + MAX_FOOTER_SIZE);
+	output.extend_from_slice(&data[..head_size]);
+	output.extend_from_slice(&compressed_tail);
+
+	// Finally pad to a multiple of 4 and write the footer
+	let padding = (4 - output.len() % 4) % 4;
+	output.resize(output.len() + padding, 0xff);
+	let footer_size = MIN_FOOTER_SIZE + padding;
+	if output.len() + MIN_FOOTER_SIZE >= data.len() {
+		return Ok(None);
+	}
+	let footer = Footer {
+		compressed_size: u32::try_from(compressed_tail.len() + footer_size)
+			.ok()
+			.filter(|&size| size < 1 << 24)
+			.ok_or(CompressError::TooBig)?,
+		footer_size:     footer_size as u8,
+		extra_size:      (data.len() - (output.len() + MIN_FOOTER_SIZE)) as u32,
+	};
+	output.extend_from_slice(&footer.to_bytes());
+
+	Ok(Some(output))
+}
+
+/// BLZ match encoding
+struct Blz;
+
+impl MatchEncoding for Blz {
+	const LEN_RANGES: &'static [(usize, usize, usize)] = &[(3, 0x12, 2)];
+	const MAX_OFFSET: usize = 0x1002;
+	const MIN_OFFSET: usize = 3;
+
+	fn read_match<R: io::Read>(first: u8, reader: &mut R) -> Result<(usize, usize), io::Error> {
+		let second = reader.read_u8()?;
+		let len = usize::from(first >> 4) + 3;
+		let offset = (usize::from(first & 0xf) << 8 | usize::from(second)) + 3;
+
+		Ok((offset, len))
+	}
+
+	fn write_match(offset: usize, len: usize, bytes: &mut Vec<u8>) {
+		let disp = offset - 3;
+		bytes.push(((len - 3) << 4 | disp >> 8) as u8);
+		bytes.push(disp as u8);
+	}
+}
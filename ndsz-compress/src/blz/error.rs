@@ -0,0 +1,48 @@
+//! Errors
+
+// Imports
+use std::io;
+
+/// Error for [`decompress`](super::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Invalid footer size
+	#[error("Invalid footer size: {footer_size:#x}")]
+	InvalidFooterSize { footer_size: usize },
+
+	/// Invalid compressed size
+	#[error("Invalid compressed size: {compressed_size:#x}")]
+	InvalidCompressedSize { compressed_size: usize },
+
+	/// Decompressed data too big
+	#[error("Decompressed data too big")]
+	TooBig,
+
+	/// Unable to read data
+	#[error("Unable to read data")]
+	ReadData(#[source] io::Error),
+
+	/// Invalid match
+	#[error("Invalid match at {pos:#x} with offset {offset:#x} and length {len:#x}")]
+	InvalidMatch {
+		offset: usize,
+		len:    usize,
+		pos:    usize,
+	},
+
+	/// Data decompressed past the head
+	#[error("Data decompressed past the head")]
+	TooMuchData,
+
+	/// Data didn't decompress up to the head
+	#[error("Data didn't decompress up to the head, missing {missing:#x} bytes")]
+	TooLittleData { missing: usize },
+}
+
+/// Error for [`compress`](super::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Compressed tail too big
+	#[error("Compressed tail too big")]
+	TooBig,
+}
@@ -0,0 +1,86 @@
+//! Tests
+
+// Imports
+use {super::Footer, crate::test_data};
+
+/// Compresses `data` and checks it decompresses back to it, returning the compressed data
+fn round_trip(data: &[u8], min_head_size: usize) -> Vec<u8> {
+	let compressed = super::compress(data, min_head_size)
+		.expect("Unable to compress")
+		.expect("Data wasn't compressible");
+	assert!(compressed.len() < data.len());
+	assert_eq!(compressed[..min_head_size], data[..min_head_size]);
+
+	let decompressed = super::decompress(&compressed)
+		.expect("Unable to decompress")
+		.expect("Data wasn't compressed");
+	assert_eq!(decompressed, data);
+
+	compressed
+}
+
+#[test]
+fn compress_incompressible() {
+	assert_eq!(super::compress(&[], 0).expect("Unable to compress"), None);
+	assert_eq!(super::compress(&[0x42], 0).expect("Unable to compress"), None);
+	assert_eq!(
+		super::compress(&test_data::noise(0x2000), 0).expect("Unable to compress"),
+		None
+	);
+	assert_eq!(super::compress(&[0; 0x10], 0x20).expect("Unable to compress"), None);
+}
+
+#[test]
+fn round_trip_long_run() {
+	self::round_trip(&[0xaa; 0x10000], 0);
+}
+
+#[test]
+fn round_trip_text() {
+	self::round_trip(&test_data::text(0x8000), 0);
+}
+
+#[test]
+fn round_trip_head() {
+	let data = [&test_data::noise(0x4000)[..], &test_data::text(0x4000)].concat();
+	self::round_trip(&data, 0x4000);
+	self::round_trip(&data, 0x100);
+}
+
+#[test]
+fn round_trip_footer_padding() {
+	// Note: Check all paddings before the footer are handled.
+	for len in 0x100..0x104 {
+		let compressed = self::round_trip(&test_data::text(len), 0);
+		let footer = Footer::from_data(&compressed).expect("Missing footer");
+		assert!((super::MIN_FOOTER_SIZE..=super::MAX_FOOTER_SIZE).contains(&usize::from(footer.footer_size)));
+	}
+}
+
+#[test]
+fn decompress_uncompressed() {
+	let data = [
+		&[0x42; 0x10][..],
+		&Footer {
+			compressed_size: 0,
+			footer_size:     0,
+			extra_size:      0,
+		}
+		.to_bytes(),
+	]
+	.concat();
+	assert_eq!(super::decompress(&data).expect("Unable to decompress"), None);
+}
+
+#[test]
+fn decompress_known() {
+	// Note: The tail is `xyz`, followed by matches of `0x12` and `3` bytes, stored reversed.
+	let compressed = [
+		b'H', b'E', b'A', b'D', 0x00, 0x00, 0x00, 0xf0, b'x', b'y', b'z', 0x18, 0x10, 0x00, 0x00, 0x08, 0x08, 0x00,
+		0x00, 0x00,
+	];
+	let decompressed = super::decompress(&compressed)
+		.expect("Unable to decompress")
+		.expect("Data wasn't compressed");
+	assert_eq!(decompressed, [&b"HEAD"[..], &b"xyz".repeat(8)].concat());
+}
@@ -0,0 +1,116 @@
+//! Compression auto-detection.
+//!
+//! All formats share the same [`Header`], so the format can be detected from it's kind.
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::{diff, huffman, lz, lz10, lz11, rle, Header},
+	std::io,
+};
+
+/// Compression
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Compression {
+	/// LZ10
+	Lz10,
+
+	/// LZ11
+	Lz11,
+
+	/// Huffman, with 4-bit symbols
+	Huffman4,
+
+	/// Huffman, with 8-bit symbols
+	Huffman8,
+
+	/// RLE
+	Rle,
+
+	/// 8-bit differential filter
+	Diff8,
+
+	/// 16-bit differential filter
+	Diff16,
+}
+
+impl Compression {
+	/// Returns the compression from it's kind, in the header
+	#[must_use]
+	pub const fn from_kind(kind: u8) -> Option<Self> {
+		match kind {
+			lz10::KIND => Some(Self::Lz10),
+			lz11::KIND => Some(Self::Lz11),
+			0x24 => Some(Self::Huffman4),
+			0x28 => Some(Self::Huffman8),
+			rle::KIND => Some(Self::Rle),
+			0x81 => Some(Self::Diff8),
+			0x82 => Some(Self::Diff16),
+			_ => None,
+		}
+	}
+
+	/// Returns the kind, in the header, of this compression
+	#[must_use]
+	pub const fn kind(self) -> u8 {
+		match self {
+			Self::Lz10 => lz10::KIND,
+			Self::Lz11 => lz11::KIND,
+			Self::Huffman4 => huffman::SymbolSize::Bits4.kind(),
+			Self::Huffman8 => huffman::SymbolSize::Bits8.kind(),
+			Self::Rle => rle::KIND,
+			Self::Diff8 => diff::UnitSize::Bits8.kind(),
+			Self::Diff16 => diff::UnitSize::Bits16.kind(),
+		}
+	}
+
+	/// Compresses data into a writer.
+	///
+	/// LZ compressions use [`lz::Mode::Optimal`].
+	pub fn compress<W: io::Write>(self, data: &[u8], writer: &mut W) -> Result<(), CompressError> {
+		match self {
+			Self::Lz10 => lz10::compress(data, writer, lz::Mode::Optimal).map_err(CompressError::Lz),
+			Self::Lz11 => lz11::compress(data, writer, lz::Mode::Optimal).map_err(CompressError::Lz),
+			Self::Huffman4 =>
+				huffman::compress(data, writer, huffman::SymbolSize::Bits4).map_err(CompressError::Huffman),
+			Self::Huffman8 =>
+				huffman::compress(data, writer, huffman::SymbolSize::Bits8).map_err(CompressError::Huffman),
+			Self::Rle => rle::compress(data, writer).map_err(CompressError::Rle),
+			Self::Diff8 => diff::compress(data, writer, diff::UnitSize::Bits8).map_err(CompressError::Diff),
+			Self::Diff16 => diff::compress(data, writer, diff::UnitSize::Bits16).map_err(CompressError::Diff),
+		}
+	}
+}
+
+/// Decompresses data from a reader into a writer, detecting the compression from the header.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<Compression, DecompressError> {
+	let header = Header::from_reader(reader).map_err(DecompressError::ReadHeader)?;
+	let compression = Compression::from_kind(header.kind).ok_or(DecompressError::UnknownKind { kind: header.kind })?;
+
+	match compression {
+		Compression::Lz10 => lz10::decompress_data(header, reader, writer).map_err(DecompressError::Lz)?,
+		Compression::Lz11 => lz11::decompress_data(header, reader, writer).map_err(DecompressError::Lz)?,
+		Compression::Huffman4 => huffman::decompress_data(header, huffman::SymbolSize::Bits4, reader, writer)
+			.map_err(DecompressError::Huffman)?,
+		Compression::Huffman8 => huffman::decompress_data(header, huffman::SymbolSize::Bits8, reader, writer)
+			.map_err(DecompressError::Huffman)?,
+		Compression::Rle => rle::decompress_data(header, reader, writer).map_err(DecompressError::Rle)?,
+		Compression::Diff8 => {
+			diff::decompress_data(header, diff::UnitSize::Bits8, reader, writer).map_err(DecompressError::Diff)?;
+		},
+		Compression::Diff16 => {
+			diff::decompress_data(header, diff::UnitSize::Bits16, reader, writer).map_err(DecompressError::Diff)?;
+		},
+	}
+
+	Ok(compression)
+}
@@ -0,0 +1,52 @@
+//! Errors
+
+// Imports
+use crate::{diff, header, huffman, lz, rle};
+
+/// Error for [`decompress`](super::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] header::FromReaderError),
+
+	/// Unknown kind
+	#[error("Unknown kind: {kind:#x}")]
+	UnknownKind { kind: u8 },
+
+	/// Unable to decompress lz
+	#[error("Unable to decompress lz")]
+	Lz(#[source] lz::DecompressError),
+
+	/// Unable to decompress huffman
+	#[error("Unable to decompress huffman")]
+	Huffman(#[source] huffman::DecompressError),
+
+	/// Unable to decompress rle
+	#[error("Unable to decompress rle")]
+	Rle(#[source] rle::DecompressError),
+
+	/// Unable to decompress diff
+	#[error("Unable to decompress diff")]
+	Diff(#[source] diff::DecompressError),
+}
+
+/// Error for [`Compression::compress`](super::Compression::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Unable to compress lz
+	#[error("Unable to compress lz")]
+	Lz(#[source] lz::CompressError),
+
+	/// Unable to compress huffman
+	#[error("Unable to compress huffman")]
+	Huffman(#[source] huffman::CompressError),
+
+	/// Unable to compress rle
+	#[error("Unable to compress rle")]
+	Rle(#[source] rle::CompressError),
+
+	/// Unable to compress diff
+	#[error("Unable to compress diff")]
+	Diff(#[source] diff::CompressError),
+}
@@ -0,0 +1,130 @@
+//! Differential filter.
+//!
+//! Each unit, of either 8 or 16 bits, is stored as the difference from the previous one.
+
+// Modules
+mod error;
+#[cfg(test)]
+mod test;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::{window::Window, Header},
+	byteorder::{LittleEndian, ReadBytesExt},
+	std::{convert::TryFrom, io},
+};
+
+/// Unit size
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum UnitSize {
+	/// 8-bit
+	Bits8,
+
+	/// 16-bit
+	Bits16,
+}
+
+impl UnitSize {
+	/// Returns the unit size from it's kind, in the header
+	#[must_use]
+	pub const fn from_kind(kind: u8) -> Option<Self> {
+		match kind {
+			0x81 => Some(Self::Bits8),
+			0x82 => Some(Self::Bits16),
+			_ => None,
+		}
+	}
+
+	/// Returns the kind, in the header, of this unit size
+	#[must_use]
+	pub const fn kind(self) -> u8 {
+		match self {
+			Self::Bits8 => 0x81,
+			Self::Bits16 => 0x82,
+		}
+	}
+}
+
+/// Decompresses data from a reader into a writer.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<(), DecompressError> {
+	let header = Header::from_reader(reader).map_err(DecompressError::ReadHeader)?;
+	let unit_size = UnitSize::from_kind(header.kind).ok_or(DecompressError::WrongKind { kind: header.kind })?;
+
+	self::decompress_data(header, unit_size, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<R: io::Read, W: io::Write>(
+	header: Header,
+	unit_size: UnitSize,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	let decompressed_size = u64::from(header.decompressed_size);
+	let mut window = Window::new(writer);
+	match unit_size {
+		UnitSize::Bits8 => {
+			let mut value = 0_u8;
+			while window.len() < decompressed_size {
+				value = value.wrapping_add(reader.read_u8().map_err(DecompressError::ReadData)?);
+				window.push(value).map_err(DecompressError::WriteData)?;
+			}
+		},
+		UnitSize::Bits16 => {
+			let mut value = 0_u16;
+			while window.len() < decompressed_size {
+				value = value.wrapping_add(reader.read_u16::<LittleEndian>().map_err(DecompressError::ReadData)?);
+
+				// Note: If the size isn't a multiple of 2, we ignore the last byte
+				for byte in value.to_le_bytes() {
+					if window.len() < decompressed_size {
+						window.push(byte).map_err(DecompressError::WriteData)?;
+					}
+				}
+			}
+		},
+	}
+
+	window.finish().map_err(DecompressError::WriteData)
+}
+
+/// Compresses data into a writer.
+///
+/// When using [`UnitSize::Bits16`], the data size must be a multiple of 2.
+pub fn compress<W: io::Write>(data: &[u8], writer: &mut W, unit_size: UnitSize) -> Result<(), CompressError> {
+	let decompressed_size = u32::try_from(data.len()).map_err(|_| CompressError::TooBig)?;
+	let header = Header {
+		kind: unit_size.kind(),
+		decompressed_size,
+	};
+
+	let output = match unit_size {
+		UnitSize::Bits8 => data
+			.iter()
+			.scan(0_u8, |prev, &value| {
+				Some(value.wrapping_sub(std::mem::replace(prev, value)))
+			})
+			.collect::<Vec<_>>(),
+		UnitSize::Bits16 => {
+			if !data.len().is_multiple_of(2) {
+				return Err(CompressError::UnalignedSize);
+			}
+
+			data.chunks_exact(2)
+				.map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+				.scan(0_u16, |prev, value| {
+					Some(value.wrapping_sub(std::mem::replace(prev, value)))
+				})
+				.flat_map(u16::to_le_bytes)
+				.collect()
+		},
+	};
+
+	header.to_writer(writer).map_err(CompressError::WriteHeader)?;
+	writer.write_all(&output).map_err(CompressError::WriteData)
+}
@@ -0,0 +1,62 @@
+//! Tests
+
+// Imports
+use {
+	super::{CompressError, UnitSize},
+	crate::test_data,
+	std::io,
+};
+
+/// Compresses `data` with `unit_size` and checks it decompresses back to it
+fn round_trip(data: &[u8], unit_size: UnitSize) {
+	let mut compressed = vec![];
+	super::compress(data, &mut compressed, unit_size).expect("Unable to compress");
+
+	let mut decompressed = vec![];
+	super::decompress(&mut io::Cursor::new(&compressed), &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, data);
+}
+
+#[test]
+fn round_trip_empty() {
+	self::round_trip(&[], UnitSize::Bits8);
+	self::round_trip(&[], UnitSize::Bits16);
+}
+
+#[test]
+fn round_trip_one_unit() {
+	self::round_trip(&[0x42], UnitSize::Bits8);
+	self::round_trip(&[0x42, 0x43], UnitSize::Bits16);
+}
+
+#[test]
+fn round_trip_noise() {
+	let data = test_data::noise(0x2000);
+	self::round_trip(&data, UnitSize::Bits8);
+	self::round_trip(&data, UnitSize::Bits16);
+}
+
+#[test]
+fn compress_unaligned_bits16() {
+	let mut compressed = vec![];
+	assert!(matches!(
+		super::compress(&[0x1, 0x2, 0x3], &mut compressed, UnitSize::Bits16),
+		Err(CompressError::UnalignedSize)
+	));
+}
+
+#[test]
+fn decompress_known_bits8() {
+	let compressed = [0x81, 0x04, 0x00, 0x00, 0x01, 0x01, 0x01, 0xfe];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, [0x01, 0x02, 0x03, 0x01]);
+}
+
+#[test]
+fn decompress_known_bits16() {
+	let compressed = [0x82, 0x04, 0x00, 0x00, 0x34, 0x12, 0xff, 0xff];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, [0x34, 0x12, 0x33, 0x12]);
+}
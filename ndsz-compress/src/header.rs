@@ -0,0 +1,69 @@
+//! Compression header
+
+// Modules
+mod error;
+
+// Exports
+pub use error::{FromReaderError, ToWriterError};
+
+// Imports
+use {
+	byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt},
+	ndsz_util::ReadByteArray,
+	std::io,
+};
+
+/// Compression header.
+///
+/// Sizes that don't fit in 24 bits are stored as a 24-bit `0`, followed by the full 32-bit size.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Header {
+	/// Kind
+	pub kind: u8,
+
+	/// Decompressed size
+	pub decompressed_size: u32,
+}
+
+impl Header {
+	/// Maximum size that can be stored in the short header
+	pub const MAX_SHORT_SIZE: u32 = 0xff_ffff;
+
+	/// Reads a header from a reader
+	pub fn from_reader<R: io::Read>(reader: &mut R) -> Result<Self, FromReaderError> {
+		let bytes = reader.read_byte_array::<0x4>().map_err(FromReaderError::ReadHeader)?;
+		let kind = bytes[0];
+		let decompressed_size = match LittleEndian::read_u24(&bytes[1..]) {
+			0 => reader
+				.read_u32::<LittleEndian>()
+				.map_err(FromReaderError::ReadExtendedSize)?,
+			size => size,
+		};
+
+		Ok(Self {
+			kind,
+			decompressed_size,
+		})
+	}
+
+	/// Writes this header to a writer
+	pub fn to_writer<W: io::Write>(self, writer: &mut W) -> Result<(), ToWriterError> {
+		let mut bytes = [self.kind, 0, 0, 0];
+
+		// Note: A size of `0` must also use the extended size, else it would be read as one.
+		match self.decompressed_size {
+			1..=Self::MAX_SHORT_SIZE => {
+				LittleEndian::write_u24(&mut bytes[1..], self.decompressed_size);
+				writer.write_all(&bytes).map_err(ToWriterError::WriteHeader)?;
+			},
+			_ => {
+				writer.write_all(&bytes).map_err(ToWriterError::WriteHeader)?;
+				writer
+					.write_u32::<LittleEndian>(self.decompressed_size)
+					.map_err(ToWriterError::WriteExtendedSize)?;
+			},
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,28 @@
+//! Errors
+
+// Imports
+use std::io;
+
+/// Error for [`Header::from_reader`](super::Header::from_reader)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] io::Error),
+
+	/// Unable to read extended size
+	#[error("Unable to read extended size")]
+	ReadExtendedSize(#[source] io::Error),
+}
+
+/// Error for [`Header::to_writer`](super::Header::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] io::Error),
+
+	/// Unable to write extended size
+	#[error("Unable to write extended size")]
+	WriteExtendedSize(#[source] io::Error),
+}
@@ -0,0 +1,343 @@
+//! Huffman compression.
+//!
+//! Data is split into 4 or 8-bit symbols, each encoded by a path within a tree,
+//! stored before the data.
+
+// Modules
+mod error;
+#[cfg(test)]
+mod test;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::{window::Window, Header},
+	byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+	std::{cmp::Reverse, collections::BinaryHeap, convert::TryFrom, io},
+};
+
+/// Symbol size
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SymbolSize {
+	/// 4-bit.
+	///
+	/// The low nibble of each byte comes first.
+	Bits4,
+
+	/// 8-bit
+	Bits8,
+}
+
+impl SymbolSize {
+	/// Returns the symbol size from it's kind, in the header
+	#[must_use]
+	pub const fn from_kind(kind: u8) -> Option<Self> {
+		match kind {
+			0x24 => Some(Self::Bits4),
+			0x28 => Some(Self::Bits8),
+			_ => None,
+		}
+	}
+
+	/// Returns the kind, in the header, of this symbol size
+	#[must_use]
+	pub const fn kind(self) -> u8 {
+		match self {
+			Self::Bits4 => 0x24,
+			Self::Bits8 => 0x28,
+		}
+	}
+
+	/// Returns the number of symbols
+	const fn symbols_len(self) -> usize {
+		match self {
+			Self::Bits4 => 0x10,
+			Self::Bits8 => 0x100,
+		}
+	}
+}
+
+/// Node offset mask
+const NODE_OFFSET_MASK: u8 = 0x3f;
+
+/// Node flag for child 0 being a symbol
+const NODE_CHILD0_SYMBOL: u8 = 0x80;
+
+/// Node flag for child 1 being a symbol
+const NODE_CHILD1_SYMBOL: u8 = 0x40;
+
+/// Decompresses data from a reader into a writer.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<(), DecompressError> {
+	let header = Header::from_reader(reader).map_err(DecompressError::ReadHeader)?;
+	let symbol_size = SymbolSize::from_kind(header.kind).ok_or(DecompressError::WrongKind { kind: header.kind })?;
+
+	self::decompress_data(header, symbol_size, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<R: io::Read, W: io::Write>(
+	header: Header,
+	symbol_size: SymbolSize,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	// Read the tree.
+	// Note: The tree includes it's size at index 0, as offsets are relative to it.
+	let tree_size = reader.read_u8().map_err(DecompressError::ReadTree)?;
+	let mut tree = vec![0; (usize::from(tree_size) + 1) * 2];
+	tree[0] = tree_size;
+	reader.read_exact(&mut tree[1..]).map_err(DecompressError::ReadTree)?;
+
+	// Then decode all symbols
+	let decompressed_size = u64::from(header.decompressed_size);
+	let mut window = Window::new(writer);
+	let mut node_idx = 1;
+	let mut low_nibble = None;
+	while window.len() < decompressed_size {
+		let bits = reader.read_u32::<LittleEndian>().map_err(DecompressError::ReadData)?;
+		for bit in (0..32).rev() {
+			let node = tree[node_idx];
+			let child0_idx = (node_idx & !1) + usize::from(node & NODE_OFFSET_MASK) * 2 + 2;
+			let (child_idx, is_symbol) = match (bits >> bit) & 0x1 != 0 {
+				false => (child0_idx, node & NODE_CHILD0_SYMBOL != 0),
+				true => (child0_idx + 1, node & NODE_CHILD1_SYMBOL != 0),
+			};
+			let child = *tree
+				.get(child_idx)
+				.ok_or(DecompressError::InvalidNode { idx: node_idx })?;
+
+			if !is_symbol {
+				node_idx = child_idx;
+				continue;
+			}
+			node_idx = 1;
+
+			match symbol_size {
+				SymbolSize::Bits4 => match low_nibble.take() {
+					Some(low_nibble) => window
+						.push(low_nibble | (child & 0xf) << 4)
+						.map_err(DecompressError::WriteData)?,
+					None => low_nibble = Some(child & 0xf),
+				},
+				SymbolSize::Bits8 => window.push(child).map_err(DecompressError::WriteData)?,
+			}
+
+			if window.len() >= decompressed_size {
+				break;
+			}
+		}
+	}
+
+	window.finish().map_err(DecompressError::WriteData)
+}
+
+/// Compresses data into a writer.
+///
+/// Note: The whole data is required upfront, as the header contains it's size.
+pub fn compress<W: io::Write>(data: &[u8], writer: &mut W, symbol_size: SymbolSize) -> Result<(), CompressError> {
+	let decompressed_size = u32::try_from(data.len()).map_err(|_| CompressError::TooBig)?;
+	let header = Header {
+		kind: symbol_size.kind(),
+		decompressed_size,
+	};
+
+	// Build the tree and get the code of each symbol
+	let symbols = match symbol_size {
+		SymbolSize::Bits4 => data.iter().flat_map(|&byte| [byte & 0xf, byte >> 4]).collect(),
+		SymbolSize::Bits8 => data.to_vec(),
+	};
+	let nodes = self::build_tree(&symbols, symbol_size);
+	let mut codes = vec![vec![]; symbol_size.symbols_len()];
+	self::collect_codes(&nodes, nodes.len() - 1, &mut vec![], &mut codes);
+	let tree = self::layout_tree(&nodes)?;
+
+	// Then write everything
+	header.to_writer(writer).map_err(CompressError::WriteHeader)?;
+	writer.write_all(&tree).map_err(CompressError::WriteTree)?;
+
+	let mut output = vec![];
+	let mut bits = 0_u32;
+	let mut bits_len = 0;
+	for &symbol in &symbols {
+		for &bit in &codes[usize::from(symbol)] {
+			bits |= u32::from(bit) << (31 - bits_len);
+			bits_len += 1;
+			if bits_len == 32 {
+				output
+					.write_u32::<LittleEndian>(bits)
+					.expect("Writing to a `Vec` can't fail");
+				bits = 0;
+				bits_len = 0;
+			}
+		}
+	}
+	if bits_len != 0 {
+		output
+			.write_u32::<LittleEndian>(bits)
+			.expect("Writing to a `Vec` can't fail");
+	}
+
+	writer.write_all(&output).map_err(CompressError::WriteData)
+}
+
+/// Tree node
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Node {
+	/// Symbol
+	Symbol { symbol: u8 },
+
+	/// Branch
+	Branch {
+		children:    [usize; 2],
+		symbols_len: usize,
+	},
+}
+
+impl Node {
+	/// Returns the number of symbols within this node
+	const fn symbols_len(self) -> usize {
+		match self {
+			Self::Symbol { .. } => 1,
+			Self::Branch { symbols_len, .. } => symbols_len,
+		}
+	}
+}
+
+/// Builds the huffman tree for `symbols`.
+///
+/// The root is the last node.
+fn build_tree(symbols: &[u8], symbol_size: SymbolSize) -> Vec<Node> {
+	let mut counts = vec![0_u64; symbol_size.symbols_len()];
+	for &symbol in symbols {
+		counts[usize::from(symbol)] += 1;
+	}
+
+	// Note: The tree needs at least 2 symbols, so if we don't have them, we add unused ones.
+	let mut nodes = vec![];
+	let mut queue = BinaryHeap::new();
+	for (symbol, &count) in (0..=u8::MAX).zip(&counts) {
+		let unused_needed = 2_usize.saturating_sub(nodes.len()) > counts[usize::from(symbol)..].len() - 1;
+		if count != 0 || unused_needed {
+			queue.push(Reverse((count, nodes.len())));
+			nodes.push(Node::Symbol { symbol });
+		}
+	}
+
+	// Then join the 2 least common nodes until we only have the root
+	while let (Some(Reverse((lhs_count, lhs))), Some(Reverse((rhs_count, rhs)))) = (queue.pop(), queue.pop()) {
+		queue.push(Reverse((lhs_count + rhs_count, nodes.len())));
+		nodes.push(Node::Branch {
+			children:    [lhs, rhs],
+			symbols_len: nodes[lhs].symbols_len() + nodes[rhs].symbols_len(),
+		});
+	}
+
+	nodes
+}
+
+/// Collects the codes of all symbols within `node_idx`
+fn collect_codes(nodes: &[Node], node_idx: usize, code: &mut Vec<bool>, codes: &mut [Vec<bool>]) {
+	match nodes[node_idx] {
+		Node::Symbol { symbol } => codes[usize::from(symbol)] = code.clone(),
+		Node::Branch { children, .. } =>
+			for (&child_idx, bit) in children.iter().zip([false, true]) {
+				code.push(bit);
+				self::collect_codes(nodes, child_idx, code, codes);
+				code.pop();
+			},
+	}
+}
+
+/// Lays out the tree into bytes, including it's size.
+///
+/// Each branch's children must be at most [`NODE_OFFSET_MASK`] pairs after it's own pair,
+/// so instead of laying out the tree in order, we prefer branches with the fewest symbols,
+/// as these are quicker to finish, except when a branch is about to be out of range.
+fn layout_tree(nodes: &[Node]) -> Result<Vec<u8>, CompressError> {
+	/// Pending branch
+	struct Pending {
+		/// Node index
+		node_idx: usize,
+
+		/// Index within the tree
+		tree_idx: usize,
+
+		/// Last pair index it's children may be placed at
+		max_pair_idx: usize,
+	}
+
+	// Note: Index 0 is the size, and index 1 is the root.
+	let mut tree = vec![0, 0];
+	let mut pending = vec![Pending {
+		node_idx:     nodes.len() - 1,
+		tree_idx:     1,
+		max_pair_idx: usize::from(NODE_OFFSET_MASK),
+	}];
+	while !pending.is_empty() {
+		let pair_idx = (tree.len() - 2) / 2;
+
+		// Check if we can place the branch with the fewest symbols without any other
+		// branch going out of range, else place the one closest to going out of range.
+		pending.sort_by_key(|pending| pending.max_pair_idx);
+		let fewest_symbols_pos = (0..pending.len())
+			.min_by_key(|&pos| nodes[pending[pos].node_idx].symbols_len())
+			.expect("Pending branches weren't empty");
+		let can_place_fewest_symbols = (0..pending.len())
+			.filter(|&pos| pos != fewest_symbols_pos)
+			.enumerate()
+			.all(|(order, pos)| pending[pos].max_pair_idx > pair_idx + order);
+		let cur = match can_place_fewest_symbols {
+			true => pending.remove(fewest_symbols_pos),
+			false => pending.remove(0),
+		};
+		if pair_idx > cur.max_pair_idx {
+			return Err(CompressError::TreeTooBig);
+		}
+
+		// Then place it's children and update it
+		let children = match nodes[cur.node_idx] {
+			Node::Branch { children, .. } => children,
+			Node::Symbol { .. } => unreachable!("Only branches are pending"),
+		};
+		// Note: The root's offset is relative to the first pair, while all others are relative to the pair after theirs.
+		let offset = match cur.tree_idx {
+			1 => pair_idx,
+			_ => pair_idx - (cur.tree_idx - 2) / 2 - 1,
+		};
+		let mut node = u8::try_from(offset).expect("Offset was within range");
+		for ((child_idx, flag), tree_idx) in children
+			.iter()
+			.copied()
+			.zip([NODE_CHILD0_SYMBOL, NODE_CHILD1_SYMBOL])
+			.zip(tree.len()..)
+		{
+			match nodes[child_idx] {
+				Node::Symbol { symbol } => {
+					node |= flag;
+					tree.push(symbol);
+				},
+				Node::Branch { .. } => {
+					tree.push(0);
+					pending.push(Pending {
+						node_idx: child_idx,
+						tree_idx,
+						max_pair_idx: pair_idx + usize::from(NODE_OFFSET_MASK) + 1,
+					});
+				},
+			}
+		}
+		tree[cur.tree_idx] = node;
+	}
+
+	// Finally pad the tree so the data is 4-byte aligned and write the size
+	if !tree.len().is_multiple_of(4) {
+		tree.extend_from_slice(&[0, 0]);
+	}
+	tree[0] = u8::try_from(tree.len() / 2 - 1).map_err(|_| CompressError::TreeTooBig)?;
+
+	Ok(tree)
+}
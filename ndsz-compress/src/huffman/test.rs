@@ -0,0 +1,95 @@
+//! Tests
+
+// Imports
+use {super::SymbolSize, crate::test_data, std::io};
+
+/// Compresses `data` with `symbol_size` and checks it decompresses back to it
+fn round_trip(data: &[u8], symbol_size: SymbolSize) {
+	let mut compressed = vec![];
+	super::compress(data, &mut compressed, symbol_size).expect("Unable to compress");
+
+	let mut decompressed = vec![];
+	super::decompress(&mut io::Cursor::new(&compressed), &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, data);
+}
+
+/// Round trips `data` with all symbol sizes
+fn round_trip_all(data: &[u8]) {
+	self::round_trip(data, SymbolSize::Bits4);
+	self::round_trip(data, SymbolSize::Bits8);
+}
+
+#[test]
+fn round_trip_empty() {
+	self::round_trip_all(&[]);
+}
+
+#[test]
+fn round_trip_one_byte() {
+	self::round_trip_all(&[0x42]);
+}
+
+#[test]
+fn round_trip_single_symbol() {
+	self::round_trip_all(&[0x77; 0x100]);
+}
+
+#[test]
+fn round_trip_all_symbols() {
+	let data = (0..=u8::MAX).collect::<Vec<_>>();
+	self::round_trip_all(&data);
+}
+
+#[test]
+fn round_trip_all_symbols_skewed() {
+	// Note: Symbol counts grow exponentially, so the tree is as deep as possible.
+	let data = (0..=u8::MAX)
+		.flat_map(|symbol| {
+			let count = 1 << (symbol / 16);
+			std::iter::repeat_n(symbol, count)
+		})
+		.collect::<Vec<_>>();
+	self::round_trip_all(&data);
+}
+
+#[test]
+fn round_trip_text() {
+	self::round_trip_all(&test_data::text(0x8000));
+}
+
+#[test]
+fn round_trip_noise() {
+	self::round_trip_all(&test_data::noise(0x2000));
+}
+
+#[test]
+fn decompress_known_bits8() {
+	// Note: `a` is `0`, `b` is `10` and `c` is `11`.
+	let compressed = [
+		0x28, 0x03, 0x00, 0x00, 0x02, 0x80, b'a', 0xc0, b'b', b'c', 0x00, 0x00, 0x00, 0x58,
+	];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, b"abc");
+}
+
+#[test]
+fn decompress_known_bits4() {
+	// Note: `1` is `0` and `2` is `1`, with the low nibble first.
+	let compressed = [0x24, 0x01, 0x00, 0x00, 0x01, 0xc0, 0x01, 0x02, 0x00, 0x00, 0x00, 0x40];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, [0x21]);
+}
+
+#[test]
+fn compress_all_symbols_tree_size() {
+	// Note: With every symbol equally likely, all codes are 8 bits, so the data isn't compressed.
+	let data = (0..=u8::MAX).collect::<Vec<_>>();
+	let mut compressed = vec![];
+	super::compress(&data, &mut compressed, SymbolSize::Bits8).expect("Unable to compress");
+
+	let tree_size = usize::from(compressed[4]);
+	assert_eq!(tree_size, 0xff);
+	assert_eq!(compressed.len(), 4 + (tree_size + 1) * 2 + data.len());
+}
@@ -0,0 +1,25 @@
+//! Nintendo compression formats
+//!
+//! Adapted from `https://problemkaputt.de/gbatek.htm#biosdecompressionfunctions`
+
+// Modules
+pub mod blz;
+pub mod compression;
+pub mod diff;
+pub mod header;
+pub mod huffman;
+pub mod lz;
+pub mod lz10;
+pub mod lz11;
+pub mod reader;
+pub mod rle;
+#[cfg(test)]
+mod test_data;
+mod window;
+
+// Exports
+pub use self::{
+	compression::{decompress, Compression},
+	header::Header,
+	reader::DecompressReader,
+};
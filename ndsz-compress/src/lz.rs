@@ -0,0 +1,405 @@
+//! LZ77 compression.
+//!
+//! Shared by [`lz10`](crate::lz10) and [`lz11`](crate::lz11), which only differ in how
+//! matches are encoded.
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::{window::Window, Header},
+	byteorder::ReadBytesExt,
+	std::{convert::TryFrom, io},
+};
+
+/// Window size
+pub const WINDOW_SIZE: usize = 0x1000;
+
+/// Minimum match length
+pub const MIN_MATCH_LEN: usize = 3;
+
+/// Compression mode
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Mode {
+	/// Greedy.
+	///
+	/// Always uses the longest match available.
+	Greedy,
+
+	/// Optimal.
+	///
+	/// Chooses the sequence of literals and matches that results in the smallest output.
+	/// Slower than [`Mode::Greedy`].
+	Optimal,
+}
+
+/// Match encoding
+pub(crate) trait MatchEncoding {
+	/// All match length ranges, with the encoded size of matches within them.
+	///
+	/// Ranges must be contiguous, starting at [`MIN_MATCH_LEN`].
+	const LEN_RANGES: &'static [(usize, usize, usize)];
+
+	/// Minimum match offset
+	const MIN_OFFSET: usize = 1;
+
+	/// Maximum match offset
+	const MAX_OFFSET: usize = WINDOW_SIZE;
+
+	/// Reads a match, given it's first byte, returning it's offset and length
+	fn read_match<R: io::Read>(first: u8, reader: &mut R) -> Result<(usize, usize), io::Error>;
+
+	/// Writes a match
+	fn write_match(offset: usize, len: usize, bytes: &mut Vec<u8>);
+
+	/// Returns the maximum match length
+	fn max_match_len() -> usize {
+		let &(_, max_len, _) = Self::LEN_RANGES.last().expect("Must have at least 1 length range");
+		max_len
+	}
+
+	/// Returns the encoded size of a match of length `len`
+	fn match_size(len: usize) -> usize {
+		let &(_, _, size) = Self::LEN_RANGES
+			.iter()
+			.find(|&&(min_len, max_len, _)| (min_len..=max_len).contains(&len))
+			.expect("Match length was out of range");
+		size
+	}
+}
+
+/// Token
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Token {
+	/// Literal byte
+	Literal(u8),
+
+	/// Match
+	Match { offset: usize, len: usize },
+}
+
+/// Decompresses data with kind `kind` from a reader into a writer
+pub(crate) fn decompress<E: MatchEncoding, R: io::Read, W: io::Write>(
+	kind: u8,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	let header = Header::from_reader(reader).map_err(DecompressError::ReadHeader)?;
+	if header.kind != kind {
+		return Err(DecompressError::WrongKind { kind: header.kind });
+	}
+
+	self::decompress_data::<E, _, _>(header, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<E: MatchEncoding, R: io::Read, W: io::Write>(
+	header: Header,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	let decompressed_size = u64::from(header.decompressed_size);
+	let mut window = Window::new(writer);
+	while window.len() < decompressed_size {
+		let flags = reader.read_u8().map_err(DecompressError::ReadData)?;
+		for bit in (0..8).rev() {
+			let remaining = decompressed_size - window.len();
+			if remaining == 0 {
+				break;
+			}
+
+			match (flags >> bit) & 0x1 != 0 {
+				true => {
+					let first = reader.read_u8().map_err(DecompressError::ReadData)?;
+					let (offset, len) = E::read_match(first, reader).map_err(DecompressError::ReadData)?;
+					if !window.can_copy(offset) {
+						return Err(DecompressError::InvalidOffset {
+							offset,
+							pos: window.len(),
+						});
+					}
+
+					// Note: Matches may go past the end, in which case we ignore the rest
+					let len = usize::try_from(remaining).map_or(len, |remaining| len.min(remaining));
+					window.copy(offset, len).map_err(DecompressError::WriteData)?;
+				},
+				false => {
+					let byte = reader.read_u8().map_err(DecompressError::ReadData)?;
+					window.push(byte).map_err(DecompressError::WriteData)?;
+				},
+			}
+		}
+	}
+
+	window.finish().map_err(DecompressError::WriteData)
+}
+
+/// Compresses data into a writer, with kind `kind`
+pub(crate) fn compress<E: MatchEncoding, W: io::Write>(
+	kind: u8,
+	data: &[u8],
+	writer: &mut W,
+	mode: Mode,
+) -> Result<(), CompressError> {
+	let decompressed_size = u32::try_from(data.len()).map_err(|_| CompressError::TooBig)?;
+	let header = Header {
+		kind,
+		decompressed_size,
+	};
+	header.to_writer(writer).map_err(CompressError::WriteHeader)?;
+
+	let tokens = self::parse::<E>(data, mode);
+	self::write_tokens::<E, _>(&tokens, writer).map_err(CompressError::WriteData)
+}
+
+/// Parses `data` into tokens
+pub(crate) fn parse<E: MatchEncoding>(data: &[u8], mode: Mode) -> Vec<Token> {
+	let matches = self::longest_matches::<E>(data);
+	match mode {
+		Mode::Greedy => self::parse_greedy(data, &matches),
+		Mode::Optimal => self::parse_optimal::<E>(data, &matches),
+	}
+}
+
+/// Writes all tokens in blocks of 8, each preceded by their flags
+pub(crate) fn write_tokens<E: MatchEncoding, W: io::Write>(tokens: &[Token], writer: &mut W) -> Result<(), io::Error> {
+	let mut block = Vec::with_capacity(1 + 8 * 4);
+	for tokens in tokens.chunks(8) {
+		block.clear();
+		block.push(0);
+		for (idx, &token) in tokens.iter().enumerate() {
+			match token {
+				Token::Literal(byte) => block.push(byte),
+				Token::Match { offset, len } => {
+					block[0] |= 0x80 >> idx;
+					E::write_match(offset, len, &mut block);
+				},
+			}
+		}
+
+		writer.write_all(&block)?;
+	}
+
+	Ok(())
+}
+
+/// Parses `data` into tokens, always using the longest match
+fn parse_greedy(data: &[u8], matches: &[Match]) -> Vec<Token> {
+	let mut tokens = vec![];
+	let mut pos = 0;
+	while pos < data.len() {
+		let m = matches[pos];
+		match m.len >= MIN_MATCH_LEN {
+			true => {
+				tokens.push(Token::Match {
+					offset: m.offset,
+					len:    m.len,
+				});
+				pos += m.len;
+			},
+			false => {
+				tokens.push(Token::Literal(data[pos]));
+				pos += 1;
+			},
+		}
+	}
+
+	tokens
+}
+
+/// Parses `data` into the tokens with the smallest encoded size.
+///
+/// Since any prefix of a match is also a match, we only need the longest match at each
+/// position, and then choose, from the end, the cheapest length for each position.
+fn parse_optimal<E: MatchEncoding>(data: &[u8], matches: &[Match]) -> Vec<Token> {
+	// Note: Costs are in bits, to account for the flag bit of each token.
+	let mut costs = MinTree::new(data.len() + 1);
+	costs.set(data.len(), 0);
+	let mut choices = vec![0; data.len()];
+	for pos in (0..data.len()).rev() {
+		let mut best_cost = 8 + 1 + costs.get(pos + 1);
+		let mut best_len = 1;
+
+		let m = matches[pos];
+		for &(min_len, max_len, size) in E::LEN_RANGES {
+			let max_len = max_len.min(m.len);
+			if min_len > max_len {
+				break;
+			}
+
+			let (cost, end) = costs.min(pos + min_len, pos + max_len);
+			let cost = (size as u64) * 8 + 1 + cost;
+			if cost < best_cost {
+				best_cost = cost;
+				best_len = end - pos;
+			}
+		}
+
+		costs.set(pos, best_cost);
+		choices[pos] = best_len;
+	}
+
+	let mut tokens = vec![];
+	let mut pos = 0;
+	while pos < data.len() {
+		let len = choices[pos];
+		tokens.push(match len {
+			1 => Token::Literal(data[pos]),
+			_ => Token::Match {
+				offset: matches[pos].offset,
+				len,
+			},
+		});
+		pos += len;
+	}
+
+	tokens
+}
+
+/// Match
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+struct Match {
+	/// Offset
+	offset: usize,
+
+	/// Length
+	len: usize,
+}
+
+/// Hash table size, in bits
+const HASH_BITS: u32 = 15;
+
+/// Sentinel for no position in the hash chains
+const NO_POS: usize = usize::MAX;
+
+/// Finds the longest match, within the offsets and lengths of `E`, at each position of `data`.
+///
+/// Positions without a match of at least [`MIN_MATCH_LEN`] have a length of `0`.
+fn longest_matches<E: MatchEncoding>(data: &[u8]) -> Vec<Match> {
+	let hash = |pos: usize| {
+		let value = (usize::from(data[pos]) << 10) ^ (usize::from(data[pos + 1]) << 5) ^ usize::from(data[pos + 2]);
+		value & ((1 << HASH_BITS) - 1)
+	};
+	let match_len = |src: usize, dst: usize, start: usize, max_len: usize| {
+		start +
+			data[src + start..]
+				.iter()
+				.zip(&data[dst + start..dst + max_len])
+				.take_while(|(lhs, rhs)| lhs == rhs)
+				.count()
+	};
+
+	// Note: `heads` holds the last position of each hash and `prevs` the previous position with the same hash.
+	let mut heads = vec![NO_POS; 1 << HASH_BITS];
+	let mut prevs = vec![NO_POS; data.len()];
+	let mut matches = vec![Match::default(); data.len()];
+	for pos in 0..data.len().saturating_sub(MIN_MATCH_LEN - 1) {
+		let max_len = E::max_match_len().min(data.len() - pos);
+		let mut best = Match::default();
+
+		// Note: The previous match, shifted by 1, is still a match, which lets us
+		//       skip comparing most of it when in long runs.
+		if let Some(prev) = pos.checked_sub(1).map(|prev_pos| matches[prev_pos]) {
+			if prev.len > MIN_MATCH_LEN {
+				best = Match {
+					offset: prev.offset,
+					len:    match_len(pos - prev.offset, pos, prev.len - 1, max_len),
+				};
+			}
+		}
+
+		// Then go through all positions with the same hash, within the window
+		let min_pos = pos.saturating_sub(E::MAX_OFFSET);
+		let hash = hash(pos);
+		let mut cur_pos = heads[hash];
+		while cur_pos != NO_POS && cur_pos >= min_pos && best.len < max_len {
+			// Note: We only need to fully compare if it would be longer than the best match
+			if pos - cur_pos >= E::MIN_OFFSET && data[cur_pos + best.len] == data[pos + best.len] {
+				let len = match_len(cur_pos, pos, 0, max_len);
+				if len > best.len {
+					best = Match {
+						offset: pos - cur_pos,
+						len,
+					};
+				}
+			}
+
+			cur_pos = prevs[cur_pos];
+		}
+
+		if best.len >= MIN_MATCH_LEN {
+			matches[pos] = best;
+		}
+		prevs[pos] = heads[hash];
+		heads[hash] = pos;
+	}
+
+	matches
+}
+
+/// Minimum tree.
+///
+/// Segment tree of costs that allows finding the minimum cost within a range.
+struct MinTree {
+	/// Number of leaves
+	len: usize,
+
+	/// All nodes, with their cost and leaf index.
+	///
+	/// Node `1` is the root, and the leaves start at `len`.
+	nodes: Vec<(u64, usize)>,
+}
+
+impl MinTree {
+	/// Creates a new tree, with all costs as `u64::MAX`
+	fn new(len: usize) -> Self {
+		let mut nodes = vec![(u64::MAX, 0); 2 * len];
+		for (idx, node) in nodes[len..].iter_mut().enumerate() {
+			node.1 = idx;
+		}
+
+		Self { len, nodes }
+	}
+
+	/// Returns the cost of a leaf
+	fn get(&self, idx: usize) -> u64 {
+		self.nodes[self.len + idx].0
+	}
+
+	/// Sets the cost of a leaf
+	fn set(&mut self, idx: usize, cost: u64) {
+		let mut node = self.len + idx;
+		self.nodes[node].0 = cost;
+		while node > 1 {
+			node /= 2;
+			self.nodes[node] = self.nodes[2 * node].min(self.nodes[2 * node + 1]);
+		}
+	}
+
+	/// Returns the minimum cost, and it's leaf, within `start..=end`.
+	///
+	/// Ties are broken by the lowest leaf.
+	fn min(&self, start: usize, end: usize) -> (u64, usize) {
+		let mut min = (u64::MAX, end);
+		let mut start = self.len + start;
+		let mut end = self.len + end + 1;
+		while start < end {
+			if start % 2 == 1 {
+				min = min.min(self.nodes[start]);
+				start += 1;
+			}
+			if end % 2 == 1 {
+				end -= 1;
+				min = min.min(self.nodes[end]);
+			}
+			start /= 2;
+			end /= 2;
+		}
+
+		min
+	}
+}
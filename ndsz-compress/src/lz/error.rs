@@ -0,0 +1,44 @@
+//! Errors
+
+// Imports
+use {crate::header, std::io};
+
+/// Error for [`lz10::decompress`](crate::lz10::decompress) and [`lz11::decompress`](crate::lz11::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] header::FromReaderError),
+
+	/// Wrong kind
+	#[error("Wrong kind: {kind:#x}")]
+	WrongKind { kind: u8 },
+
+	/// Unable to read data
+	#[error("Unable to read data")]
+	ReadData(#[source] io::Error),
+
+	/// Invalid match offset
+	#[error("Invalid match offset {offset:#x} at {pos:#x}")]
+	InvalidOffset { offset: usize, pos: u64 },
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
+
+/// Error for [`lz10::compress`](crate::lz10::compress) and [`lz11::compress`](crate::lz11::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Data too big
+	#[error("Data too big")]
+	TooBig,
+
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] header::ToWriterError),
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
@@ -0,0 +1,64 @@
+//! LZ10 compression.
+//!
+//! LZ77 with matches of `3..=0x12` bytes, each encoded in 2 bytes.
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Imports
+use {
+	crate::{
+		lz::{self, CompressError, DecompressError, MatchEncoding, Mode},
+		Header,
+	},
+	byteorder::ReadBytesExt,
+	std::io,
+};
+
+/// Kind, in the header
+pub const KIND: u8 = 0x10;
+
+/// Decompresses data from a reader into a writer.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<(), DecompressError> {
+	lz::decompress::<Lz10, _, _>(KIND, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<R: io::Read, W: io::Write>(
+	header: Header,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	lz::decompress_data::<Lz10, _, _>(header, reader, writer)
+}
+
+/// Compresses data into a writer.
+///
+/// Note: The whole data is required upfront, as the header contains it's size.
+pub fn compress<W: io::Write>(data: &[u8], writer: &mut W, mode: Mode) -> Result<(), CompressError> {
+	lz::compress::<Lz10, _>(KIND, data, writer, mode)
+}
+
+/// LZ10 match encoding
+struct Lz10;
+
+impl MatchEncoding for Lz10 {
+	const LEN_RANGES: &'static [(usize, usize, usize)] = &[(3, 0x12, 2)];
+
+	fn read_match<R: io::Read>(first: u8, reader: &mut R) -> Result<(usize, usize), io::Error> {
+		let second = reader.read_u8()?;
+		let len = usize::from(first >> 4) + 3;
+		let offset = (usize::from(first & 0xf) << 8 | usize::from(second)) + 1;
+
+		Ok((offset, len))
+	}
+
+	fn write_match(offset: usize, len: usize, bytes: &mut Vec<u8>) {
+		let disp = offset - 1;
+		bytes.push(((len - 3) << 4 | disp >> 8) as u8);
+		bytes.push(disp as u8);
+	}
+}
@@ -0,0 +1,84 @@
+//! Tests
+
+// Imports
+use {
+	crate::{lz::Mode, test_data},
+	std::io,
+};
+
+/// Compresses `data` with `mode` and checks it decompresses back to it, returning the compressed data
+fn round_trip(data: &[u8], mode: Mode) -> Vec<u8> {
+	let mut compressed = vec![];
+	super::compress(data, &mut compressed, mode).expect("Unable to compress");
+
+	let mut decompressed = vec![];
+	super::decompress(&mut io::Cursor::new(&compressed), &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, data);
+
+	compressed
+}
+
+/// Round trips `data` with all modes, checking the optimal mode is never bigger
+fn round_trip_all(data: &[u8]) {
+	let greedy = self::round_trip(data, Mode::Greedy);
+	let optimal = self::round_trip(data, Mode::Optimal);
+	assert!(optimal.len() <= greedy.len());
+}
+
+#[test]
+fn round_trip_empty() {
+	self::round_trip_all(&[]);
+}
+
+#[test]
+fn round_trip_one_byte() {
+	self::round_trip_all(&[0x42]);
+}
+
+#[test]
+fn round_trip_long_run() {
+	self::round_trip_all(&[0xaa; 0x10000]);
+}
+
+#[test]
+fn round_trip_text() {
+	self::round_trip_all(&test_data::text(0x8000));
+}
+
+#[test]
+fn round_trip_noise() {
+	self::round_trip_all(&test_data::noise(0x2000));
+}
+
+#[test]
+fn round_trip_repeat_past_window() {
+	// Note: Only the second copy is far enough back to be out of the window.
+	let noise = test_data::noise(0x1000);
+	let data = [&noise[..], &noise[..], &[0; 0x10], &noise[..]].concat();
+	self::round_trip_all(&data);
+}
+
+#[test]
+fn compress_max_len_match() {
+	let mut compressed = vec![];
+	super::compress(&[b'a'; 1 + 0x12], &mut compressed, Mode::Greedy).expect("Unable to compress");
+	assert_eq!(compressed, [0x10, 0x13, 0x00, 0x00, 0x40, b'a', 0xf0, 0x00]);
+}
+
+#[test]
+fn decompress_known() {
+	let compressed = [0x10, 0x09, 0x00, 0x00, 0x10, b'a', b'b', b'c', 0x30, 0x02];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, b"abcabcabc");
+}
+
+#[test]
+fn decompress_invalid_offset() {
+	let compressed = [0x10, 0x04, 0x00, 0x00, 0x40, b'a', 0x00, 0x01];
+	let mut decompressed = vec![];
+	assert!(matches!(
+		super::decompress(&mut &compressed[..], &mut decompressed),
+		Err(super::DecompressError::InvalidOffset { offset: 2, pos: 1 })
+	));
+}
@@ -0,0 +1,91 @@
+//! LZ11 compression.
+//!
+//! LZ77 with matches of `3..=0x10110` bytes, encoded in 2, 3 or 4 bytes depending on their length.
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Imports
+use {
+	crate::{
+		lz::{self, CompressError, DecompressError, MatchEncoding, Mode},
+		Header,
+	},
+	byteorder::ReadBytesExt,
+	std::io,
+};
+
+/// Kind, in the header
+pub const KIND: u8 = 0x11;
+
+/// Decompresses data from a reader into a writer.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<(), DecompressError> {
+	lz::decompress::<Lz11, _, _>(KIND, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<R: io::Read, W: io::Write>(
+	header: Header,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	lz::decompress_data::<Lz11, _, _>(header, reader, writer)
+}
+
+/// Compresses data into a writer.
+///
+/// Note: The whole data is required upfront, as the header contains it's size.
+pub fn compress<W: io::Write>(data: &[u8], writer: &mut W, mode: Mode) -> Result<(), CompressError> {
+	lz::compress::<Lz11, _>(KIND, data, writer, mode)
+}
+
+/// LZ11 match encoding
+struct Lz11;
+
+impl MatchEncoding for Lz11 {
+	const LEN_RANGES: &'static [(usize, usize, usize)] = &[(3, 0x10, 2), (0x11, 0x110, 3), (0x111, 0x10110, 4)];
+
+	fn read_match<R: io::Read>(first: u8, reader: &mut R) -> Result<(usize, usize), io::Error> {
+		// Note: The high nibble of the first byte indicates the size of the match
+		let (len, disp_high) = match first >> 4 {
+			0 => {
+				let second = reader.read_u8()?;
+				let len = (usize::from(first & 0xf) << 4 | usize::from(second >> 4)) + 0x11;
+				(len, second & 0xf)
+			},
+			1 => {
+				let second = reader.read_u8()?;
+				let third = reader.read_u8()?;
+				let len = (usize::from(first & 0xf) << 12 | usize::from(second) << 4 | usize::from(third >> 4)) + 0x111;
+				(len, third & 0xf)
+			},
+			len => (usize::from(len) + 1, first & 0xf),
+		};
+		let disp_low = reader.read_u8()?;
+		let offset = (usize::from(disp_high) << 8 | usize::from(disp_low)) + 1;
+
+		Ok((offset, len))
+	}
+
+	fn write_match(offset: usize, len: usize, bytes: &mut Vec<u8>) {
+		let disp = offset - 1;
+		match len {
+			0x3..=0x10 => bytes.push(((len - 1) << 4 | disp >> 8) as u8),
+			0x11..=0x110 => {
+				let len = len - 0x11;
+				bytes.push((len >> 4) as u8);
+				bytes.push(((len & 0xf) << 4 | disp >> 8) as u8);
+			},
+			_ => {
+				let len = len - 0x111;
+				bytes.push((0x10 | len >> 12) as u8);
+				bytes.push((len >> 4) as u8);
+				bytes.push(((len & 0xf) << 4 | disp >> 8) as u8);
+			},
+		}
+		bytes.push(disp as u8);
+	}
+}
@@ -0,0 +1,82 @@
+//! Tests
+
+// Imports
+use {
+	crate::{lz::Mode, test_data},
+	std::io,
+};
+
+/// Compresses `data` with `mode` and checks it decompresses back to it, returning the compressed data
+fn round_trip(data: &[u8], mode: Mode) -> Vec<u8> {
+	let mut compressed = vec![];
+	super::compress(data, &mut compressed, mode).expect("Unable to compress");
+
+	let mut decompressed = vec![];
+	super::decompress(&mut io::Cursor::new(&compressed), &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, data);
+
+	compressed
+}
+
+/// Round trips `data` with all modes, checking the optimal mode is never bigger
+fn round_trip_all(data: &[u8]) {
+	let greedy = self::round_trip(data, Mode::Greedy);
+	let optimal = self::round_trip(data, Mode::Optimal);
+	assert!(optimal.len() <= greedy.len());
+}
+
+#[test]
+fn round_trip_empty() {
+	self::round_trip_all(&[]);
+}
+
+#[test]
+fn round_trip_one_byte() {
+	self::round_trip_all(&[0x42]);
+}
+
+#[test]
+fn round_trip_long_run() {
+	self::round_trip_all(&[0xaa; 0x30000]);
+}
+
+#[test]
+fn round_trip_text() {
+	self::round_trip_all(&test_data::text(0x8000));
+}
+
+#[test]
+fn round_trip_noise() {
+	self::round_trip_all(&test_data::noise(0x2000));
+}
+
+#[test]
+fn round_trip_match_len_boundaries() {
+	for &len in &[0x3, 0x10, 0x11, 0x110, 0x111, 0x1000] {
+		let mut data = test_data::noise(0x10);
+		data.resize(data.len() + len, 0xaa);
+		data.extend_from_slice(&test_data::noise(0x10));
+		self::round_trip_all(&data);
+	}
+}
+
+#[test]
+fn compress_max_len_match() {
+	let mut compressed = vec![];
+	super::compress(&vec![b'a'; 1 + 0x10110], &mut compressed, Mode::Greedy).expect("Unable to compress");
+	assert_eq!(compressed, [0x11, 0x11, 0x01, 0x01, 0x40, b'a', 0x1f, 0xff, 0xf0, 0x00]);
+}
+
+#[test]
+fn decompress_known() {
+	// Note: Contains a match of each encoded size.
+	let compressed = [
+		0x11, 0x2b, 0x01, 0x00, 0x1c, b'a', b'b', b'c', 0x50, 0x02, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00,
+	];
+	let mut expected = b"abc".repeat(3);
+	expected.resize(expected.len() + 0x11, b'c');
+	expected.resize(expected.len() + 0x111, b'c');
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, expected);
+}
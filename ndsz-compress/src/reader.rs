@@ -0,0 +1,145 @@
+//! Decompressing reader
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::NewError;
+
+// Imports
+use {
+	crate::{Compression, Header},
+	std::io::{self, SeekFrom},
+};
+
+/// Decompressing reader.
+///
+/// Detects if the data is compressed and, if so, reads the decompressed data, else reads the data as-is.
+///
+/// Data is only considered compressed if it's header is plausible for the data size, it decompresses
+/// successfully, and at most 3 bytes of padding are left over.
+#[derive(Debug)]
+pub struct DecompressReader<R> {
+	/// Inner
+	inner: Inner<R>,
+}
+
+/// Inner reader
+#[derive(Debug)]
+enum Inner<R> {
+	/// Uncompressed
+	Uncompressed(R),
+
+	/// Decompressed
+	Decompressed {
+		/// Compression
+		compression: Compression,
+
+		/// Decompressed data
+		data: io::Cursor<Vec<u8>>,
+	},
+}
+
+impl<R: io::Read + io::Seek> DecompressReader<R> {
+	/// Creates a new reader, detecting the compression of the data from the current position.
+	///
+	/// Compressed data is decompressed upfront, while uncompressed data is read directly from `reader`.
+	pub fn new(mut reader: R) -> Result<Self, NewError> {
+		let start_pos = reader.stream_position().map_err(NewError::Seek)?;
+		let inner = match self::decompress(&mut reader, start_pos)? {
+			Some((compression, data)) => Inner::Decompressed {
+				compression,
+				data: io::Cursor::new(data),
+			},
+			None => {
+				reader.seek(SeekFrom::Start(start_pos)).map_err(NewError::Seek)?;
+				Inner::Uncompressed(reader)
+			},
+		};
+
+		Ok(Self { inner })
+	}
+}
+
+impl<R> DecompressReader<R> {
+	/// Returns the compression found, if any
+	#[must_use]
+	pub const fn compression(&self) -> Option<Compression> {
+		match self.inner {
+			Inner::Uncompressed(_) => None,
+			Inner::Decompressed { compression, .. } => Some(compression),
+		}
+	}
+}
+
+impl<R: io::Read> io::Read for DecompressReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		match &mut self.inner {
+			Inner::Uncompressed(reader) => reader.read(buf),
+			Inner::Decompressed { data, .. } => data.read(buf),
+		}
+	}
+}
+
+impl<R: io::Seek> io::Seek for DecompressReader<R> {
+	fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+		match &mut self.inner {
+			Inner::Uncompressed(reader) => reader.seek(pos),
+			Inner::Decompressed { data, .. } => data.seek(pos),
+		}
+	}
+}
+
+/// Decompresses the data in `reader`, if it's compressed
+fn decompress<R: io::Read + io::Seek>(
+	reader: &mut R,
+	start_pos: u64,
+) -> Result<Option<(Compression, Vec<u8>)>, NewError> {
+	// Check the header
+	let header = match Header::from_reader(reader) {
+		Ok(header) => header,
+		Err(_) => return Ok(None),
+	};
+	let compression = match Compression::from_kind(header.kind) {
+		Some(compression) => compression,
+		None => return Ok(None),
+	};
+	let data_pos = reader.stream_position().map_err(NewError::Seek)?;
+	let end_pos = reader.seek(SeekFrom::End(0)).map_err(NewError::Seek)?;
+	let data_size = end_pos.saturating_sub(data_pos);
+	if !self::is_plausible(compression, u64::from(header.decompressed_size), data_size) {
+		return Ok(None);
+	}
+
+	// Then try to decompress it
+	reader.seek(SeekFrom::Start(start_pos)).map_err(NewError::Seek)?;
+	let mut compressed = vec![];
+	reader.read_to_end(&mut compressed).map_err(NewError::Read)?;
+	let mut compressed = compressed.as_slice();
+	let mut decompressed = vec![];
+	match crate::decompress(&mut compressed, &mut decompressed) {
+		Ok(_) if compressed.len() <= 3 => Ok(Some((compression, decompressed))),
+		_ => Ok(None),
+	}
+}
+
+/// Returns if `data_size` bytes after the header could decompress into `decompressed_size` bytes
+fn is_plausible(compression: Compression, decompressed_size: u64, data_size: u64) -> bool {
+	// Note: Bounds are loose, and account for up to 3 bytes of padding.
+	let (min_data_size, max_data_size) = match compression {
+		// Note: Each flag bit is either a literal, or a match of up to `0x12` / `0x10110` bytes in 2 / 4 bytes.
+		Compression::Lz10 => (decompressed_size / 9, decompressed_size * 9 / 8 + 4),
+		Compression::Lz11 => (decompressed_size / 0x4000, decompressed_size * 9 / 8 + 4),
+
+		// Note: Each symbol is encoded in at least 1 bit, after a tree of up to `0x200` bytes.
+		Compression::Huffman4 => (decompressed_size / 4, decompressed_size * 4 + 0x200 + 4),
+		Compression::Huffman8 => (decompressed_size / 8, decompressed_size * 32 + 0x200 + 4),
+
+		// Note: Each block is either up to `0x82` repeated bytes in 2 bytes, or up to `0x80` raw bytes.
+		Compression::Rle => (decompressed_size / 65, decompressed_size * 129 / 128 + 4),
+
+		Compression::Diff8 | Compression::Diff16 => (decompressed_size, decompressed_size + 4),
+	};
+
+	decompressed_size != 0 && (min_data_size..=max_data_size).contains(&data_size)
+}
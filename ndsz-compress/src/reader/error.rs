@@ -0,0 +1,16 @@
+//! Errors
+
+// Imports
+use std::io;
+
+/// Error for [`DecompressReader::new`](super::DecompressReader::new)
+#[derive(Debug, thiserror::Error)]
+pub enum NewError {
+	/// Unable to seek
+	#[error("Unable to seek")]
+	Seek(#[source] io::Error),
+
+	/// Unable to read data
+	#[error("Unable to read data")]
+	Read(#[source] io::Error),
+}
@@ -0,0 +1,129 @@
+//! RLE compression.
+//!
+//! Data is split into blocks of either a run of `3..=0x82` repeated bytes, or `1..=0x80` raw bytes.
+
+// Modules
+mod error;
+#[cfg(test)]
+mod test;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::{window::Window, Header},
+	byteorder::ReadBytesExt,
+	std::{convert::TryFrom, io},
+};
+
+/// Kind, in the header
+pub const KIND: u8 = 0x30;
+
+/// Minimum run length
+const MIN_RUN_LEN: usize = 3;
+
+/// Maximum run length
+const MAX_RUN_LEN: usize = 0x82;
+
+/// Maximum raw block length
+const MAX_RAW_LEN: usize = 0x80;
+
+/// Decompresses data from a reader into a writer.
+///
+/// The reader is read byte by byte, so it should be buffered.
+pub fn decompress<R: io::Read, W: io::Write>(reader: &mut R, writer: &mut W) -> Result<(), DecompressError> {
+	let header = Header::from_reader(reader).map_err(DecompressError::ReadHeader)?;
+	if header.kind != KIND {
+		return Err(DecompressError::WrongKind { kind: header.kind });
+	}
+
+	self::decompress_data(header, reader, writer)
+}
+
+/// Decompresses data from a reader into a writer, after the header
+pub(crate) fn decompress_data<R: io::Read, W: io::Write>(
+	header: Header,
+	reader: &mut R,
+	writer: &mut W,
+) -> Result<(), DecompressError> {
+	let decompressed_size = u64::from(header.decompressed_size);
+	let mut window = Window::new(writer);
+	while window.len() < decompressed_size {
+		let flags = reader.read_u8().map_err(DecompressError::ReadData)?;
+		let remaining = decompressed_size - window.len();
+
+		// Note: Blocks may go past the end, in which case we ignore the rest
+		match flags & 0x80 != 0 {
+			true => {
+				let len = u64::from(flags & 0x7f) + MIN_RUN_LEN as u64;
+				let byte = reader.read_u8().map_err(DecompressError::ReadData)?;
+				for _ in 0..len.min(remaining) {
+					window.push(byte).map_err(DecompressError::WriteData)?;
+				}
+			},
+			false => {
+				let len = u64::from(flags & 0x7f) + 1;
+				for _ in 0..len.min(remaining) {
+					let byte = reader.read_u8().map_err(DecompressError::ReadData)?;
+					window.push(byte).map_err(DecompressError::WriteData)?;
+				}
+			},
+		}
+	}
+
+	window.finish().map_err(DecompressError::WriteData)
+}
+
+/// Compresses data into a writer.
+///
+/// Note: The whole data is required upfront, as the header contains it's size.
+pub fn compress<W: io::Write>(data: &[u8], writer: &mut W) -> Result<(), CompressError> {
+	let decompressed_size = u32::try_from(data.len()).map_err(|_| CompressError::TooBig)?;
+	let header = Header {
+		kind: KIND,
+		decompressed_size,
+	};
+	header.to_writer(writer).map_err(CompressError::WriteHeader)?;
+
+	let mut output = vec![];
+	let mut raw_start = 0;
+	let mut pos = 0;
+	while pos < data.len() {
+		let run_len = data[pos..]
+			.iter()
+			.take(MAX_RUN_LEN)
+			.take_while(|&&byte| byte == data[pos])
+			.count();
+
+		// If we don't have a run, or the raw block is full, continue the raw block
+		if run_len < MIN_RUN_LEN {
+			pos += 1;
+			if pos - raw_start == MAX_RAW_LEN {
+				self::push_raw(&data[raw_start..pos], &mut output);
+				raw_start = pos;
+			}
+			continue;
+		}
+
+		// Else write the raw block, if any, and the run
+		self::push_raw(&data[raw_start..pos], &mut output);
+		output.push(0x80 | (run_len - MIN_RUN_LEN) as u8);
+		output.push(data[pos]);
+		pos += run_len;
+		raw_start = pos;
+	}
+	self::push_raw(&data[raw_start..], &mut output);
+
+	writer.write_all(&output).map_err(CompressError::WriteData)
+}
+
+/// Pushes a raw block, if not empty
+fn push_raw(data: &[u8], output: &mut Vec<u8>) {
+	if data.is_empty() {
+		return;
+	}
+
+	output.push((data.len() - 1) as u8);
+	output.extend_from_slice(data);
+}
@@ -0,0 +1,40 @@
+//! Errors
+
+// Imports
+use {crate::header, std::io};
+
+/// Error for [`decompress`](super::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] header::FromReaderError),
+
+	/// Wrong kind
+	#[error("Wrong kind: {kind:#x}")]
+	WrongKind { kind: u8 },
+
+	/// Unable to read data
+	#[error("Unable to read data")]
+	ReadData(#[source] io::Error),
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
+
+/// Error for [`compress`](super::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Data too big
+	#[error("Data too big")]
+	TooBig,
+
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] header::ToWriterError),
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
@@ -0,0 +1,77 @@
+//! Tests
+
+// Imports
+use {crate::test_data, std::io};
+
+/// Compresses `data` and checks it decompresses back to it, returning the compressed data
+fn round_trip(data: &[u8]) -> Vec<u8> {
+	let mut compressed = vec![];
+	super::compress(data, &mut compressed).expect("Unable to compress");
+
+	let mut decompressed = vec![];
+	super::decompress(&mut io::Cursor::new(&compressed), &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, data);
+
+	compressed
+}
+
+#[test]
+fn round_trip_empty() {
+	self::round_trip(&[]);
+}
+
+#[test]
+fn round_trip_one_byte() {
+	self::round_trip(&[0x42]);
+}
+
+#[test]
+fn round_trip_runs() {
+	for &len in &[
+		2,
+		super::MIN_RUN_LEN,
+		super::MAX_RUN_LEN,
+		super::MAX_RUN_LEN + 1,
+		0x1000,
+	] {
+		let data = [&[0x01, 0x02][..], &vec![0xaa; len], &[0x03]].concat();
+		self::round_trip(&data);
+	}
+}
+
+#[test]
+fn round_trip_raw() {
+	for &len in &[
+		super::MAX_RAW_LEN - 1,
+		super::MAX_RAW_LEN,
+		super::MAX_RAW_LEN + 1,
+		0x1000,
+	] {
+		self::round_trip(&test_data::noise(len));
+	}
+}
+
+#[test]
+fn round_trip_text() {
+	self::round_trip(&test_data::text(0x8000));
+}
+
+#[test]
+fn compress_known() {
+	let compressed = self::round_trip(b"aaaaabc");
+	assert_eq!(compressed, [0x30, 0x07, 0x00, 0x00, 0x82, b'a', 0x01, b'b', b'c']);
+}
+
+#[test]
+fn compress_max_len_run() {
+	let compressed = self::round_trip(&[b'a'; super::MAX_RUN_LEN]);
+	assert_eq!(compressed, [0x30, 0x82, 0x00, 0x00, 0xff, b'a']);
+}
+
+#[test]
+fn decompress_known() {
+	let compressed = [0x30, 0x07, 0x00, 0x00, 0x82, b'a', 0x01, b'b', b'c'];
+	let mut decompressed = vec![];
+	super::decompress(&mut &compressed[..], &mut decompressed).expect("Unable to decompress");
+	assert_eq!(decompressed, b"aaaaabc");
+}
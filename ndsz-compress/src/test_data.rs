@@ -0,0 +1,30 @@
+//! Test data
+
+/// Returns `len` bytes of pseudo-random noise, which shouldn't be compressible
+pub fn noise(len: usize) -> Vec<u8> {
+	// Note: Xorshift, so we don't need any dependencies.
+	let mut state = 0x2545_f491_u32;
+	(0..len)
+		.map(|_| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			(state >> 24) as u8
+		})
+		.collect()
+}
+
+/// Returns `len` bytes of text, with plenty of repetition at varying distances
+pub fn text(len: usize) -> Vec<u8> {
+	const WORDS: &[&str] = &["nitro ", "file ", "name ", "table ", "overlay ", "narc ", "\n"];
+
+	let mut data = vec![];
+	let mut idx = 0_usize;
+	while data.len() < len {
+		data.extend_from_slice(WORDS[(idx * idx + idx / 3) % WORDS.len()].as_bytes());
+		idx += 1;
+	}
+	data.truncate(len);
+
+	data
+}
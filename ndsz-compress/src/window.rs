@@ -0,0 +1,77 @@
+//! Decompression window
+
+// Imports
+use {crate::lz, std::io};
+
+/// Decompression window.
+///
+/// Buffers the output, keeping the last [`lz::WINDOW_SIZE`] bytes around for back-references.
+pub struct Window<'a, W> {
+	/// Writer
+	writer: &'a mut W,
+
+	/// Buffer
+	buffer: Vec<u8>,
+
+	/// Bytes already flushed to the writer
+	flushed_len: u64,
+}
+
+impl<'a, W: io::Write> Window<'a, W> {
+	/// Number of bytes to buffer, on top of the window, before flushing
+	const FLUSH_LEN: usize = 0x10000;
+
+	/// Creates a new window over a writer
+	pub fn new(writer: &'a mut W) -> Self {
+		Self {
+			writer,
+			buffer: Vec::with_capacity(lz::WINDOW_SIZE + Self::FLUSH_LEN),
+			flushed_len: 0,
+		}
+	}
+
+	/// Returns the number of bytes output so far
+	pub fn len(&self) -> u64 {
+		self.flushed_len + self.buffer.len() as u64
+	}
+
+	/// Pushes a byte
+	pub fn push(&mut self, byte: u8) -> Result<(), io::Error> {
+		self.buffer.push(byte);
+
+		if self.buffer.len() >= lz::WINDOW_SIZE + Self::FLUSH_LEN {
+			let len = self.buffer.len() - lz::WINDOW_SIZE;
+			self.writer.write_all(&self.buffer[..len])?;
+			self.buffer.drain(..len);
+			self.flushed_len += len as u64;
+		}
+
+		Ok(())
+	}
+
+	/// Returns if `offset` bytes back is within the window
+	pub fn can_copy(&self, offset: usize) -> bool {
+		(1..=lz::WINDOW_SIZE).contains(&offset) && offset as u64 <= self.len()
+	}
+
+	/// Copies `len` bytes from `offset` bytes back.
+	///
+	/// # Panics
+	/// Panics if `offset` isn't within the window. See [`Self::can_copy`].
+	pub fn copy(&mut self, offset: usize, len: usize) -> Result<(), io::Error> {
+		assert!(self.can_copy(offset), "Offset must be within the window");
+
+		// Note: The source and destination may overlap, so we must copy byte by byte.
+		for _ in 0..len {
+			let byte = self.buffer[self.buffer.len() - offset];
+			self.push(byte)?;
+		}
+
+		Ok(())
+	}
+
+	/// Flushes all remaining bytes to the writer
+	pub fn finish(self) -> Result<(), io::Error> {
+		self.writer.write_all(&self.buffer)
+	}
+}
@@ -0,0 +1,20 @@
+//! Errors
+
+// Imports
+use {ndsz_compress::reader, std::io};
+
+/// Error for [`detect`](super::detect)
+#[derive(Debug, thiserror::Error)]
+pub enum DetectError {
+	/// Unable to seek
+	#[error("Unable to seek")]
+	Seek(#[source] io::Error),
+
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] io::Error),
+
+	/// Unable to detect compression
+	#[error("Unable to detect compression")]
+	DetectCompression(#[source] reader::NewError),
+}
@@ -0,0 +1,300 @@
+//! File type detection
+//!
+//! Detects the type of nds files from their magic and structure, as most games
+//! store their files under names without any extension.
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::DetectError;
+
+// Imports
+use {
+	byteorder::{ByteOrder, LittleEndian, ReadBytesExt},
+	ndsz_compress::{Compression, DecompressReader},
+	ndsz_generic_header::{Header, NitroFile},
+	std::{
+		convert::TryFrom,
+		io::{self, Read, SeekFrom},
+	},
+};
+
+/// File type
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FileType {
+	/// Narc archive
+	Narc,
+
+	/// Narc archive without any headers
+	Narcless,
+
+	/// Character graphics
+	Ncgr,
+
+	/// Color palette
+	Nclr,
+
+	/// Cell data
+	Ncer,
+
+	/// Screen data
+	Nscr,
+
+	/// Cell animation
+	Nanr,
+
+	/// Multi-cell data
+	Nmcr,
+
+	/// 3D model
+	Nsbmd,
+
+	/// 3D texture
+	Nsbtx,
+
+	/// 3D skeletal animation
+	Nsbca,
+
+	/// 3D texture pattern animation
+	Nsbtp,
+
+	/// 3D texture SRT animation
+	Nsbta,
+
+	/// Sound data archive
+	Sdat,
+
+	/// Sequence
+	Sseq,
+
+	/// Sound bank
+	Sbnk,
+
+	/// Wave archive
+	Swar,
+
+	/// Sequence archive
+	Ssar,
+
+	/// Stream
+	Strm,
+
+	/// Message table
+	Bmg,
+
+	/// Font
+	Nftr,
+
+	/// Compressed with any of the bios compressions
+	Compressed(Compression),
+}
+
+impl FileType {
+	/// Returns the extension for this file type
+	#[must_use]
+	pub const fn extension(self) -> &'static str {
+		match self {
+			Self::Narc => "narc",
+			Self::Narcless => "narcless",
+			Self::Ncgr => "ncgr",
+			Self::Nclr => "nclr",
+			Self::Ncer => "ncer",
+			Self::Nscr => "nscr",
+			Self::Nanr => "nanr",
+			Self::Nmcr => "nmcr",
+			Self::Nsbmd => "nsbmd",
+			Self::Nsbtx => "nsbtx",
+			Self::Nsbca => "nsbca",
+			Self::Nsbtp => "nsbtp",
+			Self::Nsbta => "nsbta",
+			Self::Sdat => "sdat",
+			Self::Sseq => "sseq",
+			Self::Sbnk => "sbnk",
+			Self::Swar => "swar",
+			Self::Ssar => "ssar",
+			Self::Strm => "strm",
+			Self::Bmg => "bmg",
+			Self::Nftr => "nftr",
+			Self::Compressed(compression) => match compression {
+				Compression::Lz10 => "lz10",
+				Compression::Lz11 => "lz11",
+				Compression::Huffman4 | Compression::Huffman8 => "huff",
+				Compression::Rle => "rle",
+				Compression::Diff8 | Compression::Diff16 => "diff",
+			},
+		}
+	}
+}
+
+/// File structure, checked after the magic
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Structure {
+	/// Generic header, followed by sequential sub-sections
+	SubSections,
+
+	/// Generic header, followed by a table of offsets to each sub-section
+	OffsetTable,
+
+	/// Sound data archive header
+	Sdat,
+
+	/// Message table header
+	Bmg,
+}
+
+/// All file types with a magic, along with their structure
+const MAGICS: &[(&[u8], FileType, Structure)] = &[
+	(b"NARC", FileType::Narc, Structure::SubSections),
+	(b"RGCN", FileType::Ncgr, Structure::SubSections),
+	(b"RLCN", FileType::Nclr, Structure::SubSections),
+	(b"RECN", FileType::Ncer, Structure::SubSections),
+	(b"RCSN", FileType::Nscr, Structure::SubSections),
+	(b"RNAN", FileType::Nanr, Structure::SubSections),
+	(b"RCMN", FileType::Nmcr, Structure::SubSections),
+	(b"BMD0", FileType::Nsbmd, Structure::OffsetTable),
+	(b"BTX0", FileType::Nsbtx, Structure::OffsetTable),
+	(b"BCA0", FileType::Nsbca, Structure::OffsetTable),
+	(b"BTP0", FileType::Nsbtp, Structure::OffsetTable),
+	(b"BTA0", FileType::Nsbta, Structure::OffsetTable),
+	(b"SDAT", FileType::Sdat, Structure::Sdat),
+	(b"SSEQ", FileType::Sseq, Structure::SubSections),
+	(b"SBNK", FileType::Sbnk, Structure::SubSections),
+	(b"SWAR", FileType::Swar, Structure::SubSections),
+	(b"SSAR", FileType::Ssar, Structure::SubSections),
+	(b"STRM", FileType::Strm, Structure::SubSections),
+	(b"MESGbmg1", FileType::Bmg, Structure::Bmg),
+	(b"RTFN", FileType::Nftr, Structure::SubSections),
+];
+
+/// Detects the file type of the data from the current position of `reader`.
+///
+/// Files with a magic are only detected if their structure is also valid, while narcless
+/// archives and compressed files are detected by their structure alone.
+///
+/// The reader is left at it's original position.
+pub fn detect<R: io::Read + io::Seek>(reader: &mut R) -> Result<Option<FileType>, DetectError> {
+	let start_pos = reader.stream_position().map_err(DetectError::Seek)?;
+	let file_type = self::detect_from(reader, start_pos)?;
+	reader.seek(SeekFrom::Start(start_pos)).map_err(DetectError::Seek)?;
+
+	Ok(file_type)
+}
+
+/// Detects the file type of the data starting at `start_pos`
+fn detect_from<R: io::Read + io::Seek>(reader: &mut R, start_pos: u64) -> Result<Option<FileType>, DetectError> {
+	let len = reader.seek(SeekFrom::End(0)).map_err(DetectError::Seek)? - start_pos;
+	reader.seek(SeekFrom::Start(start_pos)).map_err(DetectError::Seek)?;
+	let mut header = Vec::with_capacity(Header::SIZE);
+	reader
+		.by_ref()
+		.take(Header::SIZE as u64)
+		.read_to_end(&mut header)
+		.map_err(DetectError::ReadHeader)?;
+	let header = match <&[u8; Header::SIZE]>::try_from(header.as_slice()) {
+		Ok(header) => header,
+		Err(_) => return self::detect_compression(reader, start_pos),
+	};
+
+	// Check the file types with a magic
+	if let Some(&(_, file_type, structure)) = MAGICS.iter().find(|(magic, ..)| header.starts_with(magic)) {
+		let is_valid = match structure {
+			Structure::SubSections => {
+				reader.seek(SeekFrom::Start(start_pos)).map_err(DetectError::Seek)?;
+				NitroFile::from_reader(&mut *reader).is_ok()
+			},
+			Structure::OffsetTable => {
+				reader.seek(SeekFrom::Start(start_pos)).map_err(DetectError::Seek)?;
+				NitroFile::from_reader_with_offset_table(&mut *reader).is_ok()
+			},
+			Structure::Sdat => self::check_sdat(header, len),
+			Structure::Bmg => self::check_bmg(header, len),
+		};
+
+		return match is_valid {
+			true => Ok(Some(file_type)),
+			false => Ok(None),
+		};
+	}
+
+	// Else check for a narcless archive, then for compression
+	match self::check_narcless(reader, header, len) {
+		true => Ok(Some(FileType::Narcless)),
+		false => self::detect_compression(reader, start_pos),
+	}
+}
+
+/// Detects if the data starting at `start_pos` is compressed
+fn detect_compression<R: io::Read + io::Seek>(reader: &mut R, start_pos: u64) -> Result<Option<FileType>, DetectError> {
+	reader.seek(SeekFrom::Start(start_pos)).map_err(DetectError::Seek)?;
+	let reader = DecompressReader::new(reader).map_err(DetectError::DetectCompression)?;
+
+	Ok(reader.compression().map(FileType::Compressed))
+}
+
+/// Checks a sound data archive header.
+///
+/// It's the generic header, but with a size of `0x40`, which includes the offsets of each block.
+fn check_sdat(header: &[u8; Header::SIZE], len: u64) -> bool {
+	let byte_order = LittleEndian::read_u16(&header[0x4..0x6]);
+	let file_size = LittleEndian::read_u32(&header[0x8..0xc]);
+	let header_size = LittleEndian::read_u16(&header[0xc..0xe]);
+
+	(byte_order == Header::BYTE_ORDER || byte_order == Header::BYTE_ORDER_SWAPPED) &&
+		header_size == 0x40 &&
+		(u64::from(header_size)..=len).contains(&u64::from(file_size))
+}
+
+/// Checks a message table header.
+///
+/// After the 8-byte magic, it contains the file size and number of sections, within a `0x20`-byte header.
+fn check_bmg(header: &[u8; Header::SIZE], len: u64) -> bool {
+	let file_size = LittleEndian::read_u32(&header[0x8..0xc]);
+	let sections_len = LittleEndian::read_u32(&header[0xc..0x10]);
+
+	(0x20..=len).contains(&u64::from(file_size)) && sections_len != 0
+}
+
+/// Checks a narcless archive.
+///
+/// It's header contains the offset and length of the fnt and fat, which should be contiguous,
+/// with all files in the fat being within the data after them.
+///
+/// The reader must be right after the header.
+fn check_narcless<R: io::Read>(reader: &mut R, header: &[u8; Header::SIZE], len: u64) -> bool {
+	let fnt_offset = LittleEndian::read_u32(&header[0x0..0x4]);
+	let fnt_len = LittleEndian::read_u32(&header[0x4..0x8]);
+	let fat_offset = LittleEndian::read_u32(&header[0x8..0xc]);
+	let fat_len = LittleEndian::read_u32(&header[0xc..0x10]);
+	let data_offset = u64::from(fat_offset) + u64::from(fat_len);
+	if fnt_offset != 0x10 ||
+		u64::from(fat_offset) != u64::from(fnt_offset) + u64::from(fnt_len) ||
+		fnt_len < 0x8 ||
+		!fat_len.is_multiple_of(0x8) ||
+		data_offset > len
+	{
+		return false;
+	}
+
+	// Check the root directory of the fnt, which contains the number of directories
+	let mut fnt = vec![0; fnt_len as usize];
+	if reader.read_exact(&mut fnt).is_err() {
+		return false;
+	}
+	let root_table_offset = LittleEndian::read_u32(&fnt[0x0..0x4]);
+	let dirs_len = LittleEndian::read_u16(&fnt[0x6..0x8]);
+	if dirs_len == 0 || !(u32::from(dirs_len) * 0x8..fnt_len).contains(&root_table_offset) {
+		return false;
+	}
+
+	// Then check all files are within the data
+	let data_len = len - data_offset;
+	(0..fat_len / 0x8).all(
+		|_| match (reader.read_u32::<LittleEndian>(), reader.read_u32::<LittleEndian>()) {
+			(Ok(start), Ok(end)) => start <= end && u64::from(end) <= data_len,
+			_ => false,
+		},
+	)
+}
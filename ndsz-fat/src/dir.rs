@@ -11,12 +11,44 @@ use ndsz_util::AsciiStrArr;
 
 /// Directory
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dir {
 	/// Entries
 	pub entries: Vec<DirEntry>,
 }
 
 impl Dir {
+	/// Finds the id of the file at `path`.
+	///
+	/// `path` components are separated by `/`. Returns `None` if the file doesn't exist,
+	/// or if `path` refers to a directory.
+	#[must_use]
+	pub fn find(&self, path: &str) -> Option<u16> {
+		match self.find_entry(path)?.kind {
+			DirEntryKind::File { id } => Some(id),
+			DirEntryKind::Dir { .. } => None,
+		}
+	}
+
+	/// Finds the entry at `path`.
+	///
+	/// `path` components are separated by `/`, and any empty components are ignored.
+	#[must_use]
+	pub fn find_entry(&self, path: &str) -> Option<&DirEntry> {
+		let path = path.trim_start_matches('/');
+		let (name, rest) = path.split_once('/').unwrap_or((path, ""));
+		let entry = self.entries.iter().find(|entry| entry.name.as_str() == name)?;
+
+		// If there are no more components, this is our entry, else recurse into it
+		match rest.trim_start_matches('/').is_empty() {
+			true => Some(entry),
+			false => match entry.kind {
+				DirEntryKind::File { .. } => None,
+				DirEntryKind::Dir { ref dir, .. } => dir.find_entry(rest),
+			},
+		}
+	}
+
 	/// Walks through all entries in this directory and sub-directory.
 	///
 	/// Note: Performs a depth-first walk.
@@ -39,6 +71,7 @@ impl Dir {
 
 /// Directory entry
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirEntry {
 	/// Name
 	pub name: AsciiStrArr<0x80>,
@@ -49,6 +82,8 @@ pub struct DirEntry {
 
 /// Directory entry kind
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DirEntryKind {
 	/// File
 	File {
@@ -1,20 +1,26 @@
 //! File allocation table
 
 // Modules
+pub mod allocator;
 mod error;
 
 // Exports
-pub use self::error::FromReaderError;
+pub use self::{
+	allocator::Allocator,
+	error::{FromReaderError, OpenFileError, ToWriterError},
+};
 
 // Imports
 use {
 	crate::FilePtr,
 	itertools::Itertools,
+	ndsz_util::IoSlice,
 	std::{io, iter},
 };
 
 /// File allocation table
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FileAllocationTable {
 	/// All pointers
 	pub ptrs: Vec<FilePtr>,
@@ -36,4 +42,42 @@ impl FileAllocationTable {
 
 		Ok(Self { ptrs })
 	}
+
+	/// Writes the file allocation table to a writer
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), ToWriterError> {
+		for ptr in &self.ptrs {
+			writer.write_all(&ptr.to_bytes()).map_err(ToWriterError::WriteFile)?;
+		}
+
+		Ok(())
+	}
+
+	/// Opens file `id` within `reader`
+	pub fn open_file<R: io::Seek>(&self, reader: R, id: u16) -> Result<IoSlice<R>, OpenFileError> {
+		let ptr = self.ptrs.get(usize::from(id)).ok_or(OpenFileError::UnknownId { id })?;
+		IoSlice::new(reader, u64::from(ptr.start_address)..u64::from(ptr.end_address)).map_err(OpenFileError::Slice)
+	}
+
+	/// Returns the end of the slot of file `id`.
+	///
+	/// The slot of a file is the space it may grow into without overlapping any other file.
+	/// It ends at the end of the file, aligned to `alignment`, or at the start of the next
+	/// file, whichever comes first.
+	#[must_use]
+	pub fn slot_end(&self, id: u16, alignment: u32) -> Option<u32> {
+		let ptr = self.ptrs.get(usize::from(id))?;
+		let aligned_end = match ptr.end_address % alignment {
+			0 => ptr.end_address,
+			rem => ptr.end_address.saturating_add(alignment - rem),
+		};
+
+		let next_start = (0..)
+			.zip(&self.ptrs)
+			.filter(|&(other_id, other_ptr)| other_id != id && !other_ptr.is_empty())
+			.map(|(_, other_ptr)| other_ptr.start_address)
+			.filter(|&start_address| start_address >= ptr.end_address)
+			.min();
+
+		Some(next_start.map_or(aligned_end, |next_start| aligned_end.min(next_start)))
+	}
 }
@@ -0,0 +1,167 @@
+//! File allocator
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::AllocError;
+
+// Imports
+use {
+	crate::FilePtr,
+	std::{
+		convert::TryFrom,
+		io::{self, Read},
+	},
+};
+
+/// File allocator.
+///
+/// Lays out files one after the other, each aligned to `alignment`, starting at
+/// the start offset.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Allocator {
+	/// Alignment
+	alignment: u32,
+
+	/// Current offset
+	cur_offset: u32,
+
+	/// Padding byte
+	padding: Option<u8>,
+}
+
+impl Allocator {
+	/// Alignment used by narcs
+	pub const NARC_ALIGNMENT: u32 = 0x4;
+	/// Alignment used by roms
+	pub const ROM_ALIGNMENT: u32 = 0x200;
+
+	/// Creates a new allocator starting at `start_offset`.
+	///
+	/// # Panics
+	/// Panics if `alignment` is `0`.
+	#[must_use]
+	pub fn new(alignment: u32, start_offset: u32) -> Self {
+		assert_ne!(alignment, 0, "Alignment may not be 0");
+
+		Self {
+			alignment,
+			cur_offset: start_offset,
+			padding: None,
+		}
+	}
+
+	/// Sets the byte used to pad the space between files.
+	///
+	/// Without one, the space between files is skipped over when padding.
+	#[must_use]
+	pub fn with_padding(self, padding: u8) -> Self {
+		Self {
+			padding: Some(padding),
+			..self
+		}
+	}
+
+	/// Returns the alignment
+	#[must_use]
+	pub fn alignment(&self) -> u32 {
+		self.alignment
+	}
+
+	/// Returns the padding byte
+	#[must_use]
+	pub fn padding(&self) -> Option<u8> {
+		self.padding
+	}
+
+	/// Returns the current offset.
+	///
+	/// This is the end of the last allocated file.
+	#[must_use]
+	pub fn cur_offset(&self) -> u32 {
+		self.cur_offset
+	}
+
+	/// Allocates a file of size `size`, aligned after all other files.
+	///
+	/// Empty files are given an aligned position, but don't take up any space.
+	pub fn alloc(&mut self, size: u32) -> Result<FilePtr, AllocError> {
+		let start_address = self
+			.cur_offset
+			.checked_add(self.alignment - 1)
+			.map(|offset| offset / self.alignment * self.alignment)
+			.ok_or(AllocError::TooBig)?;
+
+		self.alloc_exact(start_address, size)
+	}
+
+	/// Allocates a file of size `size` at `offset`, if it's after all other files,
+	/// else allocates it like [`Self::alloc`].
+	pub fn alloc_at(&mut self, offset: u32, size: u32) -> Result<FilePtr, AllocError> {
+		match offset >= self.cur_offset {
+			true => self.alloc_exact(offset, size),
+			false => self.alloc(size),
+		}
+	}
+
+	/// Allocates files with sizes `sizes`, in order
+	pub fn alloc_all<I: IntoIterator<Item = u32>>(&mut self, sizes: I) -> Result<Vec<FilePtr>, AllocError> {
+		sizes.into_iter().map(|size| self.alloc(size)).collect()
+	}
+
+	/// Re-allocates files with sizes `sizes`, keeping their existing positions in `ptrs`.
+	///
+	/// Files that still fit within their existing position are kept there, while any files that
+	/// grew, or that aren't in `ptrs`, are appended after all existing files.
+	pub fn realloc_all<I: IntoIterator<Item = u32>>(
+		&mut self,
+		ptrs: &[FilePtr],
+		sizes: I,
+	) -> Result<Vec<FilePtr>, AllocError> {
+		if let Some(end_address) = ptrs.iter().map(|ptr| ptr.end_address).max() {
+			self.cur_offset = self.cur_offset.max(end_address);
+		}
+
+		sizes
+			.into_iter()
+			.enumerate()
+			.map(|(idx, size)| match ptrs.get(idx) {
+				Some(ptr) if size <= ptr.len() => Ok(FilePtr {
+					start_address: ptr.start_address,
+					end_address:   ptr.start_address + size,
+				}),
+				_ => self.alloc(size),
+			})
+			.collect()
+	}
+
+	/// Pads `len` bytes in `writer`.
+	///
+	/// Writes the padding byte, if any, else seeks over them.
+	pub fn pad<W: io::Write + io::Seek>(&self, writer: &mut W, len: u64) -> Result<(), io::Error> {
+		match self.padding {
+			Some(padding) => io::copy(&mut io::repeat(padding).take(len), writer).map(|_| ()),
+			None => {
+				let len =
+					i64::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Padding too big"))?;
+				writer.seek(io::SeekFrom::Current(len)).map(|_| ())
+			},
+		}
+	}
+
+	/// Allocates a file of size `size` at `start_address`
+	fn alloc_exact(&mut self, start_address: u32, size: u32) -> Result<FilePtr, AllocError> {
+		let end_address = start_address.checked_add(size).ok_or(AllocError::TooBig)?;
+
+		// Note: Empty files don't take up any space, so we don't advance past them.
+		if size != 0 {
+			self.cur_offset = end_address;
+		}
+
+		Ok(FilePtr {
+			start_address,
+			end_address,
+		})
+	}
+}
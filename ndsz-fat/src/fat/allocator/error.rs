@@ -0,0 +1,9 @@
+//! Errors
+
+/// Error for [`Allocator::alloc`](super::Allocator::alloc) and similar
+#[derive(PartialEq, Eq, Clone, Copy, Debug, thiserror::Error)]
+pub enum AllocError {
+	/// Too big
+	#[error("Files exceed the maximum offset")]
+	TooBig,
+}
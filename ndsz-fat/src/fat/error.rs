@@ -10,3 +10,23 @@ pub enum FromReaderError {
 	#[error("Unable to read file")]
 	ReadFile(#[source] io::Error),
 }
+
+/// Error for [`FileAllocationTable::to_writer`](super::FileAllocationTable::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Unable to write file
+	#[error("Unable to write file")]
+	WriteFile(#[source] io::Error),
+}
+
+/// Error for [`FileAllocationTable::open_file`](super::FileAllocationTable::open_file)
+#[derive(Debug, thiserror::Error)]
+pub enum OpenFileError {
+	/// Unknown file id
+	#[error("Unknown file id {id}")]
+	UnknownId { id: u16 },
+
+	/// Unable to slice file
+	#[error("Unable to slice file")]
+	Slice(#[source] io::Error),
+}
@@ -1,10 +1,14 @@
 //! File pointer
 
 // Imports
-use byteorder::{ByteOrder, LittleEndian};
+use {
+	byteorder::{ByteOrder, LittleEndian},
+	std::ops::Range,
+};
 
 /// File pointer
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FilePtr {
 	/// Start address
 	pub start_address: u32,
@@ -26,4 +30,39 @@ impl FilePtr {
 			end_address:   LittleEndian::read_u32(bytes.end_address),
 		}
 	}
+
+	/// Encodes this file pointer as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x8] {
+		let mut bytes = [0; 0x8];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			start_address: [0x4],
+			  end_address: [0x4],
+		);
+
+		LittleEndian::write_u32(bytes_mut.start_address, self.start_address);
+		LittleEndian::write_u32(bytes_mut.end_address, self.end_address);
+
+		bytes
+	}
+
+	/// Returns the length of this file.
+	///
+	/// If the end address is before the start address, returns `0`.
+	#[must_use]
+	pub fn len(self) -> u32 {
+		self.end_address.saturating_sub(self.start_address)
+	}
+
+	/// Returns if this file is empty
+	#[must_use]
+	pub fn is_empty(self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the range of this file
+	#[must_use]
+	pub fn range(self) -> Range<u32> {
+		self.start_address..self.end_address
+	}
 }
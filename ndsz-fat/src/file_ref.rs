@@ -0,0 +1,50 @@
+//! File reference
+
+// Imports
+use {crate::FileNameTable, std::fmt};
+
+/// File reference.
+///
+/// Refers to a file either by it's id or by it's path.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FileRef<'a> {
+	/// Id
+	Id(u16),
+
+	/// Path
+	Path(&'a str),
+}
+
+impl<'a> FileRef<'a> {
+	/// Resolves the id of this file.
+	///
+	/// Ids aren't checked to exist, while paths are looked up in `fnt`.
+	#[must_use]
+	pub fn resolve(self, fnt: &FileNameTable) -> Option<u16> {
+		match self {
+			Self::Id(id) => Some(id),
+			Self::Path(path) => fnt.root.find(path),
+		}
+	}
+}
+
+impl<'a> From<u16> for FileRef<'a> {
+	fn from(id: u16) -> Self {
+		Self::Id(id)
+	}
+}
+
+impl<'a> From<&'a str> for FileRef<'a> {
+	fn from(path: &'a str) -> Self {
+		Self::Path(path)
+	}
+}
+
+impl<'a> fmt::Display for FileRef<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Id(id) => write!(f, "#{id}"),
+			Self::Path(path) => write!(f, "{path:?}"),
+		}
+	}
+}
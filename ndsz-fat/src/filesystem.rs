@@ -0,0 +1,431 @@
+//! Editable filesystem
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{EditError, NewError, WriteError};
+
+// Imports
+use {
+	crate::{fat::Allocator, Dir, DirEntry, DirEntryKind, FileAllocationTable, FileNameTable, FilePtr},
+	ndsz_util::{AsciiStrArr, IoSlice},
+	std::{collections::BTreeMap, convert::TryFrom, fs, io, path::PathBuf},
+};
+
+/// Editable filesystem.
+///
+/// Holds the directory tree of a [`FileNameTable`], alongside the data of each file, which
+/// may be edited and then written back out.
+///
+/// Paths are separated by `/`, with any empty components being ignored.
+#[derive(Clone, Debug)]
+pub struct Filesystem<R> {
+	/// Root directory
+	root: FsDir,
+
+	/// Hidden files, by id.
+	///
+	/// These are the files in the fat that aren't in the fnt, such as overlays.
+	hidden_files: BTreeMap<u16, FileData>,
+
+	/// Reader for the original files
+	reader: R,
+}
+
+impl<R> Filesystem<R> {
+	/// Creates a filesystem from it's tables.
+	///
+	/// All file pointers in `fat` are relative to `reader`.
+	pub fn new(fnt: &FileNameTable, fat: &FileAllocationTable, reader: R) -> Result<Self, NewError> {
+		let mut hidden_files = (0..)
+			.zip(&fat.ptrs)
+			.map(|(id, &ptr)| (id, FileData::Original(ptr)))
+			.collect::<BTreeMap<_, _>>();
+		let root = FsDir::new(&fnt.root, &mut hidden_files)?;
+
+		Ok(Self {
+			root,
+			hidden_files,
+			reader,
+		})
+	}
+
+	/// Returns the data of the file at `path`
+	#[must_use]
+	pub fn file(&self, path: &str) -> Option<&FileData> {
+		let (parent, name) = self::split_path(path).ok()?;
+		match &self.root.dir(&parent).ok()?.entry(name)?.kind {
+			FsEntryKind::File(data) => Some(data),
+			FsEntryKind::Dir(_) => None,
+		}
+	}
+
+	/// Adds a file at `path`
+	pub fn add_file(&mut self, path: &str, data: FileData) -> Result<(), EditError> {
+		self.add_entry(path, FsEntryKind::File(data))
+	}
+
+	/// Adds an empty directory at `path`
+	pub fn add_dir(&mut self, path: &str) -> Result<(), EditError> {
+		self.add_entry(path, FsEntryKind::Dir(FsDir { entries: vec![] }))
+	}
+
+	/// Removes the file or directory at `path`.
+	///
+	/// Directories are removed alongside all their entries.
+	pub fn remove(&mut self, path: &str) -> Result<(), EditError> {
+		let (parent, name) = self::split_path(path)?;
+		let parent = self.root.dir_mut(&parent)?;
+		let idx = parent
+			.entry_idx(name)
+			.ok_or_else(|| EditError::NotFound { path: path.to_owned() })?;
+		parent.entries.remove(idx);
+
+		Ok(())
+	}
+
+	/// Renames the file or directory at `path` to `new_name`
+	pub fn rename(&mut self, path: &str, new_name: &str) -> Result<(), EditError> {
+		let (parent, name) = self::split_path(path)?;
+		let new_name = self::parse_name(new_name)?;
+		let parent_path = parent.join("/");
+		let parent = self.root.dir_mut(&parent)?;
+		let idx = parent
+			.entry_idx(name)
+			.ok_or_else(|| EditError::NotFound { path: path.to_owned() })?;
+		if new_name.as_str() == name {
+			return Ok(());
+		}
+		if parent.entry(new_name.as_str()).is_some() {
+			return Err(EditError::AlreadyExists {
+				path: format!("{parent_path}/{new_name}"),
+			});
+		}
+
+		parent.entries[idx].name = new_name;
+
+		Ok(())
+	}
+
+	/// Moves the file or directory at `path` into the directory at `dest_path`
+	pub fn move_entry(&mut self, path: &str, dest_path: &str) -> Result<(), EditError> {
+		let (parent, name) = self::split_path(path)?;
+		let dest = self::path_components(dest_path).collect::<Vec<_>>();
+
+		// Note: We can't move a directory into itself, or any of it's sub-directories
+		if dest.len() > parent.len() && dest[..parent.len()] == parent[..] && dest[parent.len()] == name {
+			return Err(EditError::MoveIntoSelf { path: path.to_owned() });
+		}
+
+		// Check that the destination exists and doesn't have an entry with the same name before removing anything
+		if self.root.dir(&dest)?.entry(name).is_some() {
+			return Err(EditError::AlreadyExists {
+				path: format!("{dest_path}/{name}"),
+			});
+		}
+
+		let parent = self.root.dir_mut(&parent)?;
+		let idx = parent
+			.entry_idx(name)
+			.ok_or_else(|| EditError::NotFound { path: path.to_owned() })?;
+		let entry = parent.entries.remove(idx);
+		self.root.dir_mut(&dest)?.entries.push(entry);
+
+		Ok(())
+	}
+
+	/// Replaces the contents of the file at `path`
+	pub fn replace(&mut self, path: &str, data: FileData) -> Result<(), EditError> {
+		let (parent, name) = self::split_path(path)?;
+		let entry = self
+			.root
+			.dir_mut(&parent)?
+			.entry_mut(name)
+			.ok_or_else(|| EditError::NotFound { path: path.to_owned() })?;
+		match &mut entry.kind {
+			FsEntryKind::File(file_data) => *file_data = data,
+			FsEntryKind::Dir(_) => return Err(EditError::NotAFile { path: path.to_owned() }),
+		}
+
+		Ok(())
+	}
+
+	/// Replaces the contents of hidden file `id`
+	pub fn replace_hidden(&mut self, id: u16, data: FileData) -> Result<(), EditError> {
+		let file_data = self
+			.hidden_files
+			.get_mut(&id)
+			.ok_or(EditError::UnknownHiddenFile { id })?;
+		*file_data = data;
+
+		Ok(())
+	}
+
+	/// Writes all files to `writer`, returning the new file name and allocation tables.
+	///
+	/// Hidden files keep their ids, while all other files are renumbered contiguously after them.
+	/// Files are laid out by `allocator`, and `writer` must be positioned at it's current offset.
+	pub fn write<W: io::Write + io::Seek>(
+		&mut self,
+		writer: &mut W,
+		allocator: &mut Allocator,
+	) -> Result<(FileNameTable, FileAllocationTable), WriteError>
+	where
+		R: io::Read + io::Seek,
+	{
+		// Build the fnt, with files after all hidden files
+		let first_file_id = match self.hidden_files.keys().next_back() {
+			Some(id) => id.checked_add(1).ok_or(WriteError::TooManyFiles)?,
+			None => 0,
+		};
+		let mut fnt = FileNameTable {
+			root: self.root.to_dir(),
+		};
+		fnt.assign_ids(first_file_id).map_err(WriteError::AssignIds)?;
+
+		// Then collect all files
+		let mut files = self
+			.hidden_files
+			.iter()
+			.map(|(&id, data)| (id, data))
+			.collect::<BTreeMap<_, _>>();
+		self.root.collect_files(&fnt.root, &mut files);
+
+		// And write them
+		let files_len = files.keys().next_back().map_or(0, |&id| usize::from(id) + 1);
+		let mut ptrs = vec![
+			FilePtr {
+				start_address: 0,
+				end_address:   0,
+			};
+			files_len
+		];
+		let mut cur_offset = allocator.cur_offset();
+		for (&id, data) in &files {
+			let size = match data {
+				FileData::Original(ptr) => ptr.len(),
+				FileData::Bytes(bytes) => u32::try_from(bytes.len()).map_err(|_| WriteError::FileTooBig { id })?,
+				FileData::Path(path) => {
+					let metadata = fs::metadata(path).map_err(|source| WriteError::FileMetadata {
+						path: path.clone(),
+						source,
+					})?;
+					u32::try_from(metadata.len()).map_err(|_| WriteError::FileTooBig { id })?
+				},
+			};
+			let ptr = allocator.alloc(size).map_err(WriteError::Alloc)?;
+			allocator
+				.pad(writer, u64::from(ptr.start_address - cur_offset))
+				.map_err(WriteError::Pad)?;
+
+			let written = match data {
+				FileData::Original(original_ptr) => {
+					let mut file = IoSlice::new(
+						&mut self.reader,
+						u64::from(original_ptr.start_address)..u64::from(original_ptr.end_address),
+					)
+					.map_err(|source| WriteError::ReadOriginal { id, source })?;
+					io::copy(&mut file, writer)
+				},
+				FileData::Bytes(bytes) => writer.write_all(bytes).map(|()| bytes.len() as u64),
+				FileData::Path(path) => {
+					let mut file = fs::File::open(path).map_err(|source| WriteError::OpenPath {
+						path: path.clone(),
+						source,
+					})?;
+					io::copy(&mut file, writer)
+				},
+			}
+			.map_err(|source| WriteError::WriteFile { id, source })?;
+			if written != u64::from(size) {
+				return Err(WriteError::FileSizeChanged { id });
+			}
+
+			ptrs[usize::from(id)] = ptr;
+			cur_offset = ptr.end_address;
+		}
+
+		Ok((fnt, FileAllocationTable { ptrs }))
+	}
+
+	/// Adds an entry at `path`
+	fn add_entry(&mut self, path: &str, kind: FsEntryKind) -> Result<(), EditError> {
+		let (parent, name) = self::split_path(path)?;
+		let name = self::parse_name(name)?;
+		let parent = self.root.dir_mut(&parent)?;
+		if parent.entry(name.as_str()).is_some() {
+			return Err(EditError::AlreadyExists { path: path.to_owned() });
+		}
+
+		parent.entries.push(FsEntry { name, kind });
+
+		Ok(())
+	}
+}
+
+/// File data
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum FileData {
+	/// Original file, within the reader
+	Original(FilePtr),
+
+	/// Bytes
+	Bytes(Vec<u8>),
+
+	/// File on disk
+	Path(PathBuf),
+}
+
+/// Filesystem directory
+#[derive(Clone, Debug)]
+struct FsDir {
+	/// Entries
+	entries: Vec<FsEntry>,
+}
+
+impl FsDir {
+	/// Creates a directory from a fnt directory, taking all it's files from `files`
+	fn new(dir: &Dir, files: &mut BTreeMap<u16, FileData>) -> Result<Self, NewError> {
+		let entries = dir
+			.entries
+			.iter()
+			.map(|entry| {
+				let kind = match entry.kind {
+					DirEntryKind::File { id } =>
+						FsEntryKind::File(files.remove(&id).ok_or(NewError::UnknownFileId { id })?),
+					DirEntryKind::Dir { ref dir, .. } => FsEntryKind::Dir(Self::new(dir, files)?),
+				};
+
+				Ok(FsEntry { name: entry.name, kind })
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self { entries })
+	}
+
+	/// Converts this directory to a fnt directory, with all ids as `0`
+	fn to_dir(&self) -> Dir {
+		let entries = self
+			.entries
+			.iter()
+			.map(|entry| DirEntry {
+				name: entry.name,
+				kind: match &entry.kind {
+					FsEntryKind::File(_) => DirEntryKind::File { id: 0 },
+					FsEntryKind::Dir(dir) => DirEntryKind::Dir {
+						id:  0,
+						dir: dir.to_dir(),
+					},
+				},
+			})
+			.collect();
+
+		Dir { entries }
+	}
+
+	/// Collects all files, by their id in `dir`.
+	///
+	/// `dir` must have been created from this directory with [`Self::to_dir`].
+	fn collect_files<'a>(&'a self, dir: &Dir, files: &mut BTreeMap<u16, &'a FileData>) {
+		for (entry, dir_entry) in self.entries.iter().zip(&dir.entries) {
+			match (&entry.kind, &dir_entry.kind) {
+				(FsEntryKind::File(data), &DirEntryKind::File { id }) => {
+					files.insert(id, data);
+				},
+				(FsEntryKind::Dir(fs_dir), DirEntryKind::Dir { dir, .. }) => fs_dir.collect_files(dir, files),
+				_ => unreachable!("Directory wasn't created from this directory"),
+			}
+		}
+	}
+
+	/// Returns the entry named `name`
+	fn entry(&self, name: &str) -> Option<&FsEntry> {
+		self.entries.iter().find(|entry| entry.name.as_str() == name)
+	}
+
+	/// Returns the entry named `name` mutably
+	fn entry_mut(&mut self, name: &str) -> Option<&mut FsEntry> {
+		self.entries.iter_mut().find(|entry| entry.name.as_str() == name)
+	}
+
+	/// Returns the index of the entry named `name`
+	fn entry_idx(&self, name: &str) -> Option<usize> {
+		self.entries.iter().position(|entry| entry.name.as_str() == name)
+	}
+
+	/// Returns the directory at `components`
+	fn dir(&self, components: &[&str]) -> Result<&Self, EditError> {
+		components.iter().enumerate().try_fold(self, |dir, (idx, &name)| {
+			let path = || components[..=idx].join("/");
+			match &dir
+				.entry(name)
+				.ok_or_else(|| EditError::NotFound { path: path() })?
+				.kind
+			{
+				FsEntryKind::Dir(dir) => Ok(dir),
+				FsEntryKind::File(_) => Err(EditError::NotADir { path: path() }),
+			}
+		})
+	}
+
+	/// Returns the directory at `components` mutably
+	fn dir_mut(&mut self, components: &[&str]) -> Result<&mut Self, EditError> {
+		components.iter().enumerate().try_fold(self, |dir, (idx, &name)| {
+			let path = || components[..=idx].join("/");
+			match &mut dir
+				.entry_mut(name)
+				.ok_or_else(|| EditError::NotFound { path: path() })?
+				.kind
+			{
+				FsEntryKind::Dir(dir) => Ok(dir),
+				FsEntryKind::File(_) => Err(EditError::NotADir { path: path() }),
+			}
+		})
+	}
+}
+
+/// Filesystem entry
+#[derive(Clone, Debug)]
+struct FsEntry {
+	/// Name
+	name: AsciiStrArr<0x80>,
+
+	/// Kind
+	kind: FsEntryKind,
+}
+
+/// Filesystem entry kind
+#[derive(Clone, Debug)]
+enum FsEntryKind {
+	/// File
+	File(FileData),
+
+	/// Directory
+	Dir(FsDir),
+}
+
+/// Returns all components of a path
+fn path_components(path: &str) -> impl Iterator<Item = &str> {
+	path.split('/').filter(|component| !component.is_empty())
+}
+
+/// Splits a path into it's parent components and name
+fn split_path(path: &str) -> Result<(Vec<&str>, &str), EditError> {
+	let mut components = self::path_components(path).collect::<Vec<_>>();
+	let name = components
+		.pop()
+		.ok_or_else(|| EditError::EmptyPath { path: path.to_owned() })?;
+
+	Ok((components, name))
+}
+
+/// Parses an entry name
+fn parse_name(name: &str) -> Result<AsciiStrArr<0x80>, EditError> {
+	// Note: Names are stored with a 7-bit length in the fnt
+	if name.is_empty() || name.len() > 0x7f || name.contains('/') {
+		return Err(EditError::InvalidName { name: name.to_owned() });
+	}
+
+	AsciiStrArr::from_bytes(name).map_err(|_| EditError::InvalidName { name: name.to_owned() })
+}
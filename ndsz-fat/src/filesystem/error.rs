@@ -0,0 +1,111 @@
+//! Errors
+
+// Imports
+use {
+	crate::{fat::allocator, fnt},
+	std::{io, path::PathBuf},
+};
+
+/// Error for [`Filesystem::new`](super::Filesystem::new)
+#[derive(PartialEq, Eq, Clone, Copy, Debug, thiserror::Error)]
+pub enum NewError {
+	/// Unknown file id
+	#[error("File {id} in the fnt isn't in the fat, or is in the fnt multiple times")]
+	UnknownFileId { id: u16 },
+}
+
+/// Error for editing a [`Filesystem`](super::Filesystem)
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+pub enum EditError {
+	/// Empty path
+	#[error("Path {path:?} is empty")]
+	EmptyPath { path: String },
+
+	/// Invalid name
+	#[error("Invalid name {name:?}")]
+	InvalidName { name: String },
+
+	/// Not found
+	#[error("{path:?} not found")]
+	NotFound { path: String },
+
+	/// Already exists
+	#[error("{path:?} already exists")]
+	AlreadyExists { path: String },
+
+	/// Not a directory
+	#[error("{path:?} is not a directory")]
+	NotADir { path: String },
+
+	/// Not a file
+	#[error("{path:?} is not a file")]
+	NotAFile { path: String },
+
+	/// Moving a directory into itself
+	#[error("Cannot move {path:?} into itself")]
+	MoveIntoSelf { path: String },
+
+	/// Unknown hidden file
+	#[error("Unknown hidden file {id}")]
+	UnknownHiddenFile { id: u16 },
+}
+
+/// Error for [`Filesystem::write`](super::Filesystem::write)
+#[derive(Debug, thiserror::Error)]
+pub enum WriteError {
+	/// Too many files
+	#[error("Too many files")]
+	TooManyFiles,
+
+	/// Unable to assign ids
+	#[error("Unable to assign ids")]
+	AssignIds(#[source] fnt::AssignIdsError),
+
+	/// File too big
+	#[error("File {id} is too big")]
+	FileTooBig { id: u16 },
+
+	/// Unable to get file metadata
+	#[error("Unable to get metadata of {path:?}")]
+	FileMetadata {
+		path:   PathBuf,
+		#[source]
+		source: io::Error,
+	},
+
+	/// Unable to allocate file
+	#[error("Unable to allocate file")]
+	Alloc(#[source] allocator::AllocError),
+
+	/// Unable to write padding
+	#[error("Unable to write padding")]
+	Pad(#[source] io::Error),
+
+	/// Unable to read original file
+	#[error("Unable to read original file {id}")]
+	ReadOriginal {
+		id:     u16,
+		#[source]
+		source: io::Error,
+	},
+
+	/// Unable to open file
+	#[error("Unable to open {path:?}")]
+	OpenPath {
+		path:   PathBuf,
+		#[source]
+		source: io::Error,
+	},
+
+	/// Unable to write file
+	#[error("Unable to write file {id}")]
+	WriteFile {
+		id:     u16,
+		#[source]
+		source: io::Error,
+	},
+
+	/// File size changed while writing
+	#[error("File {id} changed size while writing")]
+	FileSizeChanged { id: u16 },
+}
@@ -2,27 +2,38 @@
 
 // Modules
 mod error;
+pub mod index;
 pub mod main_table;
 pub mod sub_table;
+#[cfg(test)]
+mod test;
 
 // Exports
 pub use self::{
-	error::FromReaderError,
+	error::{AssignIdsError, FromReaderError, ToWriterError},
+	index::Index,
 	main_table::{MainTable, MainTableEntry},
 	sub_table::{SubTable, SubTableEntry, SubTableEntryKind},
 };
 
 // Imports
-use {crate::Dir, std::io};
+use {
+	crate::{Dir, DirEntryKind},
+	std::{convert::TryFrom, io},
+};
 
 /// File name table
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FileNameTable {
 	/// Root directory
 	pub root: Dir,
 }
 
 impl FileNameTable {
+	/// Id of the root directory
+	pub const ROOT_ID: u16 = 0xf000;
+
 	/// Reads the FNT from a reader
 	pub fn from_reader<R: io::Read + io::Seek>(reader: &mut R) -> Result<Self, FromReaderError> {
 		// Read the main table
@@ -31,9 +42,182 @@ impl FileNameTable {
 		// Read the root entry
 		let root = main_table
 			.root_entry
-			.read_dir(reader, 61440, &main_table.entries)
+			.read_dir(reader, Self::ROOT_ID, &main_table.entries)
 			.map_err(FromReaderError::ReadRootDir)?;
 
 		Ok(Self { root })
 	}
+
+	/// Assigns ids to all directories and files.
+	///
+	/// Directories are given ids starting after [`Self::ROOT_ID`], and files are given
+	/// contiguous ids, starting at `first_file_id`. All entries of a directory are assigned
+	/// ids before recursing into any sub-directories.
+	pub fn assign_ids(&mut self, first_file_id: u16) -> Result<(), AssignIdsError> {
+		let mut next_dir_id = Self::ROOT_ID + 1;
+		let mut next_file_id = first_file_id;
+		self::assign_dir_ids(&mut self.root, &mut next_dir_id, &mut next_file_id)
+	}
+
+	/// Writes the FNT to a writer.
+	///
+	/// Directory ids must be contiguous, starting at [`Self::ROOT_ID`], and the files of each
+	/// directory must have contiguous ids. These may be ensured by calling [`Self::assign_ids`] first.
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), ToWriterError> {
+		// Collect all directories and sort them by id
+		let mut next_file_id = self::first_file_id(&self.root).unwrap_or(0);
+		let mut dirs = vec![];
+		self::collect_dirs(&self.root, Self::ROOT_ID, Self::ROOT_ID, &mut next_file_id, &mut dirs)?;
+		dirs.sort_by_key(|dir| dir.id);
+		for (dir, id) in dirs.iter().zip(Self::ROOT_ID..) {
+			if dir.id != id {
+				return Err(ToWriterError::NonContiguousDirIds { id: dir.id });
+			}
+		}
+		let dirs_len = u16::try_from(dirs.len()).map_err(|_| ToWriterError::TooManyDirs)?;
+
+		// Then build all sub-tables
+		let mut sub_tables = vec![];
+		let mut sub_table_offsets = vec![];
+		for dir in &dirs {
+			sub_table_offsets.push(sub_tables.len());
+			let sub_table = SubTable {
+				entries: dir
+					.dir
+					.entries
+					.iter()
+					.map(|entry| SubTableEntry {
+						name: entry.name,
+						kind: match entry.kind {
+							DirEntryKind::File { .. } => SubTableEntryKind::File,
+							DirEntryKind::Dir { id, .. } => SubTableEntryKind::Dir { id },
+						},
+					})
+					.collect(),
+			};
+			sub_table
+				.to_writer(&mut sub_tables)
+				.map_err(|source| ToWriterError::BuildSubTable { id: dir.id, source })?;
+		}
+
+		// And finally write the main table before them
+		let main_table_len = 0x8 * dirs.len();
+		for (dir, sub_table_offset) in dirs.iter().zip(sub_table_offsets) {
+			let sub_table_offset =
+				u32::try_from(main_table_len + sub_table_offset).map_err(|_| ToWriterError::TooBig)?;
+
+			// Note: On the root entry, the number of directories is stored instead of the parent
+			let parent_id = match dir.id {
+				Self::ROOT_ID => dirs_len,
+				_ => dir.parent_id,
+			};
+
+			let entry = MainTableEntry {
+				sub_table_offset,
+				first_file_id: dir.first_file_id,
+				parent_id,
+			};
+			writer
+				.write_all(&entry.to_bytes())
+				.map_err(ToWriterError::WriteMainTable)?;
+		}
+		writer.write_all(&sub_tables).map_err(ToWriterError::WriteSubTables)?;
+
+		Ok(())
+	}
+}
+
+/// Directory found while writing
+struct FntDir<'a> {
+	/// Id
+	id: u16,
+
+	/// Parent id
+	parent_id: u16,
+
+	/// First file id
+	first_file_id: u16,
+
+	/// Directory
+	dir: &'a Dir,
+}
+
+/// Assigns ids to all entries of a directory, then recurses into all sub-directories
+fn assign_dir_ids(dir: &mut Dir, next_dir_id: &mut u16, next_file_id: &mut u16) -> Result<(), AssignIdsError> {
+	for entry in &mut dir.entries {
+		let (id, next_id) = match &mut entry.kind {
+			DirEntryKind::File { id } => (id, &mut *next_file_id),
+			DirEntryKind::Dir { id, .. } => (id, &mut *next_dir_id),
+		};
+
+		*id = *next_id;
+		*next_id = next_id.checked_add(1).ok_or(AssignIdsError::TooManyEntries)?;
+	}
+
+	for entry in &mut dir.entries {
+		if let DirEntryKind::Dir { dir, .. } = &mut entry.kind {
+			self::assign_dir_ids(dir, next_dir_id, next_file_id)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Collects all directories.
+///
+/// Directories without any files use the id of the next file as their first file id.
+fn collect_dirs<'a>(
+	dir: &'a Dir,
+	id: u16,
+	parent_id: u16,
+	next_file_id: &mut u16,
+	dirs: &mut Vec<FntDir<'a>>,
+) -> Result<(), ToWriterError> {
+	let mut file_ids = dir.entries.iter().filter_map(|entry| match entry.kind {
+		DirEntryKind::File { id } => Some(id),
+		DirEntryKind::Dir { .. } => None,
+	});
+	let first_file_id = file_ids.clone().next().unwrap_or(*next_file_id);
+	for (file_id, expected_id) in file_ids.clone().zip(first_file_id..) {
+		if file_id != expected_id {
+			return Err(ToWriterError::NonContiguousFileIds { dir_id: id, file_id });
+		}
+	}
+	if let Some(last_file_id) = file_ids.next_back() {
+		*next_file_id = last_file_id.wrapping_add(1);
+	}
+
+	dirs.push(FntDir {
+		id,
+		parent_id,
+		first_file_id,
+		dir,
+	});
+
+	for entry in &dir.entries {
+		if let DirEntryKind::Dir {
+			id: sub_id,
+			dir: ref sub_dir,
+		} = entry.kind
+		{
+			self::collect_dirs(sub_dir, sub_id, id, next_file_id, dirs)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns the first file id found in a directory or it's sub-directories
+fn first_file_id(dir: &Dir) -> Option<u16> {
+	let file_id = dir.entries.iter().find_map(|entry| match entry.kind {
+		DirEntryKind::File { id } => Some(id),
+		DirEntryKind::Dir { .. } => None,
+	});
+
+	file_id.or_else(|| {
+		dir.entries.iter().find_map(|entry| match entry.kind {
+			DirEntryKind::File { .. } => None,
+			DirEntryKind::Dir { ref dir, .. } => self::first_file_id(dir),
+		})
+	})
 }
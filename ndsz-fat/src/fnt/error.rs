@@ -1,7 +1,10 @@
 //! Errors
 
 // Imports
-use super::main_table;
+use {
+	super::{main_table, sub_table},
+	std::io,
+};
 
 /// Error for [`FileNameTable::from_reader`](super::FileNameTable::from_reader)
 #[derive(Debug, thiserror::Error)]
@@ -14,3 +17,47 @@ pub enum FromReaderError {
 	#[error("Unable to read root directory")]
 	ReadRootDir(#[source] main_table::ReadDirError),
 }
+
+/// Error for [`FileNameTable::assign_ids`](super::FileNameTable::assign_ids)
+#[derive(PartialEq, Eq, Clone, Copy, Debug, thiserror::Error)]
+pub enum AssignIdsError {
+	/// Too many entries
+	#[error("Too many entries")]
+	TooManyEntries,
+}
+
+/// Error for [`FileNameTable::to_writer`](super::FileNameTable::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Directory ids must be contiguous
+	#[error("Directory ids must be contiguous, found {id:#x}")]
+	NonContiguousDirIds { id: u16 },
+
+	/// File ids must be contiguous within a directory
+	#[error("File ids must be contiguous within directory {dir_id:#x}, found {file_id}")]
+	NonContiguousFileIds { dir_id: u16, file_id: u16 },
+
+	/// Too many directories
+	#[error("Too many directories")]
+	TooManyDirs,
+
+	/// File name table too big
+	#[error("File name table too big")]
+	TooBig,
+
+	/// Unable to build sub-table
+	#[error("Unable to build sub-table of directory {id:#x}")]
+	BuildSubTable {
+		id:     u16,
+		#[source]
+		source: sub_table::ToWriterError,
+	},
+
+	/// Unable to write main table
+	#[error("Unable to write main table")]
+	WriteMainTable(#[source] io::Error),
+
+	/// Unable to write sub-tables
+	#[error("Unable to write sub-tables")]
+	WriteSubTables(#[source] io::Error),
+}
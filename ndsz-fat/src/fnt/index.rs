@@ -0,0 +1,123 @@
+//! Fnt index
+
+// Imports
+use {
+	super::FileNameTable,
+	crate::{Dir, DirEntryKind},
+	std::collections::{BTreeMap, HashMap},
+};
+
+/// File name table index.
+///
+/// Maps all file and directory ids to their paths, and back.
+///
+/// Paths are separated by `/`, with the root directory having an empty path.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Index {
+	/// All file paths, by id
+	file_paths: BTreeMap<u16, String>,
+
+	/// All directories, by id
+	dirs: BTreeMap<u16, IndexDir>,
+
+	/// All file ids, by path
+	file_ids: HashMap<String, u16>,
+
+	/// All directory ids, by path
+	dir_ids: HashMap<String, u16>,
+}
+
+impl Index {
+	/// Builds the index of a file name table
+	#[must_use]
+	pub fn new(fnt: &FileNameTable) -> Self {
+		let mut index = Self {
+			file_paths: BTreeMap::new(),
+			dirs:       BTreeMap::new(),
+			file_ids:   HashMap::new(),
+			dir_ids:    HashMap::new(),
+		};
+		index.insert_dir(&fnt.root, FileNameTable::ROOT_ID, None, String::new());
+
+		index
+	}
+
+	/// Returns the path of file `id`
+	#[must_use]
+	pub fn file_path(&self, id: u16) -> Option<&str> {
+		self.file_paths.get(&id).map(String::as_str)
+	}
+
+	/// Returns the path of directory `id`
+	#[must_use]
+	pub fn dir_path(&self, id: u16) -> Option<&str> {
+		self.dirs.get(&id).map(|dir| dir.path.as_str())
+	}
+
+	/// Returns the parent of directory `id`.
+	///
+	/// Returns `None` for the root directory.
+	#[must_use]
+	pub fn dir_parent(&self, id: u16) -> Option<u16> {
+		self.dirs.get(&id)?.parent_id
+	}
+
+	/// Returns the id of the file at `path`
+	#[must_use]
+	pub fn file_id(&self, path: &str) -> Option<u16> {
+		self.file_ids.get(path).copied()
+	}
+
+	/// Returns the id of the directory at `path`
+	#[must_use]
+	pub fn dir_id(&self, path: &str) -> Option<u16> {
+		self.dir_ids.get(path).copied()
+	}
+
+	/// Returns if file `id` is in the file name table
+	#[must_use]
+	pub fn contains_file(&self, id: u16) -> bool {
+		self.file_paths.contains_key(&id)
+	}
+
+	/// Returns all files, ordered by id
+	pub fn files(&self) -> impl Iterator<Item = (u16, &str)> + '_ {
+		self.file_paths.iter().map(|(&id, path)| (id, path.as_str()))
+	}
+
+	/// Returns all directories, ordered by id
+	pub fn dirs(&self) -> impl Iterator<Item = (u16, &str)> + '_ {
+		self.dirs.iter().map(|(&id, dir)| (id, dir.path.as_str()))
+	}
+
+	/// Inserts a directory and all it's entries
+	fn insert_dir(&mut self, dir: &Dir, id: u16, parent_id: Option<u16>, path: String) {
+		for entry in &dir.entries {
+			let entry_path = match path.is_empty() {
+				true => entry.name.as_str().to_owned(),
+				false => format!("{path}/{}", entry.name),
+			};
+
+			match entry.kind {
+				DirEntryKind::File { id: file_id } => {
+					self.file_ids.insert(entry_path.clone(), file_id);
+					self.file_paths.insert(file_id, entry_path);
+				},
+				DirEntryKind::Dir { id: dir_id, ref dir } => self.insert_dir(dir, dir_id, Some(id), entry_path),
+			}
+		}
+
+		self.dir_ids.insert(path.clone(), id);
+		self.dirs.insert(id, IndexDir { path, parent_id });
+	}
+}
+
+/// Index directory
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct IndexDir {
+	/// Path
+	path: String,
+
+	/// Parent id
+	parent_id: Option<u16>,
+}
@@ -9,7 +9,7 @@ pub use self::error::{FromReaderError, ReadDirError, ReadSubTableError};
 // Imports
 use {
 	super::{SubTable, SubTableEntryKind},
-	crate::{Dir, DirEntry, DirEntryKind},
+	crate::{Dir, DirEntry, DirEntryKind, FileNameTable},
 	byteorder::{ByteOrder, LittleEndian},
 	itertools::Itertools,
 	ndsz_util::{IoSlice, ReadByteArray},
@@ -82,6 +82,25 @@ impl MainTableEntry {
 		}
 	}
 
+	/// Writes this entry to bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x8] {
+		let mut bytes = [0; 0x8];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				sub_table_offset: [0x4],
+				first_file_id   : [0x2],
+				parent_id       : [0x2],
+			);
+
+			LittleEndian::write_u32(bytes.sub_table_offset, self.sub_table_offset);
+			LittleEndian::write_u16(bytes.first_file_id, self.first_file_id);
+			LittleEndian::write_u16(bytes.parent_id, self.parent_id);
+		}
+
+		bytes
+	}
+
 	/// Reads the sub-table from this entry
 	pub fn read_sub_table<R: io::Read + io::Seek>(&self, reader: &mut R) -> Result<SubTable, ReadSubTableError> {
 		let mut slice =
@@ -90,7 +109,10 @@ impl MainTableEntry {
 		SubTable::from_reader(&mut slice).map_err(ReadSubTableError::ReadSubTable)
 	}
 
-	/// Reads a directory from this entry
+	/// Reads a directory with id `id` from this entry.
+	///
+	/// `main_entries` are all entries after the root entry, which sub-directories
+	/// are looked up in by their id.
 	pub fn read_dir<R: io::Read + io::Seek>(
 		&self,
 		reader: &mut R,
@@ -98,7 +120,6 @@ impl MainTableEntry {
 		main_entries: &[MainTableEntry],
 	) -> Result<Dir, ReadDirError> {
 		let sub_table = self.read_sub_table(reader).map_err(ReadDirError::ReadSubTable)?;
-		let mut parent_main_entries = main_entries.iter().filter(|main_entry| main_entry.parent_id == id);
 		let mut cur_file_id = self.first_file_id;
 
 		let entries = sub_table
@@ -111,13 +132,25 @@ impl MainTableEntry {
 						cur_file_id += 1;
 						DirEntryKind::File { id }
 					},
-					SubTableEntryKind::Dir { id } => {
-						let main_entry = parent_main_entries.next().ok_or(ReadDirError::NoMainEntry)?;
+					SubTableEntryKind::Dir { id: dir_id } => {
+						let main_entry = dir_id
+							.checked_sub(FileNameTable::ROOT_ID + 1)
+							.and_then(|idx| main_entries.get(usize::from(idx)))
+							.ok_or(ReadDirError::NoMainEntry { id: dir_id })?;
+
+						// Note: Also ensures the directories form a tree, as the root has no parent.
+						if main_entry.parent_id != id {
+							return Err(ReadDirError::WrongParent {
+								id:        dir_id,
+								parent_id: main_entry.parent_id,
+							});
+						}
+
 						let dir = main_entry
-							.read_dir(reader, id, main_entries)
+							.read_dir(reader, dir_id, main_entries)
 							.map_err(|err| ReadDirError::ReadSubDir(Box::new(err)))?;
 
-						DirEntryKind::Dir { id, dir }
+						DirEntryKind::Dir { id: dir_id, dir }
 					},
 				};
 
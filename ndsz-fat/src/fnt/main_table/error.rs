@@ -37,8 +37,12 @@ pub enum ReadDirError {
 	ReadSubTable(#[source] ReadSubTableError),
 
 	/// Directory had no main entry
-	#[error("Directory had no main entry")]
-	NoMainEntry,
+	#[error("Directory {id:#x} had no main entry")]
+	NoMainEntry { id: u16 },
+
+	/// Directory's main entry had a different parent
+	#[error("Directory {id:#x}'s main entry had parent {parent_id:#x}")]
+	WrongParent { id: u16, parent_id: u16 },
 
 	/// Unable to read sub-directory
 	#[error("Unable to read sub-directory")]
@@ -7,7 +7,7 @@ mod error;
 // Exports
 pub use self::{
 	entry::{SubTableEntry, SubTableEntryKind},
-	error::FromReaderError,
+	error::{FromReaderError, ToWriterError},
 };
 
 // Imports
@@ -33,4 +33,16 @@ impl SubTable {
 
 		Ok(Self { entries })
 	}
+
+	/// Writes this sub table to a writer
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), ToWriterError> {
+		for entry in &self.entries {
+			entry.to_writer(writer).map_err(ToWriterError::WriteEntry)?;
+		}
+
+		// Note: The table is terminated by an entry with length `0`
+		writer.write_all(&[0x0]).map_err(ToWriterError::WriteEnd)?;
+
+		Ok(())
+	}
 }
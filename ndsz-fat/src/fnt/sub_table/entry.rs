@@ -4,11 +4,11 @@
 mod error;
 
 // Exports
-pub use self::error::FromReaderError;
+pub use self::error::{FromReaderError, ToWriterError};
 
 // Imports
 use {
-	byteorder::{LittleEndian, ReadBytesExt},
+	byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
 	ndsz_util::AsciiStrArr,
 	std::io,
 };
@@ -59,4 +59,29 @@ impl SubTableEntry {
 
 		Ok(Some(Self { name, kind }))
 	}
+
+	/// Writes this sub table entry to a writer
+	pub fn to_writer<W: io::Write>(self, writer: &mut W) -> Result<(), ToWriterError> {
+		let name = self.name.as_bytes();
+		let len = match name.len() {
+			len @ 0x1..=0x7f => len as u8,
+			len => return Err(ToWriterError::NameLen { len }),
+		};
+
+		match self.kind {
+			SubTableEntryKind::File => {
+				writer.write_u8(len).map_err(ToWriterError::WriteTypeLen)?;
+				writer.write_all(name).map_err(ToWriterError::WriteName)?;
+			},
+			SubTableEntryKind::Dir { id } => {
+				writer.write_u8(0x80 | len).map_err(ToWriterError::WriteTypeLen)?;
+				writer.write_all(name).map_err(ToWriterError::WriteName)?;
+				writer
+					.write_u16::<LittleEndian>(id)
+					.map_err(ToWriterError::WriteDirId)?;
+			},
+		}
+
+		Ok(())
+	}
 }
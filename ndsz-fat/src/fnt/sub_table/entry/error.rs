@@ -26,3 +26,23 @@ pub enum FromReaderError {
 	#[error("Found a reserved dir kind")]
 	ReservedDirKind,
 }
+
+/// Error for [`SubTableEntry::to_writer`](super::SubTableEntry::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Name must be between 1 and 127 characters
+	#[error("Name must be between 1 and 127 characters, found {len}")]
+	NameLen { len: usize },
+
+	/// Unable to write the type/len field
+	#[error("Unable to write the type/len field")]
+	WriteTypeLen(#[source] io::Error),
+
+	/// Unable to write name
+	#[error("Unable to write name")]
+	WriteName(#[source] io::Error),
+
+	/// Unable to write directory id
+	#[error("Unable to write directory id")]
+	WriteDirId(#[source] io::Error),
+}
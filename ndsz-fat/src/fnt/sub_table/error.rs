@@ -1,7 +1,7 @@
 //! Errors
 
 // Imports
-use super::entry;
+use {super::entry, std::io};
 
 /// Error for [`SubTable::from_reader`](super::SubTable::from_reader)
 #[derive(Debug, thiserror::Error)]
@@ -10,3 +10,15 @@ pub enum FromReaderError {
 	#[error("Unable to read entry")]
 	ReadEntry(#[source] entry::FromReaderError),
 }
+
+/// Error for [`SubTable::to_writer`](super::SubTable::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Unable to write entry
+	#[error("Unable to write entry")]
+	WriteEntry(#[source] entry::ToWriterError),
+
+	/// Unable to write end of table
+	#[error("Unable to write end of table")]
+	WriteEnd(#[source] io::Error),
+}
@@ -0,0 +1,68 @@
+//! Tests
+
+// Imports
+use {
+	super::FileNameTable,
+	crate::{Dir, DirEntry, DirEntryKind},
+	ndsz_util::AsciiStrArr,
+	std::{convert::TryFrom, io},
+};
+
+/// Creates a file entry
+fn file(name: &str, id: u16) -> DirEntry {
+	DirEntry {
+		name: AsciiStrArr::try_from(name).expect("Invalid name"),
+		kind: DirEntryKind::File { id },
+	}
+}
+
+/// Creates a directory entry
+fn dir(name: &str, id: u16, entries: Vec<DirEntry>) -> DirEntry {
+	DirEntry {
+		name: AsciiStrArr::try_from(name).expect("Invalid name"),
+		kind: DirEntryKind::Dir {
+			id,
+			dir: Dir { entries },
+		},
+	}
+}
+
+/// Writes and reads back `fnt`
+fn round_trip(fnt: &FileNameTable) -> FileNameTable {
+	let mut bytes = vec![];
+	fnt.to_writer(&mut bytes).expect("Unable to write fnt");
+	FileNameTable::from_reader(&mut io::Cursor::new(bytes)).expect("Unable to read fnt")
+}
+
+#[test]
+fn round_trip_sorted_sub_dirs() {
+	let fnt = FileNameTable {
+		root: Dir {
+			entries: vec![
+				dir("a", 0xf001, vec![file("x", 0)]),
+				dir("b", 0xf002, vec![file("y", 1)]),
+				file("z", 2),
+			],
+		},
+	};
+
+	assert_eq!(self::round_trip(&fnt), fnt);
+}
+
+#[test]
+fn round_trip_unsorted_sub_dirs() {
+	let fnt = FileNameTable {
+		root: Dir {
+			entries: vec![
+				dir("b", 0xf002, vec![file("y", 1), dir("c", 0xf003, vec![file("w", 2)])]),
+				dir("a", 0xf001, vec![file("x", 0)]),
+			],
+		},
+	};
+
+	let read_fnt = self::round_trip(&fnt);
+	assert_eq!(read_fnt, fnt);
+	assert_eq!(read_fnt.root.find("a/x"), Some(0));
+	assert_eq!(read_fnt.root.find("b/y"), Some(1));
+	assert_eq!(read_fnt.root.find("b/c/w"), Some(2));
+}
@@ -6,12 +6,16 @@
 pub mod dir;
 pub mod fat;
 pub mod file_ptr;
+pub mod file_ref;
+pub mod filesystem;
 pub mod fnt;
 
 // Exports
 pub use self::{
 	dir::{Dir, DirEntry, DirEntryKind},
-	fat::FileAllocationTable,
+	fat::{Allocator, FileAllocationTable},
 	file_ptr::FilePtr,
+	file_ref::FileRef,
+	filesystem::Filesystem,
 	fnt::FileNameTable,
 };
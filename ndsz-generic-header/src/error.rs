@@ -3,9 +3,9 @@
 /// Error for [`Header::from_bytes`](super::Header::from_bytes)
 #[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
 pub enum FromBytesError {
-	/// Wrong constant
-	#[error("Wrong constant: {constant:#x}")]
-	WrongConstant { constant: u32 },
+	/// Wrong byte order
+	#[error("Wrong byte order: {byte_order:#x}")]
+	WrongByteOrder { byte_order: u16 },
 
 	/// Wrong header size
 	#[error("Wrong header size: {header_size:#x}")]
@@ -5,9 +5,13 @@
 
 // Modules
 mod error;
+pub mod nitro_file;
 
 // Exports
-pub use error::FromBytesError;
+pub use {
+	error::FromBytesError,
+	nitro_file::{NitroFile, SubSection},
+};
 
 // Imports
 use byteorder::{ByteOrder, LittleEndian};
@@ -18,6 +22,14 @@ pub struct Header {
 	/// Magic
 	pub magic: [u8; 4],
 
+	/// Byte order mark.
+	///
+	/// Either [`Self::BYTE_ORDER`] or [`Self::BYTE_ORDER_SWAPPED`].
+	pub byte_order: u16,
+
+	/// Version
+	pub version: u16,
+
 	/// Section size
 	pub section_size: u32,
 
@@ -26,30 +38,67 @@ pub struct Header {
 }
 
 impl Header {
+	/// Byte order mark used by most formats.
+	///
+	/// Together with the version, the constant after the magic reads as `0xfffe0001`.
+	pub const BYTE_ORDER: u16 = 0xfeff;
+	/// Swapped byte order mark, used by some formats, such as narcs.
+	///
+	/// Together with the version, the constant after the magic reads as `0xfeff0100`.
+	pub const BYTE_ORDER_SWAPPED: u16 = 0xfffe;
+	/// Header size
+	pub const SIZE: usize = 0x10;
+
 	/// Parses a header from bytes
-	pub fn from_bytes(bytes: &[u8; 0x10]) -> Result<Self, FromBytesError> {
+	pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, FromBytesError> {
 		let bytes = ndsz_bytes::array_split!(bytes,
 			magic           : [0x4],
-			constant        : [0x4],
+			byte_order      : [0x2],
+			version         : [0x2],
 			section_size    : [0x4],
 			header_size     : [0x2],
 			sub_sections_len: [0x2],
 		);
 
-		let constant = LittleEndian::read_u32(bytes.constant);
+		// Note: The version isn't checked, as it differs between formats.
+		let byte_order = LittleEndian::read_u16(bytes.byte_order);
 		let header_size = LittleEndian::read_u16(bytes.header_size);
-		if constant != 0xfffe0001 {
-			return Err(FromBytesError::WrongConstant { constant });
+		if byte_order != Self::BYTE_ORDER && byte_order != Self::BYTE_ORDER_SWAPPED {
+			return Err(FromBytesError::WrongByteOrder { byte_order });
 		}
-		if header_size != 0x10 {
+		if usize::from(header_size) != Self::SIZE {
 			return Err(FromBytesError::WrongHeaderSize { header_size });
 		}
 
-
 		Ok(Self {
-			magic:            *bytes.magic,
-			section_size:     LittleEndian::read_u32(bytes.section_size),
+			magic: *bytes.magic,
+			byte_order,
+			version: LittleEndian::read_u16(bytes.version),
+			section_size: LittleEndian::read_u32(bytes.section_size),
 			sub_sections_len: LittleEndian::read_u16(bytes.sub_sections_len),
 		})
 	}
+
+	/// Encodes this header as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; Self::SIZE] {
+		let mut bytes = [0; Self::SIZE];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			magic           : [0x4],
+			byte_order      : [0x2],
+			version         : [0x2],
+			section_size    : [0x4],
+			header_size     : [0x2],
+			sub_sections_len: [0x2],
+		);
+
+		*bytes_mut.magic = self.magic;
+		LittleEndian::write_u16(bytes_mut.byte_order, self.byte_order);
+		LittleEndian::write_u16(bytes_mut.version, self.version);
+		LittleEndian::write_u32(bytes_mut.section_size, self.section_size);
+		LittleEndian::write_u16(bytes_mut.header_size, Self::SIZE as u16);
+		LittleEndian::write_u16(bytes_mut.sub_sections_len, self.sub_sections_len);
+
+		bytes
+	}
 }
@@ -0,0 +1,265 @@
+//! Nitro file
+
+// Modules
+mod error;
+
+// Exports
+pub use error::{FromReaderError, OpenSubSectionError, WriteError};
+
+// Imports
+use {
+	crate::Header,
+	byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt},
+	ndsz_util::{IoSlice, ReadByteArray},
+	std::{
+		convert::TryFrom,
+		io::{self, SeekFrom},
+	},
+};
+
+/// Nitro file.
+///
+/// Made of a [`Header`] and it's sub-sections, each with a 4-byte magic and it's size,
+/// including these 8 bytes, followed by it's data.
+///
+/// Most formats, such as narcs, graphics and most sound formats, store the sub-sections right
+/// after the header, and are read with [`NitroFile::from_reader`]. The 3D formats (`BMD0`, `BTX0`,
+/// `BCA0`, `BTP0` and `BTA0`) instead have a table of offsets to each sub-section after the header,
+/// and are read with [`NitroFile::from_reader_with_offset_table`].
+///
+/// Formats with a different header, such as sound data archives (`SDAT`), whose header is `0x40`
+/// bytes and contains the offset and size of each block, aren't supported.
+#[derive(Clone, Debug)]
+pub struct NitroFile<R> {
+	/// Header
+	header: Header,
+
+	/// Sub-sections
+	sub_sections: Vec<SubSection>,
+
+	/// Reader
+	reader: R,
+
+	/// Start position of the file within the reader
+	start_pos: u64,
+}
+
+impl<R: io::Read + io::Seek> NitroFile<R> {
+	/// Reads a nitro file, with it's sub-sections right after the header, from the current position of a reader.
+	///
+	/// Only the header and sub-section headers are read, the data is read when opening each sub-section.
+	pub fn from_reader(reader: R) -> Result<Self, FromReaderError> {
+		Self::from_reader_with_layout(reader, Layout::Contiguous)
+	}
+
+	/// Reads a nitro file, with a table of offsets to it's sub-sections after the header, from the
+	/// current position of a reader.
+	///
+	/// Only the header, offset table and sub-section headers are read, the data is read when opening
+	/// each sub-section.
+	pub fn from_reader_with_offset_table(reader: R) -> Result<Self, FromReaderError> {
+		Self::from_reader_with_layout(reader, Layout::OffsetTable)
+	}
+
+	/// Reads a nitro file with sub-sections laid out as `layout`
+	fn from_reader_with_layout(mut reader: R, layout: Layout) -> Result<Self, FromReaderError> {
+		let start_pos = reader.stream_position().map_err(FromReaderError::Seek)?;
+
+		// Read the header and check it's size fits within the reader
+		let header = {
+			let bytes = reader.read_byte_array().map_err(FromReaderError::ReadHeader)?;
+			Header::from_bytes(&bytes).map_err(FromReaderError::ParseHeader)?
+		};
+		let section_size = header.section_size;
+		let len = reader.seek(SeekFrom::End(0)).map_err(FromReaderError::Seek)? - start_pos;
+		if (section_size as usize) < Header::SIZE || u64::from(section_size) > len {
+			return Err(FromReaderError::InvalidSectionSize { section_size, len });
+		}
+
+		// Then get the offset of each sub-section, if they're in a table
+		let sub_sections_len = usize::from(header.sub_sections_len);
+		let offsets = match layout {
+			Layout::Contiguous => None,
+			Layout::OffsetTable => {
+				let table_size = 4 * u32::from(header.sub_sections_len);
+				if table_size > section_size - Header::SIZE as u32 {
+					return Err(FromReaderError::InvalidOffsetTableSize {
+						section_size,
+						sub_sections_len: header.sub_sections_len,
+					});
+				}
+
+				reader
+					.seek(SeekFrom::Start(start_pos + Header::SIZE as u64))
+					.map_err(FromReaderError::Seek)?;
+				let offsets = (0..sub_sections_len)
+					.map(|_| reader.read_u32::<LittleEndian>())
+					.collect::<Result<Vec<_>, _>>()
+					.map_err(FromReaderError::ReadOffsetTable)?;
+				Some((Header::SIZE as u32 + table_size, offsets))
+			},
+		};
+
+		// And read all sub-sections, checking they're within the section
+		let mut sub_sections = Vec::with_capacity(sub_sections_len);
+		let mut next_offset = Header::SIZE as u32;
+		for idx in 0..sub_sections_len {
+			let offset = match &offsets {
+				Some((table_end, offsets)) => match offsets[idx] {
+					offset if offset >= *table_end => offset,
+					offset => return Err(FromReaderError::SubSectionOutOfBounds { idx, offset }),
+				},
+				None => next_offset,
+			};
+			if offset > section_size || section_size - offset < SubSection::HEADER_SIZE {
+				return Err(FromReaderError::SubSectionOutOfBounds { idx, offset });
+			}
+
+			reader
+				.seek(SeekFrom::Start(start_pos + u64::from(offset)))
+				.map_err(FromReaderError::Seek)?;
+			let bytes = reader
+				.read_byte_array::<0x8>()
+				.map_err(|source| FromReaderError::ReadSubSectionHeader { idx, source })?;
+			let bytes = ndsz_bytes::array_split!(&bytes,
+				magic: [0x4],
+				size : [0x4],
+			);
+			let sub_section = SubSection {
+				magic: *bytes.magic,
+				offset,
+				size: LittleEndian::read_u32(bytes.size),
+			};
+			if sub_section.size < SubSection::HEADER_SIZE || sub_section.size > section_size - offset {
+				return Err(FromReaderError::InvalidSubSectionSize { idx, sub_section });
+			}
+
+			next_offset = offset + sub_section.size;
+			sub_sections.push(sub_section);
+		}
+
+		Ok(Self {
+			header,
+			sub_sections,
+			reader,
+			start_pos,
+		})
+	}
+
+	/// Opens the data of a sub-section by it's index
+	pub fn open_sub_section(&mut self, idx: usize) -> Result<IoSlice<&mut R>, OpenSubSectionError> {
+		let sub_section = *self
+			.sub_sections
+			.get(idx)
+			.ok_or(OpenSubSectionError::NotFound { idx })?;
+
+		IoSlice::new_with_offset_len(
+			&mut self.reader,
+			self.start_pos + u64::from(sub_section.data_offset()),
+			u64::from(sub_section.data_size()),
+		)
+		.map_err(OpenSubSectionError::Slice)
+	}
+}
+
+impl<R> NitroFile<R> {
+	/// Returns the header
+	#[must_use]
+	pub const fn header(&self) -> &Header {
+		&self.header
+	}
+
+	/// Returns all sub-sections, in order
+	#[must_use]
+	pub fn sub_sections(&self) -> &[SubSection] {
+		&self.sub_sections
+	}
+
+	/// Finds the index of the first sub-section with magic `magic`
+	#[must_use]
+	pub fn find_sub_section(&self, magic: [u8; 4]) -> Option<usize> {
+		self.sub_sections
+			.iter()
+			.position(|sub_section| sub_section.magic == magic)
+	}
+
+	/// Consumes this file and returns the reader
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+}
+
+/// Layout of the sub-sections
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Layout {
+	/// Sub-sections are right after the header, one after the other
+	Contiguous,
+
+	/// A table of offsets to each sub-section is right after the header
+	OffsetTable,
+}
+
+/// Sub-section
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SubSection {
+	/// Magic
+	pub magic: [u8; 4],
+
+	/// Offset, from the start of the file
+	pub offset: u32,
+
+	/// Size, including the sub-section header
+	pub size: u32,
+}
+
+impl SubSection {
+	/// Sub-section header size
+	pub const HEADER_SIZE: u32 = 0x8;
+
+	/// Returns the offset of the data, from the start of the file
+	#[must_use]
+	pub const fn data_offset(self) -> u32 {
+		self.offset + Self::HEADER_SIZE
+	}
+
+	/// Returns the size of the data
+	#[must_use]
+	pub const fn data_size(self) -> u32 {
+		self.size - Self::HEADER_SIZE
+	}
+}
+
+/// Writes a nitro file, given it's magic, version and the magic and data of each sub-section.
+///
+/// The byte order mark is always [`Header::BYTE_ORDER`].
+pub fn write<W: io::Write>(
+	writer: &mut W,
+	magic: [u8; 4],
+	version: u16,
+	sub_sections: &[([u8; 4], &[u8])],
+) -> Result<(), WriteError> {
+	let section_size = sub_sections
+		.iter()
+		.map(|(_, data)| data.len() + SubSection::HEADER_SIZE as usize)
+		.sum::<usize>() +
+		Header::SIZE;
+	let header = Header {
+		magic,
+		byte_order: Header::BYTE_ORDER,
+		version,
+		section_size: u32::try_from(section_size).map_err(|_| WriteError::TooBig)?,
+		sub_sections_len: u16::try_from(sub_sections.len()).map_err(|_| WriteError::TooManySubSections)?,
+	};
+	writer.write_all(&header.to_bytes()).map_err(WriteError::WriteHeader)?;
+
+	for (idx, &(magic, data)) in sub_sections.iter().enumerate() {
+		let write_sub_section = |writer: &mut W| {
+			writer.write_all(&magic)?;
+			writer.write_u32::<LittleEndian>(data.len() as u32 + SubSection::HEADER_SIZE)?;
+			writer.write_all(data)
+		};
+		write_sub_section(writer).map_err(|source| WriteError::WriteSubSection { idx, source })?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,91 @@
+//! Errors
+
+// Imports
+use {super::SubSection, crate::FromBytesError, std::io};
+
+/// Error for [`NitroFile::from_reader`](super::NitroFile::from_reader) and
+/// [`NitroFile::from_reader_with_offset_table`](super::NitroFile::from_reader_with_offset_table)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to seek
+	#[error("Unable to seek")]
+	Seek(#[source] io::Error),
+
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] io::Error),
+
+	/// Unable to parse header
+	#[error("Unable to parse header")]
+	ParseHeader(#[source] FromBytesError),
+
+	/// Invalid section size
+	#[error("Invalid section size {section_size:#x} for file of size {len:#x}")]
+	InvalidSectionSize { section_size: u32, len: u64 },
+
+	/// Invalid offset table size
+	#[error("Offset table of {sub_sections_len} sub-sections didn't fit in section of size {section_size:#x}")]
+	InvalidOffsetTableSize {
+		section_size:     u32,
+		sub_sections_len: u16,
+	},
+
+	/// Unable to read offset table
+	#[error("Unable to read offset table")]
+	ReadOffsetTable(#[source] io::Error),
+
+	/// Sub-section out of bounds
+	#[error("Sub-section {idx} at {offset:#x} was out of bounds")]
+	SubSectionOutOfBounds { idx: usize, offset: u32 },
+
+	/// Unable to read sub-section header
+	#[error("Unable to read sub-section {idx} header")]
+	ReadSubSectionHeader {
+		idx:    usize,
+		#[source]
+		source: io::Error,
+	},
+
+	/// Invalid sub-section size
+	#[error("Sub-section {idx} had an invalid size: {sub_section:?}")]
+	InvalidSubSectionSize {
+		idx:         usize,
+		sub_section: SubSection,
+	},
+}
+
+/// Error for [`NitroFile::open_sub_section`](super::NitroFile::open_sub_section)
+#[derive(Debug, thiserror::Error)]
+pub enum OpenSubSectionError {
+	/// Sub-section not found
+	#[error("Sub-section {idx} not found")]
+	NotFound { idx: usize },
+
+	/// Unable to slice reader
+	#[error("Unable to slice reader")]
+	Slice(#[source] io::Error),
+}
+
+/// Error for [`write`](super::write)
+#[derive(Debug, thiserror::Error)]
+pub enum WriteError {
+	/// File was too big
+	#[error("File was too big")]
+	TooBig,
+
+	/// Too many sub-sections
+	#[error("Too many sub-sections")]
+	TooManySubSections,
+
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] io::Error),
+
+	/// Unable to write sub-section
+	#[error("Unable to write sub-section {idx}")]
+	WriteSubSection {
+		idx:    usize,
+		#[source]
+		source: io::Error,
+	},
+}
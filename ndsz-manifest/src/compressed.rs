@@ -0,0 +1,157 @@
+//! Compressed files
+//!
+//! Files decompressed when extracting are recorded in `compressed_files.yaml`, alongside their
+//! compression, while the decompressed arm9 binary and overlays are recorded in `compressed_code.yaml`.
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_compress::{Compression, DecompressReader},
+	ndsz_nds::{ModuleParams, Overlay},
+	std::{
+		collections::{HashMap, HashSet},
+		fs,
+		io::Read,
+		path::{Path, PathBuf},
+	},
+};
+
+/// Name of the compressed files manifest
+pub const FILES_MANIFEST_NAME: &str = "compressed_files.yaml";
+
+/// Name of the compressed code manifest
+pub const CODE_MANIFEST_NAME: &str = "compressed_code.yaml";
+
+/// Compressed file
+#[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct CompressedFile {
+	/// Path of the file, relative to the output directory
+	pub path: PathBuf,
+
+	/// Compression
+	pub compression: Compression,
+}
+
+/// File decompressor
+#[derive(Debug)]
+pub struct Decompressor {
+	/// Output directory
+	output_path: PathBuf,
+
+	/// All decompressed files
+	files: Vec<CompressedFile>,
+
+	/// All decompressed arm9 and overlay binaries, relative to the output directory
+	code_files: Vec<PathBuf>,
+}
+
+impl Decompressor {
+	/// Creates a new decompressor for files within `output_path`
+	#[must_use]
+	pub fn new(output_path: &Path) -> Self {
+		Self {
+			output_path: output_path.to_path_buf(),
+			files:       vec![],
+			code_files:  vec![],
+		}
+	}
+
+	/// Decompresses `path` in-place, if it's compressed.
+	///
+	/// See [`DecompressReader`] for when files are considered compressed.
+	pub fn decompress(&mut self, path: &Path) -> Result<(), anyhow::Error> {
+		let file = fs::File::open(path).context("Unable to open file")?;
+		let mut reader = DecompressReader::new(file).context("Unable to detect compression")?;
+		let compression = match reader.compression() {
+			Some(compression) => compression,
+			None => return Ok(()),
+		};
+		let mut decompressed = vec![];
+		reader
+			.read_to_end(&mut decompressed)
+			.context("Unable to read decompressed file")?;
+		drop(reader);
+
+		println!("{} ({compression:?})", path.display());
+		fs::write(path, decompressed).context("Unable to write decompressed file")?;
+		self.files.push(CompressedFile {
+			path: crate::relative_path(&self.output_path, path)?,
+			compression,
+		});
+
+		Ok(())
+	}
+
+	/// Decompresses the arm9 binary at `path` in-place, if it's compressed.
+	///
+	/// Binaries without module params, such as homebrew ones, are never considered compressed.
+	pub fn decompress_arm9(&mut self, path: &Path, load_address: u32) -> Result<(), anyhow::Error> {
+		let data = fs::read(path).context("Unable to read arm9 binary")?;
+		if ModuleParams::find(&data).is_none() {
+			return Ok(());
+		}
+
+		let decompressed = match ndsz_nds::arm9::decompress(&data, load_address).context("Unable to decompress")? {
+			Some(decompressed) => decompressed,
+			None => return Ok(()),
+		};
+
+		println!("{} (blz)", path.display());
+		fs::write(path, decompressed).context("Unable to write decompressed arm9 binary")?;
+		self.code_files.push(crate::relative_path(&self.output_path, path)?);
+
+		Ok(())
+	}
+
+	/// Decompresses `overlay` at `path` in-place, if it's compressed, updating it's flags
+	pub fn decompress_overlay(&mut self, path: &Path, overlay: &mut Overlay) -> Result<(), anyhow::Error> {
+		if !overlay.is_compressed() {
+			return Ok(());
+		}
+
+		let data = fs::read(path).context("Unable to read overlay")?;
+		let decompressed = match overlay.decompress(&data).context("Unable to decompress")? {
+			Some(decompressed) => decompressed,
+			None => return Ok(()),
+		};
+
+		println!("{} (blz)", path.display());
+		fs::write(path, decompressed).context("Unable to write decompressed overlay")?;
+		self.code_files.push(crate::relative_path(&self.output_path, path)?);
+
+		Ok(())
+	}
+
+	/// Outputs the `compressed_files.yaml` manifest, and the `compressed_code.yaml`
+	/// manifest, if any code was decompressed
+	pub fn output_yaml(&self) -> Result<(), anyhow::Error> {
+		crate::write(&self.output_path, FILES_MANIFEST_NAME, &self.files)?;
+		if !self.code_files.is_empty() {
+			crate::write(&self.output_path, CODE_MANIFEST_NAME, &self.code_files)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Reads all files in the `compressed_files.yaml` manifest, if it exists.
+///
+/// Returned paths are joined with `input_path`.
+pub fn read_files(input_path: &Path) -> Result<HashMap<PathBuf, Compression>, anyhow::Error> {
+	let files = crate::read::<Vec<CompressedFile>>(input_path, FILES_MANIFEST_NAME)?.unwrap_or_default();
+
+	Ok(files
+		.into_iter()
+		.map(|file| (input_path.join(file.path), file.compression))
+		.collect())
+}
+
+/// Reads all binaries in the `compressed_code.yaml` manifest, if it exists.
+///
+/// Returned paths are joined with `input_path`.
+pub fn read_code(input_path: &Path) -> Result<HashSet<PathBuf>, anyhow::Error> {
+	let paths = crate::read::<Vec<PathBuf>>(input_path, CODE_MANIFEST_NAME)?.unwrap_or_default();
+
+	Ok(paths.into_iter().map(|path| input_path.join(path)).collect())
+}
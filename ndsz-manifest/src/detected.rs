@@ -0,0 +1,122 @@
+//! Detected files
+//!
+//! Files renamed by their file type when extracting are recorded in `detected_files.yaml`,
+//! alongside their original name.
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_compress::DecompressReader,
+	ndsz_detect::FileType,
+	std::{
+		collections::HashMap,
+		ffi::OsStr,
+		fs,
+		path::{Path, PathBuf},
+	},
+};
+
+/// Name of the detected files manifest
+pub const MANIFEST_NAME: &str = "detected_files.yaml";
+
+/// Detected file
+#[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct DetectedFile {
+	/// Path of the file, relative to the output directory
+	pub path: PathBuf,
+
+	/// Original name of the file
+	pub name: String,
+
+	/// File type
+	pub file_type: FileType,
+}
+
+/// File type detector
+#[derive(Debug)]
+pub struct Detector {
+	/// Output directory
+	output_path: PathBuf,
+
+	/// If files will be decompressed
+	decompress: bool,
+
+	/// All renamed files
+	files: Vec<DetectedFile>,
+}
+
+impl Detector {
+	/// Creates a new detector for files within `output_path`.
+	///
+	/// If `decompress` is set, the file type is detected after decompressing.
+	#[must_use]
+	pub fn new(output_path: &Path, decompress: bool) -> Self {
+		Self {
+			output_path: output_path.to_path_buf(),
+			decompress,
+			files: vec![],
+		}
+	}
+
+	/// Detects the file type of `path` and, if it has no extension, or a `.bin` extension,
+	/// renames it with the extension of the file type.
+	///
+	/// Returns the new path of the file.
+	pub fn rename(&mut self, path: &Path) -> Result<PathBuf, anyhow::Error> {
+		if path.extension().is_some_and(|ext| ext != "bin") {
+			return Ok(path.to_path_buf());
+		}
+
+		// Detect the file type
+		let mut file = fs::File::open(path).context("Unable to open file")?;
+		let file_type = match self.decompress {
+			true => {
+				let mut reader = DecompressReader::new(file).context("Unable to detect compression")?;
+				ndsz_detect::detect(&mut reader)
+			},
+			false => ndsz_detect::detect(&mut file),
+		}
+		.context("Unable to detect file type")?;
+		let file_type = match file_type {
+			Some(file_type) => file_type,
+			None => return Ok(path.to_path_buf()),
+		};
+
+		// Then rename it, unless another file already has the new name
+		let new_path = path.with_extension(file_type.extension());
+		if new_path.exists() {
+			return Ok(path.to_path_buf());
+		}
+		println!("{} ({file_type:?})", new_path.display());
+		fs::rename(path, &new_path).context("Unable to rename file")?;
+		self.files.push(DetectedFile {
+			path: crate::relative_path(&self.output_path, &new_path)?,
+			name: path
+				.file_name()
+				.and_then(OsStr::to_str)
+				.with_context(|| format!("Path {path:?} had an invalid file name"))?
+				.to_owned(),
+			file_type,
+		});
+
+		Ok(new_path)
+	}
+
+	/// Outputs the `detected_files.yaml` manifest
+	pub fn output_yaml(&self) -> Result<(), anyhow::Error> {
+		crate::write(&self.output_path, MANIFEST_NAME, &self.files)
+	}
+}
+
+/// Reads the original names of all files in the `detected_files.yaml` manifest, if it exists.
+///
+/// Returned paths are joined with `input_path`.
+pub fn read_names(input_path: &Path) -> Result<HashMap<PathBuf, String>, anyhow::Error> {
+	let files = crate::read::<Vec<DetectedFile>>(input_path, MANIFEST_NAME)?.unwrap_or_default();
+
+	Ok(files
+		.into_iter()
+		.map(|file| (input_path.join(file.path), file.name))
+		.collect())
+}
@@ -0,0 +1,76 @@
+//! Fnt order
+//!
+//! The original order of all filesystem paths is recorded, so that it may be kept when
+//! rebuilding, as the order directory entries are read from disk is unspecified.
+//!
+//! Directories end with a `/`.
+
+// Imports
+use {
+	ndsz_fat::{dir, Dir},
+	ndsz_util::AsciiStrArr,
+	std::path::Path,
+};
+
+/// Name of the fnt order manifest, for narcs
+pub const MANIFEST_NAME: &str = "fnt_order.yaml";
+
+/// Collects all paths within `root`, in their original order
+#[must_use]
+pub fn collect(root: &Dir) -> Vec<String> {
+	let mut paths = vec![];
+	root.walk(&mut CollectPathsVisitor {
+		cur_path: String::new(),
+		paths:    &mut paths,
+	})
+	.into_ok();
+
+	paths
+}
+
+/// Outputs the `fnt_order.yaml` manifest with all paths within `root`
+pub fn output_yaml(output_path: &Path, root: &Dir) -> Result<(), anyhow::Error> {
+	crate::write(output_path, MANIFEST_NAME, &self::collect(root))
+}
+
+/// Reads the `fnt_order.yaml` manifest, if it exists
+pub fn read(input_path: &Path) -> Result<Option<Vec<String>>, anyhow::Error> {
+	crate::read(input_path, MANIFEST_NAME)
+}
+
+/// Visitor to collect all paths
+struct CollectPathsVisitor<'paths> {
+	/// Current path, ending with a `/`, if not empty
+	cur_path: String,
+
+	/// All paths
+	paths: &'paths mut Vec<String>,
+}
+
+impl<'paths> dir::Visitor for CollectPathsVisitor<'paths> {
+	type Error = !;
+	type SubDirVisitor<'visitor, 'entry>
+		= CollectPathsVisitor<'visitor>
+	where
+		Self: 'visitor;
+
+	fn visit_file(&mut self, name: &AsciiStrArr<0x80>, _id: u16) -> Result<(), Self::Error> {
+		self.paths.push(format!("{}{name}", self.cur_path));
+
+		Ok(())
+	}
+
+	fn visit_dir<'visitor, 'entry>(
+		&'visitor mut self,
+		name: &'entry AsciiStrArr<0x80>,
+		_id: u16,
+	) -> Result<Self::SubDirVisitor<'visitor, 'entry>, Self::Error> {
+		let cur_path = format!("{}{name}/", self.cur_path);
+		self.paths.push(cur_path.clone());
+
+		Ok(CollectPathsVisitor {
+			cur_path,
+			paths: self.paths,
+		})
+	}
+}
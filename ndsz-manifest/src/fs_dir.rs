@@ -0,0 +1,186 @@
+//! Filesystem directory
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_fat::{Dir, DirEntry, DirEntryKind, FileNameTable},
+	ndsz_util::AsciiStrArr,
+	std::{
+		collections::{BTreeMap, HashMap, HashSet},
+		convert::TryFrom,
+		fs,
+		path::{Path, PathBuf},
+	},
+};
+
+/// Directory on disk
+struct DiskDir {
+	/// Entries
+	entries: Vec<DiskDirEntry>,
+}
+
+/// Directory entry on disk
+struct DiskDirEntry {
+	/// Name
+	name: AsciiStrArr<0x80>,
+
+	/// Kind
+	kind: DiskDirEntryKind,
+}
+
+/// Directory entry kind on disk
+enum DiskDirEntryKind {
+	/// File
+	File { path: PathBuf },
+
+	/// Directory
+	Dir { dir: DiskDir },
+}
+
+/// Reads the filesystem at `path` into a file name table.
+///
+/// Files are given contiguous ids, starting at `first_file_id`, and the
+/// paths of each are returned in order of their ids.
+///
+/// Entries are ordered by their position in `fnt_order`, if given, with any entries
+/// not in it being ordered by name after all others.
+///
+/// Any paths in `skip_paths` are ignored, and any files in `names` are given that name,
+/// instead of their name on disk.
+pub fn read_fnt(
+	path: &Path,
+	first_file_id: u16,
+	fnt_order: Option<&[String]>,
+	skip_paths: &HashSet<PathBuf>,
+	names: &HashMap<PathBuf, String>,
+) -> Result<(FileNameTable, Vec<PathBuf>), anyhow::Error> {
+	let order = fnt_order
+		.unwrap_or_default()
+		.iter()
+		.enumerate()
+		.map(|(pos, path)| (path.as_str(), pos))
+		.collect::<HashMap<_, _>>();
+	let disk_dir = match path.exists() {
+		true => self::read_disk_dir(path, "", &order, skip_paths, names)?,
+		false => DiskDir { entries: vec![] },
+	};
+
+	let mut fnt = FileNameTable {
+		root: self::to_dir(&disk_dir),
+	};
+	fnt.assign_ids(first_file_id).context("Unable to assign ids")?;
+
+	let mut file_paths = BTreeMap::new();
+	self::collect_file_paths(&fnt.root, &disk_dir, &mut file_paths);
+
+	Ok((fnt, file_paths.into_values().collect()))
+}
+
+/// Reads a flat list of files at `path`, named by their ids.
+///
+/// Returns the paths of each file, in order of their ids.
+///
+/// Any paths in `skip_paths` are ignored.
+pub fn read_flat(path: &Path, skip_paths: &HashSet<PathBuf>) -> Result<Vec<PathBuf>, anyhow::Error> {
+	let file_paths = fs::read_dir(path)
+		.with_context(|| format!("Unable to read directory {path:?}"))?
+		.filter(|entry| !matches!(entry, Ok(entry) if skip_paths.contains(&entry.path())))
+		.map(|entry| {
+			let path = entry.context("Unable to read directory entry")?.path();
+			let id = path
+				.file_stem()
+				.and_then(|stem| stem.to_str())
+				.and_then(|stem| stem.parse::<usize>().ok())
+				.with_context(|| format!("File {path:?} must be named by it's id"))?;
+
+			Ok((id, path))
+		})
+		.collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+
+	for (idx, &id) in file_paths.keys().enumerate() {
+		anyhow::ensure!(idx == id, "File {idx} is missing");
+	}
+
+	Ok(file_paths.into_values().collect())
+}
+
+/// Reads a directory from disk.
+///
+/// `fnt_path` is the path of the directory within the filesystem, ending with a `/`, if not empty.
+fn read_disk_dir(
+	path: &Path,
+	fnt_path: &str,
+	order: &HashMap<&str, usize>,
+	skip_paths: &HashSet<PathBuf>,
+	names: &HashMap<PathBuf, String>,
+) -> Result<DiskDir, anyhow::Error> {
+	let mut entries = fs::read_dir(path)
+		.with_context(|| format!("Unable to read directory {path:?}"))?
+		.filter(|entry| !matches!(entry, Ok(entry) if skip_paths.contains(&entry.path())))
+		.map(|entry| {
+			let entry = entry.context("Unable to read directory entry")?;
+			let path = entry.path();
+			let name = match names.get(&path) {
+				Some(name) => AsciiStrArr::try_from(name.as_str()),
+				None => AsciiStrArr::try_from(entry.file_name().as_os_str()),
+			}
+			.with_context(|| format!("Invalid file name {path:?}"))?;
+			let file_type = entry
+				.file_type()
+				.with_context(|| format!("Unable to get file type of {path:?}"))?;
+
+			let (kind, entry_fnt_path) = match file_type.is_dir() {
+				true => {
+					let entry_fnt_path = format!("{fnt_path}{name}/");
+					let dir = self::read_disk_dir(&path, &entry_fnt_path, order, skip_paths, names)?;
+					(DiskDirEntryKind::Dir { dir }, entry_fnt_path)
+				},
+				false => (DiskDirEntryKind::File { path }, format!("{fnt_path}{name}")),
+			};
+			let pos = order.get(entry_fnt_path.as_str()).copied();
+
+			Ok((pos, DiskDirEntry { name, kind }))
+		})
+		.collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+	// Note: Directory iteration order is unspecified, so we sort by the original
+	//       order, then by name.
+	entries.sort_by_key(|(pos, entry)| (pos.is_none(), *pos, entry.name));
+	let entries = entries.into_iter().map(|(_, entry)| entry).collect();
+
+	Ok(DiskDir { entries })
+}
+
+/// Converts a directory on disk into a directory, with all ids left as `0`
+fn to_dir(disk_dir: &DiskDir) -> Dir {
+	let entries = disk_dir
+		.entries
+		.iter()
+		.map(|entry| DirEntry {
+			name: entry.name,
+			kind: match &entry.kind {
+				DiskDirEntryKind::File { .. } => DirEntryKind::File { id: 0 },
+				DiskDirEntryKind::Dir { dir } => DirEntryKind::Dir {
+					id:  0,
+					dir: self::to_dir(dir),
+				},
+			},
+		})
+		.collect();
+
+	Dir { entries }
+}
+
+/// Collects the paths of all files in `dir`, by id, from the directory on disk it was converted from
+fn collect_file_paths(dir: &Dir, disk_dir: &DiskDir, file_paths: &mut BTreeMap<u16, PathBuf>) {
+	for (entry, disk_entry) in dir.entries.iter().zip(&disk_dir.entries) {
+		match (&entry.kind, &disk_entry.kind) {
+			(&DirEntryKind::File { id }, DiskDirEntryKind::File { path }) => {
+				file_paths.insert(id, path.clone());
+			},
+			(DirEntryKind::Dir { dir, .. }, DiskDirEntryKind::Dir { dir: disk_dir }) =>
+				self::collect_file_paths(dir, disk_dir, file_paths),
+			_ => unreachable!("Directory entry kind didn't match it's kind on disk"),
+		}
+	}
+}
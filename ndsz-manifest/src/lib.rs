@@ -0,0 +1,59 @@
+//! Extraction manifests
+//!
+//! Manifests are output by `ndsz-unnds` and `ndsz-unnarc` alongside the extracted files,
+//! recording how they were transformed, so that `ndsz-mknds` and `ndsz-mknarc` may reverse it.
+
+// Features
+#![feature(never_type, unwrap_infallible)]
+
+// Modules
+pub mod compressed;
+pub mod detected;
+pub mod fnt_order;
+pub mod fs_dir;
+pub mod narc;
+pub mod nested;
+
+// Imports
+use {
+	anyhow::Context,
+	std::{
+		fs,
+		path::{Path, PathBuf},
+	},
+};
+
+/// Names of all manifests output by `ndsz-unnarc` alongside the extracted files
+pub const NARC_MANIFEST_NAMES: &[&str] = &[
+	nested::MANIFEST_NAME,
+	compressed::FILES_MANIFEST_NAME,
+	detected::MANIFEST_NAME,
+	fnt_order::MANIFEST_NAME,
+];
+
+/// Writes a manifest named `name` within `output_path`
+fn write<T: serde::Serialize>(output_path: &Path, name: &str, value: &T) -> Result<(), anyhow::Error> {
+	let manifest_path = output_path.join(name);
+	let manifest_file =
+		fs::File::create(manifest_path).with_context(|| format!("Unable to create manifest {name:?}"))?;
+	serde_yaml::to_writer(manifest_file, value).with_context(|| format!("Unable to write manifest {name:?}"))
+}
+
+/// Reads a manifest named `name` within `input_path`, if it exists
+fn read<T: serde::de::DeserializeOwned>(input_path: &Path, name: &str) -> Result<Option<T>, anyhow::Error> {
+	let manifest_path = input_path.join(name);
+	if !manifest_path.exists() {
+		return Ok(None);
+	}
+
+	let manifest_file = fs::File::open(manifest_path).with_context(|| format!("Unable to open manifest {name:?}"))?;
+	let value = serde_yaml::from_reader(manifest_file).with_context(|| format!("Unable to parse manifest {name:?}"))?;
+	Ok(Some(value))
+}
+
+/// Returns `path` relative to `output_path`
+fn relative_path(output_path: &Path, path: &Path) -> Result<PathBuf, anyhow::Error> {
+	path.strip_prefix(output_path)
+		.map(Path::to_path_buf)
+		.with_context(|| format!("Path {path:?} wasn't within the output directory"))
+}
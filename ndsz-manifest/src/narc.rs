@@ -0,0 +1,66 @@
+//! Narc building
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_compress::Compression,
+	ndsz_fat::{fat::Allocator, FileAllocationTable, FileNameTable},
+	ndsz_narc::{Data, Narc},
+	ndsz_util::IoSlice,
+	std::{
+		collections::HashMap,
+		convert::TryFrom,
+		fs,
+		io::{self, Seek, Write},
+		path::{Path, PathBuf},
+	},
+};
+
+/// Builds a narc with `fnt` from the files at `file_paths`, in order of their ids.
+///
+/// Any files in `compressed_files` are compressed.
+pub fn build(
+	fnt: FileNameTable,
+	file_paths: &[PathBuf],
+	compressed_files: &HashMap<PathBuf, Compression>,
+) -> Result<Narc<io::Cursor<Vec<u8>>>, anyhow::Error> {
+	// Lay out all files and write them to the data
+	let mut allocator = Allocator::new(Allocator::NARC_ALIGNMENT, 0).with_padding(Narc::<()>::PADDING);
+	let mut data = io::Cursor::new(vec![]);
+	let mut ptrs = Vec::with_capacity(file_paths.len());
+	for path in file_paths {
+		println!("{}", path.display());
+		let file = self::read_file(path, compressed_files)?;
+		let size = u32::try_from(file.len()).with_context(|| format!("File {path:?} too big"))?;
+		let ptr = allocator.alloc(size).context("Narc too big")?;
+
+		let cur_pos = data.stream_position().context("Unable to get data position")?;
+		allocator
+			.pad(&mut data, u64::from(ptr.start_address) - cur_pos)
+			.context("Unable to write padding")?;
+		data.write_all(&file).context("Unable to write file")?;
+
+		ptrs.push(ptr);
+	}
+
+	Ok(Narc {
+		fat: FileAllocationTable { ptrs },
+		fnt,
+		data: Data(IoSlice::new(data, ..).context("Unable to create data slice")?),
+	})
+}
+
+/// Reads the file at `path`, compressing it if it's in `compressed_files`
+fn read_file(path: &Path, compressed_files: &HashMap<PathBuf, Compression>) -> Result<Vec<u8>, anyhow::Error> {
+	let file = fs::read(path).with_context(|| format!("Unable to read {path:?}"))?;
+	match compressed_files.get(path) {
+		Some(compression) => {
+			let mut compressed = vec![];
+			compression
+				.compress(&file, &mut compressed)
+				.with_context(|| format!("Unable to compress {path:?}"))?;
+			Ok(compressed)
+		},
+		None => Ok(file),
+	}
+}
@@ -0,0 +1,212 @@
+//! Nested narcs
+//!
+//! Narcs within other files are extracted to `{path}.d/` and recorded in `nested_narcs.yaml`.
+
+// Imports
+use {
+	crate::{compressed::Decompressor, detected::Detector, fnt_order, fs_dir},
+	anyhow::Context,
+	ndsz_compress::Compression,
+	ndsz_fat::{fnt, Dir, FileNameTable},
+	ndsz_narc::Narc,
+	ndsz_util::ReadByteArray,
+	std::{
+		collections::{HashMap, HashSet},
+		ffi::OsString,
+		fs,
+		io::{self, Seek, SeekFrom, Write},
+		path::{Path, PathBuf},
+	},
+};
+
+/// Name of the nested narcs manifest
+pub const MANIFEST_NAME: &str = "nested_narcs.yaml";
+
+/// Nested narc
+#[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct NestedNarc {
+	/// Path of the narc, relative to the output directory
+	pub path: PathBuf,
+
+	/// Directory the narc was extracted to, relative to the output directory
+	pub dir: PathBuf,
+
+	/// If the narc had no fnt, and so was extracted as `{idx}.bin` files.
+	pub flat: bool,
+
+	/// All filesystem paths of the narc, in their original order.
+	///
+	/// See [`fnt_order`](crate::fnt_order) for details.
+	pub fnt_order: Vec<String>,
+}
+
+/// Nested narc extractor
+#[derive(Debug)]
+pub struct NestedExtractor {
+	/// Output directory
+	output_path: PathBuf,
+
+	/// All extracted narcs, in extraction order.
+	///
+	/// Narcs always appear before any narcs within them.
+	narcs: Vec<NestedNarc>,
+}
+
+impl NestedExtractor {
+	/// Creates a new extractor for files within `output_path`
+	#[must_use]
+	pub fn new(output_path: &Path) -> Self {
+		Self {
+			output_path: output_path.to_path_buf(),
+			narcs:       vec![],
+		}
+	}
+
+	/// Extracts `path` to `{path}.d/`, if it's a narc, along with all narcs within it.
+	///
+	/// If `detector` is given, all files within are renamed by their file type, and if `decompressor`
+	/// is given, all files within are decompressed before being checked.
+	pub fn extract(
+		&mut self,
+		path: &Path,
+		mut decompressor: Option<&mut Decompressor>,
+		mut detector: Option<&mut Detector>,
+	) -> Result<(), anyhow::Error> {
+		// Check if the file is a narc
+		let mut file = fs::File::open(path).context("Unable to open file")?;
+		let is_narc = match file.read_byte_array() {
+			Ok(header_bytes) => ndsz_narc::Header::from_bytes(&header_bytes).is_ok(),
+			Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => false,
+			Err(err) => return Err(err).context("Unable to read header"),
+		};
+		if !is_narc {
+			return Ok(());
+		}
+
+		// If so, read it
+		file.seek(SeekFrom::Start(0))
+			.context("Unable to seek to start of file")?;
+		let mut narc = Narc::from_reader(file).with_context(|| format!("Unable to read narc {path:?}"))?;
+		let dir = {
+			let mut dir = OsString::from(path);
+			dir.push(".d");
+			PathBuf::from(dir)
+		};
+		println!("{}", dir.display());
+		fs::create_dir_all(&dir).context("Unable to create directory")?;
+
+		// Then extract all files
+		let flat = narc.fnt.root.entries.is_empty();
+		self.narcs.push(NestedNarc {
+			path: crate::relative_path(&self.output_path, path)?,
+			dir: crate::relative_path(&self.output_path, &dir)?,
+			flat,
+			fnt_order: fnt_order::collect(&narc.fnt.root),
+		});
+		let files = match flat {
+			true => (0..)
+				.zip(&narc.fat.ptrs)
+				.map(|(id, _)| (id, dir.join(format!("{id}.bin"))))
+				.collect::<Vec<_>>(),
+			false => {
+				let fnt_index = fnt::Index::new(&narc.fnt);
+				for (_, dir_path) in fnt_index.dirs() {
+					fs::create_dir_all(dir.join(dir_path)).context("Unable to create directory")?;
+				}
+
+				fnt_index
+					.files()
+					.map(|(id, file_path)| (id, dir.join(file_path)))
+					.collect()
+			},
+		};
+		for (id, file_path) in files {
+			println!("{}", file_path.display());
+			let mut file = narc
+				.open_file(id)
+				.with_context(|| format!("Unable to open narc file #{id}"))?;
+			let mut output_file = fs::File::create(&file_path).context("Unable to create output file")?;
+			io::copy(&mut file, &mut output_file).context("Unable to write to output file")?;
+			drop(output_file);
+
+			// And check for any nested narcs within them
+			let file_path = match detector.as_deref_mut() {
+				Some(detector) => detector
+					.rename(&file_path)
+					.with_context(|| format!("Unable to detect file type of {file_path:?}"))?,
+				None => file_path,
+			};
+			if let Some(decompressor) = decompressor.as_deref_mut() {
+				decompressor
+					.decompress(&file_path)
+					.with_context(|| format!("Unable to decompress {file_path:?}"))?;
+			}
+			self.extract(&file_path, decompressor.as_deref_mut(), detector.as_deref_mut())
+				.with_context(|| format!("Unable to extract {file_path:?}"))?;
+		}
+
+		Ok(())
+	}
+
+	/// Outputs the `nested_narcs.yaml` manifest
+	pub fn output_yaml(&self) -> Result<(), anyhow::Error> {
+		crate::write(&self.output_path, MANIFEST_NAME, &self.narcs)
+	}
+}
+
+/// Reads the directories of all nested narcs in the `nested_narcs.yaml` manifest, if it exists.
+///
+/// Returned paths are joined with `input_path`.
+pub fn read_dirs(input_path: &Path) -> Result<HashSet<PathBuf>, anyhow::Error> {
+	let narcs = crate::read::<Vec<NestedNarc>>(input_path, MANIFEST_NAME)?.unwrap_or_default();
+
+	Ok(narcs.into_iter().map(|narc| input_path.join(narc.dir)).collect())
+}
+
+/// Repacks all nested narcs in the `nested_narcs.yaml` manifest, if it exists, from their directories.
+///
+/// Narcs are repacked innermost first, replacing the original narc files, so that
+/// they may be read as any other file afterwards.
+///
+/// Any files in `compressed_files` are compressed, and any files in `names` are given that name.
+/// See [`fs_dir::read_fnt`] for details.
+pub fn repack(
+	input_path: &Path,
+	compressed_files: &HashMap<PathBuf, Compression>,
+	names: &HashMap<PathBuf, String>,
+) -> Result<(), anyhow::Error> {
+	let narcs = crate::read::<Vec<NestedNarc>>(input_path, MANIFEST_NAME)?.unwrap_or_default();
+	let dirs = narcs
+		.iter()
+		.map(|narc| input_path.join(&narc.dir))
+		.collect::<HashSet<_>>();
+
+	// Note: Narcs always appear before any narcs within them, so we go in reverse.
+	for narc in narcs.iter().rev() {
+		let path = input_path.join(&narc.path);
+		let dir = input_path.join(&narc.dir);
+		let (fnt, file_paths) = match narc.flat {
+			true => {
+				let fnt = FileNameTable {
+					root: Dir { entries: vec![] },
+				};
+				let file_paths = fs_dir::read_flat(&dir, &dirs).with_context(|| format!("Unable to read {dir:?}"))?;
+				(fnt, file_paths)
+			},
+			false => fs_dir::read_fnt(&dir, 0, Some(&narc.fnt_order), &dirs, names)
+				.with_context(|| format!("Unable to read filesystem {dir:?}"))?,
+		};
+
+		let mut narc = crate::narc::build(fnt, &file_paths, compressed_files)
+			.with_context(|| format!("Unable to build narc {path:?}"))?;
+		println!("{}", path.display());
+		let file = fs::File::create(&path).with_context(|| format!("Unable to create {path:?}"))?;
+		let mut file = io::BufWriter::new(file);
+		narc.to_writer(&mut file)
+			.with_context(|| format!("Unable to write narc {path:?}"))?;
+		file.flush().with_context(|| format!("Unable to flush {path:?}"))?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,30 @@
+//! Arguments
+
+// Imports
+use std::path::PathBuf;
+
+/// Arguments
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(clap::Parser)]
+pub struct Args {
+	/// Input path.
+	///
+	/// Should be a directory created by `ndsz-unnarc`
+	pub input_path: PathBuf,
+
+	/// Output path.
+	///
+	/// Defaults to `input_path` with the `.narc` extension
+	#[clap(long = "output", short = 'o')]
+	pub output_path: Option<PathBuf>,
+
+	/// Narcless
+	#[clap(long = "narcless")]
+	pub narcless: bool,
+
+	/// Reads the input as a flat list of files, without a fnt.
+	///
+	/// Files should be named `{idx}.bin`, as extracted by `ndsz-unnarc --extract-fat-on-empty-fnt`
+	#[clap(long = "flat")]
+	pub flat: bool,
+}
@@ -0,0 +1,76 @@
+//! Creates a `.narc`
+
+// Modules
+mod args;
+mod manifest;
+
+// Imports
+use {
+	self::args::Args,
+	anyhow::Context,
+	clap::Parser,
+	ndsz_fat::{Dir, FileNameTable},
+	ndsz_manifest::fs_dir,
+	std::{
+		fs,
+		io::{self, Write},
+	},
+	tracing_subscriber::prelude::*,
+};
+
+
+fn main() -> Result<(), anyhow::Error> {
+	// Initialize the logger
+	tracing_subscriber::registry()
+		.with(tracing_subscriber::fmt::layer().with_filter(tracing_subscriber::EnvFilter::from_default_env()))
+		.init();
+
+	// Get the arguments
+	let args = Args::parse();
+
+	// Get the output path
+	let output_path = match args.output_path {
+		Some(path) => path,
+		None => args.input_path.with_extension("narc"),
+	};
+
+	// Read the manifests and repack all nested narcs
+	let skip_paths = manifest::read_skipped_paths(&args.input_path).context("Unable to read nested narcs manifest")?;
+	let compressed_files =
+		ndsz_manifest::compressed::read_files(&args.input_path).context("Unable to read compressed files manifest")?;
+	let names =
+		ndsz_manifest::detected::read_names(&args.input_path).context("Unable to read detected files manifest")?;
+	ndsz_manifest::nested::repack(&args.input_path, &compressed_files, &names)
+		.context("Unable to repack nested narcs")?;
+
+	// Then read the filesystem, skipping any manifests and nested narcs' directories
+	let (fnt, file_paths) = match args.flat {
+		true => {
+			let fnt = FileNameTable {
+				root: Dir { entries: vec![] },
+			};
+			let file_paths = fs_dir::read_flat(&args.input_path, &skip_paths).context("Unable to read files")?;
+			(fnt, file_paths)
+		},
+		false => {
+			let fnt_order =
+				ndsz_manifest::fnt_order::read(&args.input_path).context("Unable to read fnt order manifest")?;
+			fs_dir::read_fnt(&args.input_path, 0, fnt_order.as_deref(), &skip_paths, &names)
+				.context("Unable to read filesystem")?
+		},
+	};
+
+	// And finally build and write the narc
+	let mut narc = ndsz_manifest::narc::build(fnt, &file_paths, &compressed_files).context("Unable to build narc")?;
+	let output_file = fs::File::create(&output_path).context("Unable to create output file")?;
+	let mut output_file = io::BufWriter::new(output_file);
+	match args.narcless {
+		true => narc
+			.narcless_to_writer(&mut output_file)
+			.context("Unable to write narc")?,
+		false => narc.to_writer(&mut output_file).context("Unable to write narc")?,
+	}
+	output_file.flush().context("Unable to flush output file")?;
+
+	Ok(())
+}
@@ -0,0 +1,25 @@
+//! Manifests
+
+// Imports
+use {
+	ndsz_manifest::nested,
+	std::{
+		collections::HashSet,
+		path::{Path, PathBuf},
+	},
+};
+
+/// Reads all paths that should be skipped when reading the filesystem.
+///
+/// These are the manifests themselves and the directories of all nested narcs in
+/// the `nested_narcs.yaml` manifest, if it exists, joined with `input_path`.
+pub fn read_skipped_paths(input_path: &Path) -> Result<HashSet<PathBuf>, anyhow::Error> {
+	let mut paths = nested::read_dirs(input_path)?;
+	paths.extend(
+		ndsz_manifest::NARC_MANIFEST_NAMES
+			.iter()
+			.map(|name| input_path.join(name)),
+	);
+
+	Ok(paths)
+}
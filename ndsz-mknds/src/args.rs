@@ -0,0 +1,24 @@
+//! Arguments
+
+// Imports
+use std::path::PathBuf;
+
+/// Arguments
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(clap::Parser)]
+pub struct Args {
+	/// Input path.
+	///
+	/// Should be a directory created by `ndsz-unnds`, from a rom without a dsi header
+	pub input_path: PathBuf,
+
+	/// Output path.
+	///
+	/// Defaults to `input_path` with the `.nds` extension
+	#[clap(long = "output", short = 'o')]
+	pub output_path: Option<PathBuf>,
+
+	/// Don't pad the rom up to the device capacity
+	#[clap(long = "trim")]
+	pub trim: bool,
+}
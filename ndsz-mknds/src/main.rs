@@ -1,5 +1,686 @@
 //! Creates a `.nds`
 
-fn main() {
-	println!("Hello, world!");
+// Modules
+mod args;
+mod manifest;
+
+// Imports
+use {
+	self::args::Args,
+	anyhow::Context,
+	clap::Parser,
+	ndsz_compress::Compression,
+	ndsz_fat::{fat::Allocator, FileAllocationTable, FilePtr},
+	ndsz_manifest::fs_dir,
+	ndsz_nds::{
+		banner,
+		header::{ArmLoadData, TableLoadData},
+		layout::PartKind,
+		Banner,
+		Overlay,
+		OverlayTable,
+	},
+	std::{
+		collections::{BTreeMap, HashMap, HashSet},
+		convert::TryFrom,
+		fs,
+		io::{self, Read, Write},
+		path::{Path, PathBuf},
+	},
+	tracing_subscriber::prelude::*,
+};
+
+
+/// Offset of the arm9 binary
+const ARM9_OFFSET: u32 = 0x4000;
+
+fn main() -> Result<(), anyhow::Error> {
+	// Initialize the logger
+	tracing_subscriber::registry()
+		.with(tracing_subscriber::fmt::layer().with_filter(tracing_subscriber::EnvFilter::from_default_env()))
+		.init();
+
+	// Get the arguments
+	let args = Args::parse();
+
+	// Get the output path
+	let output_path = match args.output_path {
+		Some(path) => path,
+		None => args.input_path.with_extension("nds"),
+	};
+
+	// Read the manifest, if any, and the header
+	let manifest = manifest::read(&args.input_path).context("Unable to read header manifest")?;
+	let mut header = match &manifest {
+		Some(manifest) => manifest.header,
+		None => {
+			let header_bytes = self::read_header(&args.input_path)?;
+			ndsz_nds::Header::from_bytes(&header_bytes).context("Unable to parse header")?
+		},
+	};
+	tracing::trace!(?header);
+
+	// Note: We don't write the dsi header or the twl region, so bail instead of writing a broken rom
+	anyhow::ensure!(
+		!header.unit_code.has_dsi_header(),
+		"Roms with a dsi header (unit code {:?}) aren't supported",
+		header.unit_code
+	);
+
+	// Read all overlays, hidden files and the filesystem, repacking all nested narcs first
+	let arm9_overlays =
+		self::read_overlays(&args.input_path.join("overlay9")).context("Unable to read arm9 overlays")?;
+	let arm7_overlays =
+		self::read_overlays(&args.input_path.join("overlay7")).context("Unable to read arm7 overlays")?;
+	let nested_narc_dirs =
+		ndsz_manifest::nested::read_dirs(&args.input_path).context("Unable to read nested narcs manifest")?;
+	let compressed_files =
+		ndsz_manifest::compressed::read_files(&args.input_path).context("Unable to read compressed files manifest")?;
+	let compressed_code =
+		ndsz_manifest::compressed::read_code(&args.input_path).context("Unable to read compressed code manifest")?;
+	let names =
+		ndsz_manifest::detected::read_names(&args.input_path).context("Unable to read detected files manifest")?;
+	ndsz_manifest::nested::repack(&args.input_path, &compressed_files, &names)
+		.context("Unable to repack nested narcs")?;
+	let hidden_files = self::read_hidden_files(&args.input_path.join("fat"), &nested_narc_dirs)?;
+	if let Some(manifest) = &manifest {
+		for id in &manifest.hidden_fat_files {
+			if !hidden_files.contains_key(id) {
+				tracing::warn!("Hidden file {id} is missing");
+			}
+		}
+	}
+	let overlay_file_ids = arm9_overlays
+		.iter()
+		.chain(&arm7_overlays)
+		.flatten()
+		.map(|(overlay, _)| u16::try_from(overlay.file_id).context("Overlay file id too big"))
+		.collect::<Result<Vec<_>, anyhow::Error>>()?;
+	let first_file_id = match hidden_files.keys().chain(&overlay_file_ids).max() {
+		Some(id) => id.checked_add(1).context("Too many hidden files")?,
+		None => 0,
+	};
+	let fs_path = args.input_path.join("fs");
+	let fnt_order = manifest.as_ref().map(|manifest| manifest.fnt_order.as_slice());
+	let (fnt, fs_files) = fs_dir::read_fnt(&fs_path, first_file_id, fnt_order, &nested_narc_dirs, &names)
+		.context("Unable to read filesystem")?;
+	let mut fnt_bytes = vec![];
+	fnt.to_writer(&mut fnt_bytes).context("Unable to build fnt")?;
+	let files_len = usize::from(first_file_id) + fs_files.len();
+	let fat_len = u32::try_from(files_len * 0x8).context("Too many files")?;
+
+	// Then collect all parts of the rom
+	let mut parts = vec![self::arm9_part(
+		args.input_path.join("arm9_load_data.bin"),
+		header.arm9_load_data.load_address,
+		&compressed_code,
+	)?];
+	match arm9_overlays {
+		Some(overlays) => {
+			self::push_overlay_parts(&mut parts, PartKind::Arm9OverlayTable, overlays, &compressed_code)?;
+		},
+		None => parts.push(self::path_part(
+			PartKind::Arm9OverlayTable,
+			args.input_path.join("arm9_overlay_table.bin"),
+		)?),
+	}
+	for (&id, path) in &hidden_files {
+		parts.push(self::file_part(PartKind::File { id }, path.clone(), &compressed_files)?);
+	}
+	parts.push(self::path_part(
+		PartKind::Arm7,
+		args.input_path.join("arm7_load_data.bin"),
+	)?);
+	match arm7_overlays {
+		Some(overlays) => {
+			self::push_overlay_parts(&mut parts, PartKind::Arm7OverlayTable, overlays, &compressed_code)?;
+		},
+		None => parts.push(self::path_part(
+			PartKind::Arm7OverlayTable,
+			args.input_path.join("arm7_overlay_table.bin"),
+		)?),
+	}
+	parts.push(self::bytes_part(PartKind::FileNameTable, fnt_bytes)?);
+	parts.push(PendingPart {
+		kind: PartKind::FileAllocationTable,
+		size: fat_len,
+		data: PartData::Bytes(vec![]),
+	});
+	parts.push(match self::read_banner(&args.input_path)? {
+		Some(banner) => self::bytes_part(PartKind::Banner, banner)?,
+		None => self::path_part(PartKind::Banner, args.input_path.join("banner.bin"))?,
+	});
+	let mut fs_file_ids = HashMap::new();
+	for (path, id) in fs_files.into_iter().zip(first_file_id..) {
+		let fs_file_path = path
+			.strip_prefix(&fs_path)
+			.with_context(|| format!("Filesystem file {path:?} wasn't within the filesystem"))?
+			.iter()
+			.map(|component| component.to_str())
+			.collect::<Option<Vec<_>>>()
+			.with_context(|| format!("Filesystem path {path:?} wasn't utf-8"))?
+			.join("/");
+		fs_file_ids.insert(fs_file_path.clone(), id);
+		parts.push(self::file_part(
+			PartKind::FsFile { path: fs_file_path },
+			path,
+			&compressed_files,
+		)?);
+	}
+
+	// And layout them, following the original layout, if we have it
+	let alignment = match &manifest {
+		Some(manifest) => {
+			manifest.layout.sort_by_order(&mut parts, |part| &part.kind);
+			manifest.layout.alignment
+		},
+		None => Allocator::ROM_ALIGNMENT,
+	};
+	let original_offsets = manifest
+		.as_ref()
+		.map(|manifest| {
+			manifest
+				.layout
+				.parts
+				.iter()
+				.map(|part| (&part.kind, part.offset))
+				.collect::<HashMap<_, _>>()
+		})
+		.unwrap_or_default();
+	let mut layout = Layout::new(ARM9_OFFSET, alignment);
+	let mut offsets = HashMap::new();
+	let mut fat_part_idx = None;
+	for part in parts {
+		let offset_size = layout.push(part.size, part.data, original_offsets.get(&part.kind).copied())?;
+		if part.kind == PartKind::FileAllocationTable && part.size != 0 {
+			fat_part_idx = Some(layout.parts.len() - 1);
+		}
+		offsets.insert(part.kind, offset_size);
+	}
+	let offset_size_of = |kind| offsets.get(&kind).copied().unwrap_or((0, 0));
+
+	// Write the fat now that all files have been laid out
+	let mut file_ptrs = vec![
+		FilePtr {
+			start_address: 0,
+			end_address:   0,
+		};
+		files_len
+	];
+	for (kind, &offset_size) in &offsets {
+		let id = match kind {
+			PartKind::File { id } => *id,
+			PartKind::FsFile { path } => fs_file_ids[path],
+			_ => continue,
+		};
+		file_ptrs[usize::from(id)] = self::file_ptr(offset_size);
+	}
+	let mut fat_bytes = Vec::with_capacity(file_ptrs.len() * 0x8);
+	FileAllocationTable { ptrs: file_ptrs }
+		.to_writer(&mut fat_bytes)
+		.context("Unable to write fat")?;
+	if let Some(fat_part_idx) = fat_part_idx {
+		layout.parts[fat_part_idx].data = PartData::Bytes(fat_bytes);
+	}
+
+	// Get the device capacity, keeping the original one if it's big enough
+	let ntr_region_rom_size = layout.allocator.cur_offset();
+	let device_capacity_size = loop {
+		let size = header
+			.device_capacity_size()
+			.with_context(|| format!("Invalid device capacity {}", header.device_capacity))?;
+		if size >= u64::from(ntr_region_rom_size) {
+			break size;
+		}
+		header.device_capacity += 1;
+	};
+
+	// Then update the header
+	let arm9 = offset_size_of(PartKind::Arm9);
+	anyhow::ensure!(arm9.1 != 0, "Arm9 binary must exist and not be empty");
+	let arm7 = offset_size_of(PartKind::Arm7);
+	anyhow::ensure!(arm7.1 != 0, "Arm7 binary must exist and not be empty");
+	header.arm9_load_data = ArmLoadData {
+		offset: arm9.0,
+		size: arm9.1,
+		..header.arm9_load_data
+	};
+	header.arm7_load_data = ArmLoadData {
+		offset: arm7.0,
+		size: arm7.1,
+		..header.arm7_load_data
+	};
+	header.file_name_table = self::table_load_data(offset_size_of(PartKind::FileNameTable));
+	header.file_allocation_table = self::table_load_data(offset_size_of(PartKind::FileAllocationTable));
+	header.arm9_overlay_table = self::table_load_data(offset_size_of(PartKind::Arm9OverlayTable));
+	header.arm7_overlay_table = self::table_load_data(offset_size_of(PartKind::Arm7OverlayTable));
+	header.icon_banner_offset = offset_size_of(PartKind::Banner).0;
+	header.ntr_region_rom_size = ntr_region_rom_size;
+	header.update_crcs();
+	let header_bytes = header.to_bytes();
+
+	// And finally write the rom
+	let output_file = fs::File::create(&output_path).context("Unable to create output file")?;
+	let mut output_file = io::BufWriter::new(output_file);
+	output_file.write_all(&header_bytes).context("Unable to write header")?;
+	let mut cur_offset = 0x180;
+	for part in &layout.parts {
+		// Note: The header region is padded with zeros, while everything else is padded by the allocator.
+		let padding = u64::from(part.offset - cur_offset);
+		match part.offset <= ARM9_OFFSET {
+			true => self::write_padding(&mut output_file, padding, 0x0)?,
+			false => layout
+				.allocator
+				.pad(&mut output_file, padding)
+				.context("Unable to write padding")?,
+		}
+
+		match &part.data {
+			PartData::Path(path) => {
+				println!("{}", path.display());
+				let mut file = fs::File::open(path).with_context(|| format!("Unable to open {path:?}"))?;
+				let written =
+					io::copy(&mut file, &mut output_file).with_context(|| format!("Unable to write {path:?}"))?;
+				anyhow::ensure!(
+					written == u64::from(part.size),
+					"File {path:?} changed size while writing rom"
+				);
+			},
+			PartData::Bytes(bytes) => output_file.write_all(bytes).context("Unable to write to output file")?,
+		}
+
+		cur_offset = part.offset + part.size;
+	}
+	if !args.trim {
+		let padding = device_capacity_size - u64::from(cur_offset);
+		layout
+			.allocator
+			.pad(&mut output_file, padding)
+			.context("Unable to write padding")?;
+	}
+	output_file.flush().context("Unable to flush output file")?;
+
+	Ok(())
+}
+
+/// Reads the header, as well as the header parts
+fn read_header(input_path: &Path) -> Result<[u8; 0x180], anyhow::Error> {
+	let header_bytes = fs::read(input_path.join("header.bin")).context("Unable to read header")?;
+	let mut header_bytes = <[u8; 0x180]>::try_from(header_bytes.as_slice()).context("Header must be 0x180 bytes")?;
+
+	let parts = [
+		(0x15, 0x7, "reserved1"),
+		(0x88, 0x38, "reserved2"),
+		(0x160, 0x20, "reserved_debugger"),
+		(0xc0, 0x9c, "nintendo_logo"),
+	];
+	for (offset, size, name) in parts {
+		let mut path = input_path.join(name);
+		path.set_extension("bin");
+
+		// Note: If they don't exist, we just use the header's value
+		if !path.exists() {
+			continue;
+		}
+
+		let bytes = fs::read(&path).with_context(|| format!("Unable to read {name}"))?;
+		anyhow::ensure!(bytes.len() == size, "{name} must be {size:#x} bytes");
+		header_bytes[offset..(offset + size)].copy_from_slice(&bytes);
+	}
+
+	Ok(header_bytes)
+}
+
+/// Reads all overlays from an overlay directory, alongside their paths.
+///
+/// Returns `None` if the directory has no overlay manifest.
+fn read_overlays(path: &Path) -> Result<Option<Vec<(Overlay, PathBuf)>>, anyhow::Error> {
+	let manifest_path = path.join("overlays.yaml");
+	if !manifest_path.exists() {
+		return Ok(None);
+	}
+
+	let manifest_file = fs::File::open(&manifest_path).context("Unable to open overlays manifest")?;
+	let overlays =
+		serde_yaml::from_reader::<_, Vec<Overlay>>(manifest_file).context("Unable to parse overlays manifest")?;
+	let overlays = overlays
+		.into_iter()
+		.map(|overlay| {
+			let overlay_path = path.join(format!("overlay_{:04}.bin", overlay.id));
+			(overlay, overlay_path)
+		})
+		.collect();
+
+	Ok(Some(overlays))
+}
+
+/// Adds the parts of an overlay table, followed by all of it's overlays.
+///
+/// Overlays in `compressed_code` are compressed, updating their flags.
+fn push_overlay_parts(
+	parts: &mut Vec<PendingPart>,
+	table_kind: PartKind,
+	overlays: Vec<(Overlay, PathBuf)>,
+	compressed_code: &HashSet<PathBuf>,
+) -> Result<(), anyhow::Error> {
+	// Get the parts of all overlays first, compressing them and updating their compressed size
+	let mut table = OverlayTable { overlays: vec![] };
+	let mut overlay_parts = vec![];
+	for (mut overlay, path) in overlays {
+		let kind = PartKind::File {
+			id: u16::try_from(overlay.file_id).context("Overlay file id too big")?,
+		};
+		let part = match compressed_code.contains(&path) {
+			true => {
+				let data = fs::read(&path).with_context(|| format!("Unable to read {path:?}"))?;
+				match overlay
+					.compress(&data)
+					.with_context(|| format!("Unable to compress {path:?}"))?
+				{
+					Some(compressed) => {
+						println!("{} (blz)", path.display());
+						self::bytes_part(kind, compressed)?
+					},
+					None => {
+						tracing::warn!("Overlay {path:?} isn't compressible, leaving it uncompressed");
+						self::path_part(kind, path)?
+					},
+				}
+			},
+			false => {
+				if overlay.is_compressed() {
+					let size = fs::metadata(&path)
+						.with_context(|| format!("Unable to get metadata of {path:?}"))?
+						.len();
+					overlay.compressed_size = u32::try_from(size)
+						.ok()
+						.filter(|&size| size < 1 << 24)
+						.with_context(|| format!("Compressed overlay {path:?} too big"))?;
+				}
+				self::path_part(kind, path)?
+			},
+		};
+		table.overlays.push(overlay);
+		overlay_parts.push(part);
+	}
+
+	// Then the table, followed by all overlays
+	let mut table_bytes = vec![];
+	table.to_writer(&mut table_bytes).expect("Unable to write to vector");
+	parts.push(self::bytes_part(table_kind, table_bytes)?);
+	parts.extend(overlay_parts);
+
+	Ok(())
+}
+
+/// Creates the arm9 binary part, compressing it if it's in `compressed_code`
+fn arm9_part(
+	path: PathBuf,
+	load_address: u32,
+	compressed_code: &HashSet<PathBuf>,
+) -> Result<PendingPart, anyhow::Error> {
+	if !compressed_code.contains(&path) {
+		return self::path_part(PartKind::Arm9, path);
+	}
+
+	let arm9 = fs::read(&path).with_context(|| format!("Unable to read {path:?}"))?;
+	match ndsz_nds::arm9::compress(&arm9, load_address).with_context(|| format!("Unable to compress {path:?}"))? {
+		Some(compressed) => {
+			println!("{} (blz)", path.display());
+			self::bytes_part(PartKind::Arm9, compressed)
+		},
+		None => {
+			tracing::warn!("Arm9 binary {path:?} isn't compressible, leaving it uncompressed");
+			self::path_part(PartKind::Arm9, path)
+		},
+	}
+}
+
+/// Reads the banner from `banner.yaml` and it's icons.
+///
+/// Returns `None` if there's no manifest, or a `banner.bin` exists, which takes priority.
+fn read_banner(input_path: &Path) -> Result<Option<Vec<u8>>, anyhow::Error> {
+	let yaml_path = input_path.join("banner.yaml");
+	if input_path.join("banner.bin").exists() || !yaml_path.exists() {
+		return Ok(None);
+	}
+
+	let yaml_file = fs::File::open(yaml_path).context("Unable to open banner manifest")?;
+	let mut banner = serde_yaml::from_reader::<_, Banner>(yaml_file).context("Unable to parse banner manifest")?;
+
+	banner.icon = self::read_icon_png(&input_path.join("icon.png"))?;
+	if let Some(animation) = &mut banner.animation {
+		for (idx, (bitmap, palette)) in animation.bitmaps.iter_mut().zip(&mut animation.palettes).enumerate() {
+			let icon = self::read_icon_png(&input_path.join(format!("icon_{idx}.png")))?;
+			*bitmap = icon.bitmap;
+			*palette = icon.palette;
+		}
+	}
+
+	let bytes = banner.to_bytes().context("Unable to write banner")?;
+	Ok(Some(bytes))
+}
+
+/// Reads an icon from an indexed png
+fn read_icon_png(path: &Path) -> Result<banner::Icon, anyhow::Error> {
+	let file = fs::File::open(path).with_context(|| format!("Unable to open {path:?}"))?;
+	let decoder = png::Decoder::new(io::BufReader::new(file));
+	let mut reader = decoder
+		.read_info()
+		.with_context(|| format!("Unable to read png {path:?}"))?;
+	let info = reader.info();
+	anyhow::ensure!(
+		info.color_type == png::ColorType::Indexed,
+		"Icon {path:?} must be an indexed png"
+	);
+	anyhow::ensure!(
+		(info.width, info.height) == (32, 32),
+		"Icon {path:?} must be 32x32, found {}x{}",
+		info.width,
+		info.height
+	);
+	let bit_depth = info.bit_depth;
+
+	// Read the palette, filling any missing colors with black
+	let png_palette = info.palette.as_deref().unwrap_or(&[]);
+	anyhow::ensure!(png_palette.len() <= 16 * 3, "Icon {path:?} must have at most 16 colors");
+	let mut colors = [[0; 3]; 16];
+	for (color, rgb) in colors.iter_mut().zip(png_palette.chunks_exact(3)) {
+		color.copy_from_slice(rgb);
+	}
+	let palette = banner::Palette::from_rgb(&colors);
+
+	// Then read all pixels
+	let mut data = vec![0; reader.output_buffer_size()];
+	reader
+		.next_frame(&mut data)
+		.with_context(|| format!("Unable to read png {path:?}"))?;
+	let mut pixels = [0; 0x400];
+	for (y, row) in pixels.chunks_exact_mut(32).enumerate() {
+		for (x, pixel) in row.iter_mut().enumerate() {
+			*pixel = match bit_depth {
+				png::BitDepth::Eight => data[y * 32 + x],
+				png::BitDepth::Four => (data[y * 16 + x / 2] >> (4 * (1 - x % 2))) & 0xf,
+				_ => anyhow::bail!("Icon {path:?} must have a bit depth of 4 or 8"),
+			};
+			anyhow::ensure!(*pixel < 16, "Icon {path:?} uses colors past the first 16");
+		}
+	}
+
+	Ok(banner::Icon {
+		bitmap: banner::Bitmap::from_pixels(&pixels),
+		palette,
+	})
+}
+
+/// Reads all hidden files.
+///
+/// Any directories in `skip_dirs` are ignored.
+fn read_hidden_files(path: &Path, skip_dirs: &HashSet<PathBuf>) -> Result<BTreeMap<u16, PathBuf>, anyhow::Error> {
+	// If there's no directory, there are no hidden files
+	if !path.exists() {
+		return Ok(BTreeMap::new());
+	}
+
+	fs::read_dir(path)
+		.context("Unable to read hidden files directory")?
+		.filter(|entry| !matches!(entry, Ok(entry) if skip_dirs.contains(&entry.path())))
+		.map(|entry| {
+			let path = entry.context("Unable to read hidden file entry")?.path();
+			let id = path
+				.file_stem()
+				.and_then(|stem| stem.to_str())
+				.and_then(|stem| stem.parse::<u16>().ok())
+				.with_context(|| format!("Hidden file {path:?} must be named by it's id"))?;
+
+			Ok((id, path))
+		})
+		.collect()
+}
+
+/// Creates a file pointer from an offset and size
+const fn file_ptr((offset, size): (u32, u32)) -> FilePtr {
+	FilePtr {
+		start_address: offset,
+		end_address:   offset + size,
+	}
+}
+
+/// Creates a table load data from an offset and size
+const fn table_load_data((offset, length): (u32, u32)) -> TableLoadData {
+	TableLoadData { offset, length }
+}
+
+/// Writes `len` bytes of padding
+fn write_padding<W: Write>(writer: &mut W, len: u64, fill: u8) -> Result<(), anyhow::Error> {
+	io::copy(&mut io::repeat(fill).take(len), writer).context("Unable to write padding")?;
+
+	Ok(())
+}
+
+/// Creates a part from a file.
+///
+/// If the file doesn't exist, it is treated as empty.
+fn path_part(kind: PartKind, path: PathBuf) -> Result<PendingPart, anyhow::Error> {
+	let size = match path.exists() {
+		true => fs::metadata(&path)
+			.with_context(|| format!("Unable to get metadata of {path:?}"))?
+			.len(),
+		false => 0,
+	};
+	let size = u32::try_from(size).with_context(|| format!("File {path:?} too big"))?;
+
+	Ok(PendingPart {
+		kind,
+		size,
+		data: PartData::Path(path),
+	})
+}
+
+/// Creates a part from a file, compressing it if it's in `compressed_files`
+fn file_part(
+	kind: PartKind,
+	path: PathBuf,
+	compressed_files: &HashMap<PathBuf, Compression>,
+) -> Result<PendingPart, anyhow::Error> {
+	match compressed_files.get(&path) {
+		Some(compression) => {
+			let file = fs::read(&path).with_context(|| format!("Unable to read {path:?}"))?;
+			let mut compressed = vec![];
+			compression
+				.compress(&file, &mut compressed)
+				.with_context(|| format!("Unable to compress {path:?}"))?;
+			println!("{} ({compression:?})", path.display());
+			self::bytes_part(kind, compressed)
+		},
+		None => self::path_part(kind, path),
+	}
+}
+
+/// Creates a part from bytes
+fn bytes_part(kind: PartKind, bytes: Vec<u8>) -> Result<PendingPart, anyhow::Error> {
+	let size = u32::try_from(bytes.len()).context("Part too big")?;
+	Ok(PendingPart {
+		kind,
+		size,
+		data: PartData::Bytes(bytes),
+	})
+}
+
+/// Rom layout
+struct Layout {
+	/// All parts, in order
+	parts: Vec<Part>,
+
+	/// Allocator
+	allocator: Allocator,
+}
+
+impl Layout {
+	/// Creates a new layout starting at `offset`
+	fn new(offset: u32, alignment: u32) -> Self {
+		Self {
+			parts:     vec![],
+			allocator: Allocator::new(alignment, offset).with_padding(0xff),
+		}
+	}
+
+	/// Adds a part to the layout and returns it's offset and size.
+	///
+	/// If `original_offset` is after all current parts, the part is placed there,
+	/// else it is placed, aligned, after them.
+	///
+	/// Empty parts aren't added and have offset and size `0`, unless they have an original offset.
+	fn push(&mut self, size: u32, data: PartData, original_offset: Option<u32>) -> Result<(u32, u32), anyhow::Error> {
+		let ptr = match original_offset {
+			Some(offset) => self.allocator.alloc_at(offset, size),
+			None => self.allocator.alloc(size),
+		}
+		.context("Rom too big")?;
+		let offset = ptr.start_address;
+
+		if size == 0 {
+			return match original_offset {
+				Some(_) => Ok((offset, 0)),
+				None => Ok((0, 0)),
+			};
+		}
+
+		self.parts.push(Part { offset, size, data });
+
+		Ok((offset, size))
+	}
+}
+
+/// Rom part, before being laid out
+struct PendingPart {
+	/// Kind
+	kind: PartKind,
+
+	/// Size
+	size: u32,
+
+	/// Data
+	data: PartData,
+}
+
+/// Rom part
+struct Part {
+	/// Offset
+	offset: u32,
+
+	/// Size
+	size: u32,
+
+	/// Data
+	data: PartData,
+}
+
+/// Rom part data
+enum PartData {
+	/// File
+	Path(PathBuf),
+
+	/// Bytes
+	Bytes(Vec<u8>),
 }
@@ -0,0 +1,39 @@
+//! Header manifest
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_nds::{layout::Layout, Header},
+	std::{fs, path::Path},
+};
+
+/// Manifest, as output by `ndsz-unnds`
+#[derive(Debug)]
+#[derive(serde::Deserialize)]
+pub struct Manifest {
+	/// Header
+	pub header: Header,
+
+	/// All hidden fat files
+	pub hidden_fat_files: Vec<u16>,
+
+	/// All filesystem paths, in their original order.
+	///
+	/// Directories end with a `/`.
+	pub fnt_order: Vec<String>,
+
+	/// Layout of the original rom
+	pub layout: Layout,
+}
+
+/// Reads the `header.yaml` manifest, if it exists
+pub fn read(input_path: &Path) -> Result<Option<Manifest>, anyhow::Error> {
+	let path = input_path.join("header.yaml");
+	if !path.exists() {
+		return Ok(None);
+	}
+
+	let file = fs::File::open(path).context("Unable to open header manifest")?;
+	let manifest = serde_yaml::from_reader(file).context("Unable to parse header manifest")?;
+	Ok(Some(manifest))
+}
@@ -51,6 +51,7 @@ impl<R> Data<R> {
 
 /// Narc data header
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
 	/// Chunk size
 	pub chunk_size: u32,
@@ -75,4 +76,19 @@ impl Header {
 			chunk_size: LittleEndian::read_u32(bytes.chunk_size),
 		})
 	}
+
+	/// Encodes this header as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; Self::SIZE] {
+		let mut bytes = [0; Self::SIZE];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			chunk_name: [0x4],
+			chunk_size: [0x4],
+		);
+
+		*bytes_mut.chunk_name = *b"GMIF";
+		LittleEndian::write_u32(bytes_mut.chunk_size, self.chunk_size);
+
+		bytes
+	}
 }
@@ -110,3 +110,127 @@ pub enum NarclessFromReaderError {
 	#[error("Unable to read data")]
 	ReadData(#[source] data::NarclessFromReaderError),
 }
+
+/// Error for [`Narc::to_writer`](super::Narc::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Unable to build fnt
+	#[error("Unable to build fnt")]
+	BuildFnt(#[source] fnt::ToWriterError),
+
+	/// Narc too big
+	#[error("Narc too big")]
+	TooBig,
+
+	/// Too many files
+	#[error("Too many files")]
+	TooManyFiles,
+
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] io::Error),
+
+	/// Unable to write fat header
+	#[error("Unable to write fat header")]
+	WriteFatHeader(#[source] io::Error),
+
+	/// Unable to write fat
+	#[error("Unable to write fat")]
+	WriteFat(#[source] fat::ToWriterError),
+
+	/// Unable to write fnt header
+	#[error("Unable to write fnt header")]
+	WriteFntHeader(#[source] io::Error),
+
+	/// Unable to write fnt
+	#[error("Unable to write fnt")]
+	WriteFnt(#[source] io::Error),
+
+	/// Unable to write data header
+	#[error("Unable to write data header")]
+	WriteDataHeader(#[source] io::Error),
+
+	/// Unable to seek to data start
+	#[error("Unable to seek to data start")]
+	SeekData(#[source] io::Error),
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
+
+/// Error for [`Narc::narcless_to_writer`](super::Narc::narcless_to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum NarclessToWriterError {
+	/// Unable to build fnt
+	#[error("Unable to build fnt")]
+	BuildFnt(#[source] fnt::ToWriterError),
+
+	/// Narc too big
+	#[error("Narc too big")]
+	TooBig,
+
+	/// Unable to write fnt offset
+	#[error("Unable to write fnt offset")]
+	WriteFntOffset(#[source] io::Error),
+
+	/// Unable to write fnt len
+	#[error("Unable to write fnt len")]
+	WriteFntLen(#[source] io::Error),
+
+	/// Unable to write fat offset
+	#[error("Unable to write fat offset")]
+	WriteFatOffset(#[source] io::Error),
+
+	/// Unable to write fat len
+	#[error("Unable to write fat len")]
+	WriteFatLen(#[source] io::Error),
+
+	/// Unable to write fnt
+	#[error("Unable to write fnt")]
+	WriteFnt(#[source] io::Error),
+
+	/// Unable to write fat
+	#[error("Unable to write fat")]
+	WriteFat(#[source] fat::ToWriterError),
+
+	/// Unable to seek to data start
+	#[error("Unable to seek to data start")]
+	SeekData(#[source] io::Error),
+
+	/// Unable to write data
+	#[error("Unable to write data")]
+	WriteData(#[source] io::Error),
+}
+
+/// Error for [`Narc::open_file`](super::Narc::open_file)
+#[derive(Debug, thiserror::Error)]
+pub enum OpenFileError {
+	/// File not found
+	#[error("File {file} not found")]
+	NotFound { file: String },
+
+	/// Unable to open file
+	#[error("Unable to open file")]
+	Open(#[source] fat::OpenFileError),
+}
+
+/// Error for [`Narc::replace_file`](super::Narc::replace_file)
+#[derive(Debug, thiserror::Error)]
+pub enum ReplaceFileError {
+	/// File not found
+	#[error("File {file} not found")]
+	NotFound { file: String },
+
+	/// Narc too big
+	#[error("Narc too big")]
+	TooBig,
+
+	/// Unable to seek
+	#[error("Unable to seek")]
+	Seek(#[source] io::Error),
+
+	/// Unable to write
+	#[error("Unable to write")]
+	Write(#[source] io::Error),
+}
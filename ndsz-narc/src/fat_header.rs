@@ -5,6 +5,7 @@ use byteorder::{ByteOrder, LittleEndian};
 
 /// Narc fat header
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FatHeader {
 	/// Chunk size
 	pub chunk_size: u32,
@@ -39,4 +40,23 @@ impl FatHeader {
 			reserved:   LittleEndian::read_u16(bytes.reserved),
 		})
 	}
+
+	/// Encodes this header as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; Self::SIZE] {
+		let mut bytes = [0; Self::SIZE];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			chunk_name: [0x4],
+			chunk_size: [0x4],
+			files_len : [0x2],
+			reserved  : [0x2],
+		);
+
+		*bytes_mut.chunk_name = *b"BTAF";
+		LittleEndian::write_u32(bytes_mut.chunk_size, self.chunk_size);
+		LittleEndian::write_u16(bytes_mut.files_len, self.files_len);
+		LittleEndian::write_u16(bytes_mut.reserved, self.reserved);
+
+		bytes
+	}
 }
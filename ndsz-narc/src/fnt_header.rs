@@ -5,6 +5,7 @@ use byteorder::{ByteOrder, LittleEndian};
 
 /// Narc fnt header
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FntHeader {
 	/// Chunk size
 	pub chunk_size: u32,
@@ -29,4 +30,19 @@ impl FntHeader {
 			chunk_size: LittleEndian::read_u32(bytes.chunk_size),
 		})
 	}
+
+	/// Encodes this header as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; Self::SIZE] {
+		let mut bytes = [0; Self::SIZE];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			chunk_name: [0x4],
+			chunk_size: [0x4],
+		);
+
+		*bytes_mut.chunk_name = *b"BTNF";
+		LittleEndian::write_u32(bytes_mut.chunk_size, self.chunk_size);
+
+		bytes
+	}
 }
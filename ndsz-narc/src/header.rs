@@ -11,6 +11,7 @@ use byteorder::{ByteOrder, LittleEndian};
 
 /// Header
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
 	/// File size
 	pub file_size: u32,
@@ -53,4 +54,27 @@ impl Header {
 			file_size: LittleEndian::read_u32(bytes.file_size),
 		})
 	}
+
+	/// Encodes this header as bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x10] {
+		let mut bytes = [0; 0x10];
+		let bytes_mut = ndsz_bytes::array_split_mut!(&mut bytes,
+			chunk_name: [0x4],
+			byte_order: [0x2],
+			version   : [0x2],
+			file_size : [0x4],
+			chunk_size: [0x2],
+			chunks_len: [0x2],
+		);
+
+		*bytes_mut.chunk_name = *b"NARC";
+		LittleEndian::write_u16(bytes_mut.byte_order, 0xfffe);
+		LittleEndian::write_u16(bytes_mut.version, 0x0100);
+		LittleEndian::write_u32(bytes_mut.file_size, self.file_size);
+		LittleEndian::write_u16(bytes_mut.chunk_size, 0x0010);
+		LittleEndian::write_u16(bytes_mut.chunks_len, 3);
+
+		bytes
+	}
 }
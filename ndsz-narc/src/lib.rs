@@ -10,7 +10,14 @@ pub mod header;
 // Exports
 pub use self::{
 	data::Data,
-	error::{FromReaderError, NarclessFromReaderError},
+	error::{
+		FromReaderError,
+		NarclessFromReaderError,
+		NarclessToWriterError,
+		OpenFileError,
+		ReplaceFileError,
+		ToWriterError,
+	},
 	fat_header::FatHeader,
 	fnt_header::FntHeader,
 	header::Header,
@@ -18,10 +25,19 @@ pub use self::{
 
 // Imports
 use {
-	byteorder::{LittleEndian, ReadBytesExt},
-	ndsz_fat::{FileAllocationTable, FileNameTable},
+	byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+	ndsz_fat::{
+		fnt::{self, MainTableEntry},
+		FileAllocationTable,
+		FileNameTable,
+		FilePtr,
+		FileRef,
+	},
 	ndsz_util::{IoSlice, ReadByteArray},
-	std::io::{self, Read, Seek, SeekFrom},
+	std::{
+		convert::TryFrom,
+		io::{self, Read, Seek, SeekFrom},
+	},
 };
 
 /// Narc file
@@ -38,6 +54,11 @@ pub struct Narc<R> {
 }
 
 impl<R> Narc<R> {
+	/// Alignment of all chunks
+	pub const ALIGNMENT: u32 = 0x4;
+	/// Padding byte
+	pub const PADDING: u8 = 0xff;
+
 	/// Reads the narc from a reader
 	pub fn from_reader(mut reader: R) -> Result<Self, FromReaderError>
 	where
@@ -137,4 +158,271 @@ impl<R> Narc<R> {
 
 		Ok(Self { fat, fnt, data })
 	}
+
+	/// Opens a file, by id or path
+	pub fn open_file<'a, F: Into<FileRef<'a>>>(&mut self, file: F) -> Result<IoSlice<&mut IoSlice<R>>, OpenFileError>
+	where
+		R: io::Seek,
+	{
+		let file = file.into();
+		let id = file
+			.resolve(&self.fnt)
+			.ok_or_else(|| OpenFileError::NotFound { file: file.to_string() })?;
+
+		self.fat.open_file(&mut self.data.0, id).map_err(OpenFileError::Open)
+	}
+
+	/// Replaces the contents of a file, by id or path, without rebuilding the narc.
+	///
+	/// If the new contents fit within the file's slot (see [`FileAllocationTable::slot_end`]),
+	/// the file is overwritten in-place, else it is relocated to the end of the data chunk.
+	/// Afterwards, the fat entry of the file and, if relocated, the chunk sizes are updated.
+	///
+	/// Note: Only supports narcs read with [`Self::from_reader`], not narcless variants.
+	pub fn replace_file<'a, F: Into<FileRef<'a>>>(&mut self, file: F, data: &[u8]) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		let file = file.into();
+		let not_found = || ReplaceFileError::NotFound { file: file.to_string() };
+		let id = file.resolve(&self.fnt).ok_or_else(not_found)?;
+		let ptr = *self.fat.ptrs.get(usize::from(id)).ok_or_else(not_found)?;
+		let data_len = u32::try_from(data.len()).map_err(|_| ReplaceFileError::TooBig)?;
+
+		// Note: The data chunk is the last chunk, so it's end is the file size
+		let data_start = u32::try_from(self.data.0.start_pos()).map_err(|_| ReplaceFileError::TooBig)?;
+		let cur_data_len = u32::try_from(self.data.0.len()).map_err(|_| ReplaceFileError::TooBig)?;
+		let slot_end = self
+			.fat
+			.slot_end(id, Self::ALIGNMENT)
+			.expect("File id was just checked")
+			.min(cur_data_len);
+
+		// Write the file, either in-place or at the end of the data
+		let new_ptr = match ptr.start_address.checked_add(data_len) {
+			Some(end_address) if end_address <= slot_end => {
+				self.write_at(data_start + ptr.start_address, data)?;
+
+				// Note: If the new file is smaller, pad the rest of the old file
+				if end_address < ptr.end_address {
+					self.write_padding_at(data_start + end_address, ptr.end_address - end_address)?;
+				}
+
+				FilePtr {
+					start_address: ptr.start_address,
+					end_address,
+				}
+			},
+			_ => {
+				let start_address = cur_data_len
+					.checked_add(self::padding_len(cur_data_len))
+					.ok_or(ReplaceFileError::TooBig)?;
+				let end_address = start_address.checked_add(data_len).ok_or(ReplaceFileError::TooBig)?;
+				let new_data_len = end_address
+					.checked_add(self::padding_len(end_address))
+					.ok_or(ReplaceFileError::TooBig)?;
+				let file_size = data_start.checked_add(new_data_len).ok_or(ReplaceFileError::TooBig)?;
+
+				self.write_padding_at(data_start + cur_data_len, start_address - cur_data_len)?;
+				self.write_at(data_start + start_address, data)?;
+				self.write_padding_at(data_start + end_address, new_data_len - end_address)?;
+
+				// Then update the header and data chunk size
+				let header = Header { file_size };
+				self.write_at(0x0, &header.to_bytes())?;
+				let data_header = data::Header {
+					chunk_size: new_data_len + data::Header::SIZE as u32,
+				};
+				self.write_at(data_start - data::Header::SIZE as u32, &data_header.to_bytes())?;
+				self.data.0.set_end_pos(u64::from(file_size));
+
+				FilePtr {
+					start_address,
+					end_address,
+				}
+			},
+		};
+
+		// And finally update the fat
+		// Note: The fat starts right after the header and fat header
+		let fat_entry_offset = usize::from(id) * 0x8 + 0x10 + FatHeader::SIZE;
+		let fat_entry_offset = u32::try_from(fat_entry_offset).map_err(|_| ReplaceFileError::TooBig)?;
+		self.write_at(fat_entry_offset, &new_ptr.to_bytes())?;
+		self.fat.ptrs[usize::from(id)] = new_ptr;
+
+		Ok(())
+	}
+
+	/// Writes `data` at absolute position `offset`
+	fn write_at(&mut self, offset: u32, data: &[u8]) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		let writer = self.data.0.inner_mut();
+		writer
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReplaceFileError::Seek)?;
+		writer.write_all(data).map_err(ReplaceFileError::Write)
+	}
+
+	/// Writes `len` bytes of padding at absolute position `offset`
+	fn write_padding_at(&mut self, offset: u32, len: u32) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		let writer = self.data.0.inner_mut();
+		writer
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReplaceFileError::Seek)?;
+		self::write_padding(writer, len).map_err(ReplaceFileError::Write)
+	}
+
+	/// Writes the narc to a writer.
+	///
+	/// The fnt and data chunks are padded to [`Self::ALIGNMENT`].
+	/// If the fnt has no names, only it's root entry is written, as in retail narcs.
+	#[allow(clippy::wrong_self_convention)] // We need mutable access to read the data
+	pub fn to_writer<W: io::Write>(&mut self, writer: &mut W) -> Result<(), ToWriterError>
+	where
+		R: io::Read + io::Seek,
+	{
+		// Build the fnt and get the size of all chunks
+		let fnt_bytes = match self.fnt.root.entries.is_empty() {
+			true => self::flat_fnt_bytes(),
+			false => self::fnt_bytes(&self.fnt).map_err(ToWriterError::BuildFnt)?,
+		};
+		let fat_chunk_size =
+			u32::try_from(self.fat.ptrs.len() * 0x8 + FatHeader::SIZE).map_err(|_| ToWriterError::TooBig)?;
+		let fnt_chunk_size = u32::try_from(fnt_bytes.len() + FntHeader::SIZE).map_err(|_| ToWriterError::TooBig)?;
+		let data_len = u32::try_from(self.data.0.len()).map_err(|_| ToWriterError::TooBig)?;
+		let data_padding = self::padding_len(data_len);
+		let data_chunk_size = data_len
+			.checked_add(data_padding + data::Header::SIZE as u32)
+			.ok_or(ToWriterError::TooBig)?;
+		let file_size = [fat_chunk_size, fnt_chunk_size, data_chunk_size]
+			.iter()
+			.try_fold(0x10_u32, |size, &chunk_size| size.checked_add(chunk_size))
+			.ok_or(ToWriterError::TooBig)?;
+		let files_len = u16::try_from(self.fat.ptrs.len()).map_err(|_| ToWriterError::TooManyFiles)?;
+
+		// Write the header
+		let header = Header { file_size };
+		writer
+			.write_all(&header.to_bytes())
+			.map_err(ToWriterError::WriteHeader)?;
+
+		// Then the fat
+		let fat_header = FatHeader {
+			chunk_size: fat_chunk_size,
+			files_len,
+			reserved: 0,
+		};
+		writer
+			.write_all(&fat_header.to_bytes())
+			.map_err(ToWriterError::WriteFatHeader)?;
+		self.fat.to_writer(writer).map_err(ToWriterError::WriteFat)?;
+
+		// Then the fnt
+		let fnt_header = FntHeader {
+			chunk_size: fnt_chunk_size,
+		};
+		writer
+			.write_all(&fnt_header.to_bytes())
+			.map_err(ToWriterError::WriteFntHeader)?;
+		writer.write_all(&fnt_bytes).map_err(ToWriterError::WriteFnt)?;
+
+		// And finally the data
+		let data_header = data::Header {
+			chunk_size: data_chunk_size,
+		};
+		writer
+			.write_all(&data_header.to_bytes())
+			.map_err(ToWriterError::WriteDataHeader)?;
+		self.data.0.seek(SeekFrom::Start(0)).map_err(ToWriterError::SeekData)?;
+		io::copy(&mut self.data.0, writer).map_err(ToWriterError::WriteData)?;
+		self::write_padding(writer, data_padding).map_err(ToWriterError::WriteData)?;
+
+		Ok(())
+	}
+
+	/// Writes a narcless variant to a writer.
+	///
+	/// The fnt is padded to [`Self::ALIGNMENT`].
+	#[allow(clippy::wrong_self_convention)] // We need mutable access to read the data
+	pub fn narcless_to_writer<W: io::Write>(&mut self, writer: &mut W) -> Result<(), NarclessToWriterError>
+	where
+		R: io::Read + io::Seek,
+	{
+		// Build the fnt and get the offsets of everything
+		let fnt_bytes = self::fnt_bytes(&self.fnt).map_err(NarclessToWriterError::BuildFnt)?;
+		let fnt_offset = 0x10;
+		let fnt_len = u32::try_from(fnt_bytes.len()).map_err(|_| NarclessToWriterError::TooBig)?;
+		let fat_offset = fnt_offset + fnt_len;
+		let fat_len = u32::try_from(self.fat.ptrs.len() * 0x8).map_err(|_| NarclessToWriterError::TooBig)?;
+		fat_offset.checked_add(fat_len).ok_or(NarclessToWriterError::TooBig)?;
+
+		// Write the header
+		writer
+			.write_u32::<LittleEndian>(fnt_offset)
+			.map_err(NarclessToWriterError::WriteFntOffset)?;
+		writer
+			.write_u32::<LittleEndian>(fnt_len)
+			.map_err(NarclessToWriterError::WriteFntLen)?;
+		writer
+			.write_u32::<LittleEndian>(fat_offset)
+			.map_err(NarclessToWriterError::WriteFatOffset)?;
+		writer
+			.write_u32::<LittleEndian>(fat_len)
+			.map_err(NarclessToWriterError::WriteFatLen)?;
+
+		// Then the fnt and fat
+		writer.write_all(&fnt_bytes).map_err(NarclessToWriterError::WriteFnt)?;
+		self.fat.to_writer(writer).map_err(NarclessToWriterError::WriteFat)?;
+
+		// And finally the data
+		self.data
+			.0
+			.seek(SeekFrom::Start(0))
+			.map_err(NarclessToWriterError::SeekData)?;
+		io::copy(&mut self.data.0, writer).map_err(NarclessToWriterError::WriteData)?;
+
+		Ok(())
+	}
+}
+
+/// Builds the fnt, padded to [`Narc::ALIGNMENT`]
+fn fnt_bytes(fnt: &FileNameTable) -> Result<Vec<u8>, fnt::ToWriterError> {
+	let mut bytes = vec![];
+	fnt.to_writer(&mut bytes)?;
+
+	// Note: The fnt will never be bigger than `u32::MAX`, as it's directory and file ids are `u16`s.
+	let padding = self::padding_len(bytes.len() as u32);
+	bytes.resize(bytes.len() + padding as usize, Narc::<()>::PADDING);
+
+	Ok(bytes)
+}
+
+/// Builds the fnt of a narc without any names, as retail narcs do.
+///
+/// Only the root entry is written, with it's sub-table offset pointing to the low byte of it's
+/// first file id, `0`, which doubles as the end of the sub-table.
+fn flat_fnt_bytes() -> Vec<u8> {
+	let entry = MainTableEntry {
+		sub_table_offset: 0x4,
+		first_file_id:    0,
+		parent_id:        1,
+	};
+
+	entry.to_bytes().to_vec()
+}
+
+/// Returns the padding needed after `len` bytes to align them to [`Narc::ALIGNMENT`]
+fn padding_len(len: u32) -> u32 {
+	let alignment = Narc::<()>::ALIGNMENT;
+	(alignment - len % alignment) % alignment
+}
+
+/// Writes `len` bytes of padding
+fn write_padding<W: io::Write>(writer: &mut W, len: u32) -> Result<(), io::Error> {
+	io::copy(&mut io::repeat(Narc::<()>::PADDING).take(u64::from(len)), writer).map(|_| ())
 }
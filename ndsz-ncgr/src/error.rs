@@ -0,0 +1,84 @@
+//! Errors
+
+// Imports
+use {ndsz_generic_header::nitro_file, std::io};
+
+/// Error for [`Ncgr::from_reader`](super::Ncgr::from_reader)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to read file
+	#[error("Unable to read file")]
+	ReadFile(#[source] nitro_file::FromReaderError),
+
+	/// Wrong magic
+	#[error("Wrong magic: {magic:?}")]
+	WrongMagic { magic: [u8; 4] },
+
+	/// Missing character section
+	#[error("Missing character section")]
+	MissingCharacter,
+
+	/// Unable to open character section
+	#[error("Unable to open character section")]
+	OpenCharacter(#[source] nitro_file::OpenSubSectionError),
+
+	/// Unable to read character section
+	#[error("Unable to read character section")]
+	ReadCharacter(#[source] io::Error),
+
+	/// Character section too small
+	#[error("Character section too small: {size:#x}")]
+	CharacterTooSmall { size: usize },
+
+	/// Unknown character format
+	#[error("Unknown character format: {format:#x}")]
+	UnknownFormat { format: u32 },
+
+	/// Unknown character layout
+	#[error("Unknown character layout: {format:#x}")]
+	UnknownLayout { format: u32 },
+
+	/// Invalid character data offset
+	#[error("Invalid character data offset: {offset:#x}")]
+	InvalidDataOffset { offset: u32 },
+
+	/// Unable to open character position section
+	#[error("Unable to open character position section")]
+	OpenPosition(#[source] nitro_file::OpenSubSectionError),
+
+	/// Unable to read character position section
+	#[error("Unable to read character position section")]
+	ReadPosition(#[source] io::Error),
+
+	/// Character position section too small
+	#[error("Character position section too small: {size:#x}")]
+	PositionTooSmall { size: usize },
+}
+
+/// Error for [`Ncgr::to_writer`](super::Ncgr::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Too much data
+	#[error("Too much data")]
+	TooMuchData,
+
+	/// Unable to write file
+	#[error("Unable to write file")]
+	WriteFile(#[source] nitro_file::WriteError),
+}
+
+/// Error for [`Ncgr::set_pixels`](super::Ncgr::set_pixels)
+#[derive(Debug, thiserror::Error)]
+pub enum SetPixelsError {
+	/// Wrong number of pixels
+	#[error("Wrong number of pixels: {len}, expected {expected_len}")]
+	WrongLen { len: usize, expected_len: usize },
+
+	/// Pixel too big for the depth
+	#[error("Pixel at ({x}, {y}) too big for the depth: {pixel:#x}")]
+	PixelTooBig { x: usize, y: usize, pixel: u8 },
+
+	/// Non-zero pixel outside of the data
+	#[error("Non-zero pixel at ({x}, {y}) outside of the data")]
+	OutsideData { x: usize, y: usize },
+}
@@ -0,0 +1,127 @@
+//! Png export and import
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{ReadIndexedPngError, WritePngError};
+
+// Imports
+use {
+	crate::{Depth, Ncgr},
+	ndsz_nclr::Palette,
+	std::{convert::TryFrom, io},
+};
+
+/// Writes `ncgr` as an RGBA png, rendered with `palette`.
+///
+/// See [`Ncgr::to_rgba`] for details.
+pub fn write_rgba_png<W: io::Write>(ncgr: &Ncgr, palette: &Palette, writer: W) -> Result<(), WritePngError> {
+	let (width, height) = self::png_size(ncgr)?;
+	let mut encoder = png::Encoder::new(writer, width, height);
+	encoder.set_color(png::ColorType::Rgba);
+	encoder.set_depth(png::BitDepth::Eight);
+	let mut writer = encoder.write_header().map_err(WritePngError::WriteHeader)?;
+
+	writer
+		.write_image_data(&ncgr.to_rgba(palette).concat())
+		.map_err(WritePngError::WriteData)?;
+	writer.finish().map_err(WritePngError::Finish)
+}
+
+/// Writes `ncgr` as an indexed png, with `palette`.
+///
+/// The palette is padded with black to [`Depth::colors_len`] colors, and the first
+/// palette color is written as transparent.
+pub fn write_indexed_png<W: io::Write>(ncgr: &Ncgr, palette: &Palette, writer: W) -> Result<(), WritePngError> {
+	let (width, height) = self::png_size(ncgr)?;
+	let mut colors = palette.to_rgb();
+	colors.resize(ncgr.depth.colors_len(), [0; 3]);
+
+	let mut encoder = png::Encoder::new(writer, width, height);
+	encoder.set_color(png::ColorType::Indexed);
+	encoder.set_depth(match ncgr.depth {
+		Depth::Bits4 => png::BitDepth::Four,
+		Depth::Bits8 => png::BitDepth::Eight,
+	});
+	encoder.set_palette(colors.concat());
+	encoder.set_trns(vec![0]);
+	let mut writer = encoder.write_header().map_err(WritePngError::WriteHeader)?;
+
+	let pixels = ncgr.to_pixels();
+	let data = match ncgr.depth {
+		// Note: Png stores the first pixel of each byte in the upper nibble, and pads each row to a byte,
+		//       but our width is always a multiple of the tile size.
+		Depth::Bits4 => pixels.chunks(2).map(|pixels| pixels[0] << 4 | pixels[1]).collect(),
+		Depth::Bits8 => pixels,
+	};
+	writer.write_image_data(&data).map_err(WritePngError::WriteData)?;
+	writer.finish().map_err(WritePngError::Finish)
+}
+
+/// Reads an indexed png into the pixels of `ncgr`, keeping it's layout and depth.
+///
+/// The png must have the same size as `ncgr`, with indices within it's depth.
+///
+/// Returns the palette of the png.
+pub fn read_indexed_png<R: io::Read>(ncgr: &mut Ncgr, reader: R) -> Result<Palette, ReadIndexedPngError> {
+	let mut reader = png::Decoder::new(reader)
+		.read_info()
+		.map_err(ReadIndexedPngError::ReadInfo)?;
+	let info = reader.info();
+	if info.color_type != png::ColorType::Indexed {
+		return Err(ReadIndexedPngError::NotIndexed {
+			color_type: info.color_type,
+		});
+	}
+	let (width, height) = (info.width as usize, info.height as usize);
+	let (expected_width, expected_height) = (ncgr.width(), ncgr.height());
+	if (width, height) != (expected_width, expected_height) {
+		return Err(ReadIndexedPngError::WrongSize {
+			width,
+			height,
+			expected_width,
+			expected_height,
+		});
+	}
+	let bits = info.bit_depth as usize;
+	let colors = info
+		.palette
+		.as_deref()
+		.unwrap_or(&[])
+		.chunks_exact(3)
+		.map(|rgb| [rgb[0], rgb[1], rgb[2]])
+		.collect::<Vec<_>>();
+
+	// Read the data and unpack each row.
+	// Note: Png stores the first pixel of each byte in the upper bits.
+	let mut data = vec![0; reader.output_buffer_size()];
+	reader.next_frame(&mut data).map_err(ReadIndexedPngError::ReadData)?;
+	let row_len = (width * bits).div_ceil(8);
+	let pixels = data
+		.chunks(row_len)
+		.take(height)
+		.flat_map(|row| {
+			(0..width).map(move |x| {
+				let bit_idx = x * bits;
+				let shift = 8 - bits - bit_idx % 8;
+				(row[bit_idx / 8] >> shift) & ((1 << bits) - 1) as u8
+			})
+		})
+		.collect::<Vec<_>>();
+
+	ncgr.set_pixels(&pixels).map_err(ReadIndexedPngError::SetPixels)?;
+
+	Ok(Palette::from_rgb(&colors))
+}
+
+/// Returns the png size of `ncgr`
+fn png_size(ncgr: &Ncgr) -> Result<(u32, u32), WritePngError> {
+	match (u32::try_from(ncgr.width()), u32::try_from(ncgr.height())) {
+		(Ok(width), Ok(height)) if width != 0 && height != 0 => Ok((width, height)),
+		_ => Err(WritePngError::InvalidSize {
+			width:  ncgr.width(),
+			height: ncgr.height(),
+		}),
+	}
+}
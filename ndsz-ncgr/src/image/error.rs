@@ -0,0 +1,53 @@
+//! Errors
+
+// Imports
+use crate::SetPixelsError;
+
+/// Error for [`write_rgba_png`](super::write_rgba_png) and [`write_indexed_png`](super::write_indexed_png)
+#[derive(Debug, thiserror::Error)]
+pub enum WritePngError {
+	/// Invalid size
+	#[error("Invalid size: {width}x{height}")]
+	InvalidSize { width: usize, height: usize },
+
+	/// Unable to write png header
+	#[error("Unable to write png header")]
+	WriteHeader(#[source] png::EncodingError),
+
+	/// Unable to write png data
+	#[error("Unable to write png data")]
+	WriteData(#[source] png::EncodingError),
+
+	/// Unable to finish png
+	#[error("Unable to finish png")]
+	Finish(#[source] png::EncodingError),
+}
+
+/// Error for [`read_indexed_png`](super::read_indexed_png)
+#[derive(Debug, thiserror::Error)]
+pub enum ReadIndexedPngError {
+	/// Unable to read png info
+	#[error("Unable to read png info")]
+	ReadInfo(#[source] png::DecodingError),
+
+	/// Png wasn't indexed
+	#[error("Png wasn't indexed: {color_type:?}")]
+	NotIndexed { color_type: png::ColorType },
+
+	/// Wrong size
+	#[error("Wrong size: {width}x{height}, expected {expected_width}x{expected_height}")]
+	WrongSize {
+		width:           usize,
+		height:          usize,
+		expected_width:  usize,
+		expected_height: usize,
+	},
+
+	/// Unable to read png data
+	#[error("Unable to read png data")]
+	ReadData(#[source] png::DecodingError),
+
+	/// Unable to set pixels
+	#[error("Unable to set pixels")]
+	SetPixels(#[source] SetPixelsError),
+}
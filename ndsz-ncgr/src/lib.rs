@@ -0,0 +1,379 @@
+//! `.ncgr` implementation
+//!
+//! Character data is stored in the `RAHC` section, either as 8x8 tiles or linearly,
+//! with an optional `SOPC` section containing the position of the characters.
+//!
+//! Adapted from `https://www.romhacking.net/documents/469/`
+
+// Modules
+mod error;
+pub mod image;
+
+// Exports
+pub use {
+	self::error::{FromReaderError, SetPixelsError, ToWriterError},
+	ndsz_nclr::Depth,
+};
+
+// Imports
+use {
+	byteorder::{ByteOrder, LittleEndian},
+	ndsz_generic_header::{nitro_file, NitroFile},
+	ndsz_nclr::Palette,
+	std::{
+		convert::TryFrom,
+		io::{self, Read},
+	},
+};
+
+/// Pixel layout
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Layout {
+	/// Tiled, with 8x8 tiles stored sequentially
+	Tiled,
+
+	/// Linear, with rows of pixels stored sequentially
+	Linear,
+}
+
+impl Layout {
+	/// Returns the layout from it's format
+	#[must_use]
+	pub const fn from_format(format: u32) -> Option<Self> {
+		match format {
+			0 => Some(Self::Tiled),
+			1 => Some(Self::Linear),
+			_ => None,
+		}
+	}
+
+	/// Returns the format of this layout
+	#[must_use]
+	pub const fn format(self) -> u32 {
+		match self {
+			Self::Tiled => 0,
+			Self::Linear => 1,
+		}
+	}
+}
+
+/// Size, in tiles
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Size {
+	/// Width
+	pub width: u16,
+
+	/// Height
+	pub height: u16,
+}
+
+/// Character position
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Position {
+	/// X
+	pub x: u16,
+
+	/// Y
+	pub y: u16,
+
+	/// Width
+	pub width: u16,
+
+	/// Height
+	pub height: u16,
+}
+
+/// Ncgr file
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Ncgr {
+	/// Color depth
+	pub depth: Depth,
+
+	/// Pixel layout
+	pub layout: Layout,
+
+	/// Size, if specified
+	pub size: Option<Size>,
+
+	/// Mapping mode
+	pub mapping: u32,
+
+	/// Pixel data
+	pub data: Vec<u8>,
+
+	/// Character position, if any
+	pub position: Option<Position>,
+}
+
+impl Ncgr {
+	/// Magic of the character section
+	pub const CHARACTER_MAGIC: [u8; 4] = *b"RAHC";
+	/// Default width, in tiles, when the size isn't specified
+	pub const DEFAULT_WIDTH: usize = 0x20;
+	/// Magic
+	pub const MAGIC: [u8; 4] = *b"RGCN";
+	/// Magic of the character position section
+	pub const POSITION_MAGIC: [u8; 4] = *b"SOPC";
+	/// Tile size, in pixels
+	pub const TILE_SIZE: usize = 8;
+	/// Version
+	pub const VERSION: u16 = 0x0101;
+
+	/// Reads a ncgr from a reader
+	pub fn from_reader<R: io::Read + io::Seek>(reader: R) -> Result<Self, FromReaderError> {
+		let mut file = NitroFile::from_reader(reader).map_err(FromReaderError::ReadFile)?;
+		let magic = file.header().magic;
+		if magic != Self::MAGIC {
+			return Err(FromReaderError::WrongMagic { magic });
+		}
+
+		// Read the character section
+		let character_idx = file
+			.find_sub_section(Self::CHARACTER_MAGIC)
+			.ok_or(FromReaderError::MissingCharacter)?;
+		let mut character = vec![];
+		file.open_sub_section(character_idx)
+			.map_err(FromReaderError::OpenCharacter)?
+			.read_to_end(&mut character)
+			.map_err(FromReaderError::ReadCharacter)?;
+		let header = match character.get(..0x18) {
+			Some(header) => ndsz_bytes::array_split!(<&[u8; 0x18]>::try_from(header).expect("Slice had 24 bytes"),
+				height     : [0x2],
+				width      : [0x2],
+				format     : [0x4],
+				mapping    : [0x4],
+				layout     : [0x4],
+				data_size  : [0x4],
+				data_offset: [0x4],
+			),
+			None => return Err(FromReaderError::CharacterTooSmall { size: character.len() }),
+		};
+		let format = LittleEndian::read_u32(header.format);
+		let depth = Depth::from_format(format).ok_or(FromReaderError::UnknownFormat { format })?;
+		let layout_format = LittleEndian::read_u32(header.layout);
+		let layout =
+			Layout::from_format(layout_format).ok_or(FromReaderError::UnknownLayout { format: layout_format })?;
+		let size = match (
+			LittleEndian::read_u16(header.width),
+			LittleEndian::read_u16(header.height),
+		) {
+			(0xffff, 0xffff) => None,
+			(width, height) => Some(Size { width, height }),
+		};
+		let data_offset = LittleEndian::read_u32(header.data_offset);
+		let data = character
+			.get(data_offset as usize..)
+			.ok_or(FromReaderError::InvalidDataOffset { offset: data_offset })?;
+
+		// Note: Like palettes, some files have a data size larger than the section, so we ignore any missing data.
+		let data_size = usize::min(LittleEndian::read_u32(header.data_size) as usize, data.len());
+		let data = data[..data_size].to_vec();
+
+		// Then read the character position, if any
+		let position = match file.find_sub_section(Self::POSITION_MAGIC) {
+			Some(position_idx) => {
+				let mut position = vec![];
+				file.open_sub_section(position_idx)
+					.map_err(FromReaderError::OpenPosition)?
+					.read_to_end(&mut position)
+					.map_err(FromReaderError::ReadPosition)?;
+				let position = match position.get(..0x8) {
+					Some(position) => position,
+					None => return Err(FromReaderError::PositionTooSmall { size: position.len() }),
+				};
+				Some(Position {
+					x:      LittleEndian::read_u16(&position[0x0..0x2]),
+					y:      LittleEndian::read_u16(&position[0x2..0x4]),
+					width:  LittleEndian::read_u16(&position[0x4..0x6]),
+					height: LittleEndian::read_u16(&position[0x6..0x8]),
+				})
+			},
+			None => None,
+		};
+
+		Ok(Self {
+			depth,
+			layout,
+			size,
+			mapping: LittleEndian::read_u32(header.mapping),
+			data,
+			position,
+		})
+	}
+
+	/// Writes this ncgr to a writer
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), ToWriterError> {
+		// Build the character section
+		let data_size = u32::try_from(self.data.len()).map_err(|_| ToWriterError::TooMuchData)?;
+		let mut header = [0; 0x18];
+		let header_mut = ndsz_bytes::array_split_mut!(&mut header,
+			height     : [0x2],
+			width      : [0x2],
+			format     : [0x4],
+			mapping    : [0x4],
+			layout     : [0x4],
+			data_size  : [0x4],
+			data_offset: [0x4],
+		);
+		let size = self.size.unwrap_or(Size {
+			width:  0xffff,
+			height: 0xffff,
+		});
+		LittleEndian::write_u16(header_mut.height, size.height);
+		LittleEndian::write_u16(header_mut.width, size.width);
+		LittleEndian::write_u32(header_mut.format, self.depth.format());
+		LittleEndian::write_u32(header_mut.mapping, self.mapping);
+		LittleEndian::write_u32(header_mut.layout, self.layout.format());
+		LittleEndian::write_u32(header_mut.data_size, data_size);
+		LittleEndian::write_u32(header_mut.data_offset, 0x18);
+		let mut character = Vec::with_capacity(header.len() + self.data.len());
+		character.extend_from_slice(&header);
+		character.extend_from_slice(&self.data);
+
+		// Then the character position section, if any
+		let position = self.position.map(|position| {
+			let mut bytes = [0; 0x8];
+			LittleEndian::write_u16(&mut bytes[0x0..0x2], position.x);
+			LittleEndian::write_u16(&mut bytes[0x2..0x4], position.y);
+			LittleEndian::write_u16(&mut bytes[0x4..0x6], position.width);
+			LittleEndian::write_u16(&mut bytes[0x6..0x8], position.height);
+			bytes
+		});
+
+		let mut sub_sections = vec![(Self::CHARACTER_MAGIC, character.as_slice())];
+		if let Some(position) = &position {
+			sub_sections.push((Self::POSITION_MAGIC, position.as_slice()));
+		}
+		nitro_file::write(writer, Self::MAGIC, Self::VERSION, &sub_sections).map_err(ToWriterError::WriteFile)
+	}
+
+	/// Returns the number of pixels in the data
+	#[must_use]
+	pub fn pixels_len(&self) -> usize {
+		self.data.len() * 8 / self.depth.bits()
+	}
+
+	/// Returns the width, in pixels.
+	///
+	/// If the size isn't specified, uses [`Self::DEFAULT_WIDTH`] tiles, or less, if there
+	/// aren't as many tiles.
+	#[must_use]
+	pub fn width(&self) -> usize {
+		let width = match self.size {
+			Some(size) if size.width != 0 && size.height != 0 => usize::from(size.width),
+			_ => {
+				let tiles_len = self.pixels_len() / (Self::TILE_SIZE * Self::TILE_SIZE);
+				tiles_len.clamp(1, Self::DEFAULT_WIDTH)
+			},
+		};
+
+		width * Self::TILE_SIZE
+	}
+
+	/// Returns the height, in pixels.
+	///
+	/// If the size isn't specified, uses as many rows of tiles as necessary to fit all pixels.
+	#[must_use]
+	pub fn height(&self) -> usize {
+		let height = match self.size {
+			Some(size) if size.width != 0 && size.height != 0 => usize::from(size.height),
+			_ => {
+				let row_len = self.width() * Self::TILE_SIZE;
+				self.pixels_len().div_ceil(row_len)
+			},
+		};
+
+		height * Self::TILE_SIZE
+	}
+
+	/// Returns the index of the pixel at `(x, y)` within the data
+	fn pixel_idx(&self, x: usize, y: usize) -> usize {
+		match self.layout {
+			Layout::Tiled => {
+				let tile = (y / Self::TILE_SIZE) * (self.width() / Self::TILE_SIZE) + x / Self::TILE_SIZE;
+				tile * Self::TILE_SIZE * Self::TILE_SIZE + (y % Self::TILE_SIZE) * Self::TILE_SIZE + x % Self::TILE_SIZE
+			},
+			Layout::Linear => y * self.width() + x,
+		}
+	}
+
+	/// Returns the pixel at `(x, y)`.
+	///
+	/// Returns `0` for pixels outside of the data.
+	#[must_use]
+	pub fn pixel(&self, x: usize, y: usize) -> u8 {
+		let idx = self.pixel_idx(x, y);
+		match self.depth {
+			// Note: The first pixel of each byte is in the lower nibble
+			Depth::Bits4 => self
+				.data
+				.get(idx / 2)
+				.map_or(0, |&byte| (byte >> (4 * (idx % 2))) & 0xf),
+			Depth::Bits8 => self.data.get(idx).copied().unwrap_or(0),
+		}
+	}
+
+	/// Returns all pixels, row by row
+	#[must_use]
+	pub fn to_pixels(&self) -> Vec<u8> {
+		let width = self.width();
+		(0..self.height())
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(|(x, y)| self.pixel(x, y))
+			.collect()
+	}
+
+	/// Sets all pixels, row by row, keeping the current layout and depth.
+	///
+	/// Pixels outside of the data must be `0`.
+	pub fn set_pixels(&mut self, pixels: &[u8]) -> Result<(), SetPixelsError> {
+		let width = self.width();
+		let expected_len = width * self.height();
+		if pixels.len() != expected_len {
+			return Err(SetPixelsError::WrongLen {
+				len: pixels.len(),
+				expected_len,
+			});
+		}
+
+		let colors_len = self.depth.colors_len();
+		for (pos, &pixel) in pixels.iter().enumerate() {
+			let (x, y) = (pos % width, pos / width);
+			if usize::from(pixel) >= colors_len {
+				return Err(SetPixelsError::PixelTooBig { x, y, pixel });
+			}
+
+			let idx = self.pixel_idx(x, y);
+			let (byte, shift, mask) = match self.depth {
+				Depth::Bits4 => (self.data.get_mut(idx / 2), 4 * (idx % 2), 0xf),
+				Depth::Bits8 => (self.data.get_mut(idx), 0, 0xff),
+			};
+			match byte {
+				Some(byte) => *byte = (*byte & !(mask << shift)) | (pixel << shift),
+				None if pixel == 0 => (),
+				None => return Err(SetPixelsError::OutsideData { x, y }),
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Renders all pixels, row by row, as RGBA8888 with `palette`.
+	///
+	/// The first palette color is rendered as transparent, and any missing colors as black.
+	#[must_use]
+	pub fn to_rgba(&self, palette: &Palette) -> Vec<[u8; 4]> {
+		let colors = palette.to_rgb();
+		self.to_pixels()
+			.into_iter()
+			.map(|pixel| {
+				let [r, g, b] = colors.get(usize::from(pixel)).copied().unwrap_or([0; 3]);
+				let a = match pixel {
+					0 => 0,
+					_ => 0xff,
+				};
+				[r, g, b, a]
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,32 @@
+//! Color
+
+/// BGR555 color.
+///
+/// Each component is 5 bits, with red in the lowest bits, and the upper bit unused.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct Bgr555(pub u16);
+
+impl Bgr555 {
+	/// Creates a color from RGB888.
+	///
+	/// Note: The lower 3 bits of each component are discarded.
+	#[must_use]
+	pub const fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+		Self((r >> 3) as u16 | ((g >> 3) as u16) << 5 | ((b >> 3) as u16) << 10)
+	}
+
+	/// Returns this color as RGB888.
+	///
+	/// Each 5-bit component is scaled to 8 bits, so this round-trips with [`Self::from_rgb`],
+	/// aside from the unused upper bit.
+	#[must_use]
+	pub const fn to_rgb(self) -> [u8; 3] {
+		[self::scale(self.0), self::scale(self.0 >> 5), self::scale(self.0 >> 10)]
+	}
+}
+
+/// Scales the lowest 5-bit component of `color` to 8 bits
+const fn scale(color: u16) -> u8 {
+	let component = (color & 0x1f) as u8;
+	(component << 3) | (component >> 2)
+}
@@ -0,0 +1,72 @@
+//! Errors
+
+// Imports
+use {ndsz_generic_header::nitro_file, std::io};
+
+/// Error for [`Nclr::from_reader`](super::Nclr::from_reader)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to read file
+	#[error("Unable to read file")]
+	ReadFile(#[source] nitro_file::FromReaderError),
+
+	/// Wrong magic
+	#[error("Wrong magic: {magic:?}")]
+	WrongMagic { magic: [u8; 4] },
+
+	/// Missing palette section
+	#[error("Missing palette section")]
+	MissingPalette,
+
+	/// Unable to open palette section
+	#[error("Unable to open palette section")]
+	OpenPalette(#[source] nitro_file::OpenSubSectionError),
+
+	/// Unable to read palette section
+	#[error("Unable to read palette section")]
+	ReadPalette(#[source] io::Error),
+
+	/// Palette section too small
+	#[error("Palette section too small: {size:#x}")]
+	PaletteTooSmall { size: usize },
+
+	/// Unknown palette format
+	#[error("Unknown palette format: {format:#x}")]
+	UnknownFormat { format: u32 },
+
+	/// Invalid palette data offset
+	#[error("Invalid palette data offset: {offset:#x}")]
+	InvalidDataOffset { offset: u32 },
+
+	/// Unable to open palette compression section
+	#[error("Unable to open palette compression section")]
+	OpenCompression(#[source] nitro_file::OpenSubSectionError),
+
+	/// Unable to read palette compression section
+	#[error("Unable to read palette compression section")]
+	ReadCompression(#[source] io::Error),
+
+	/// Palette compression section too small
+	#[error("Palette compression section too small: {size:#x}")]
+	CompressionTooSmall { size: usize },
+
+	/// Invalid palette ids
+	#[error("Invalid palette ids: {palettes_len} at {offset:#x}")]
+	InvalidPaletteIds { palettes_len: u16, offset: u32 },
+}
+
+/// Error for [`Nclr::to_writer`](super::Nclr::to_writer)
+#[derive(Debug, thiserror::Error)]
+pub enum ToWriterError {
+	/// Too many colors
+	#[error("Too many colors")]
+	TooManyColors,
+
+	/// Too many palette ids
+	#[error("Too many palette ids")]
+	TooManyPaletteIds,
+
+	/// Unable to write file
+	#[error("Unable to write file")]
+	WriteFile(#[source] nitro_file::WriteError),
+}
@@ -0,0 +1,275 @@
+//! `.nclr` implementation
+//!
+//! Palettes are stored in the `TTLP` section, with an optional `PMCP` section
+//! containing the ids of each palette, when not all palettes are stored.
+//!
+//! Adapted from `https://www.romhacking.net/documents/469/`
+
+// Modules
+pub mod color;
+mod error;
+
+// Exports
+pub use self::{
+	color::Bgr555,
+	error::{FromReaderError, ToWriterError},
+};
+
+// Imports
+use {
+	byteorder::{ByteOrder, LittleEndian, WriteBytesExt},
+	ndsz_generic_header::{nitro_file, NitroFile},
+	std::{
+		convert::TryFrom,
+		io::{self, Read},
+	},
+};
+
+/// Color depth
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Depth {
+	/// 4-bit, with 16-color palettes
+	Bits4,
+
+	/// 8-bit, with 256-color palettes
+	Bits8,
+}
+
+impl Depth {
+	/// Returns the depth from it's format
+	#[must_use]
+	pub const fn from_format(format: u32) -> Option<Self> {
+		match format {
+			3 => Some(Self::Bits4),
+			4 => Some(Self::Bits8),
+			_ => None,
+		}
+	}
+
+	/// Returns the format of this depth
+	#[must_use]
+	pub const fn format(self) -> u32 {
+		match self {
+			Self::Bits4 => 3,
+			Self::Bits8 => 4,
+		}
+	}
+
+	/// Returns the number of bits per pixel
+	#[must_use]
+	pub const fn bits(self) -> usize {
+		match self {
+			Self::Bits4 => 4,
+			Self::Bits8 => 8,
+		}
+	}
+
+	/// Returns the number of colors in each palette
+	#[must_use]
+	pub const fn colors_len(self) -> usize {
+		match self {
+			Self::Bits4 => 0x10,
+			Self::Bits8 => 0x100,
+		}
+	}
+}
+
+/// Palette
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Palette(pub Vec<Bgr555>);
+
+impl Palette {
+	/// Creates a palette from RGB888 colors.
+	///
+	/// Note: The lower 3 bits of each component are discarded.
+	#[must_use]
+	pub fn from_rgb(colors: &[[u8; 3]]) -> Self {
+		Self(colors.iter().map(|&color| Bgr555::from_rgb(color)).collect())
+	}
+
+	/// Returns all colors as RGB888
+	#[must_use]
+	pub fn to_rgb(&self) -> Vec<[u8; 3]> {
+		self.0.iter().map(|color| color.to_rgb()).collect()
+	}
+}
+
+/// Nclr file
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Nclr {
+	/// Color depth
+	pub depth: Depth,
+
+	/// If the palettes are extended palettes
+	pub extended: bool,
+
+	/// All palettes.
+	///
+	/// Each palette has [`Depth::colors_len`] colors, except the last, which may have less.
+	pub palettes: Vec<Palette>,
+
+	/// Ids of each palette, if not all palettes are stored
+	pub palette_ids: Option<Vec<u16>>,
+}
+
+impl Nclr {
+	/// Magic of the palette compression section
+	pub const COMPRESSION_MAGIC: [u8; 4] = *b"PMCP";
+	/// Magic
+	pub const MAGIC: [u8; 4] = *b"RLCN";
+	/// Magic of the palette section
+	pub const PALETTE_MAGIC: [u8; 4] = *b"TTLP";
+	/// Version
+	pub const VERSION: u16 = 0x0100;
+
+	/// Reads a nclr from a reader
+	pub fn from_reader<R: io::Read + io::Seek>(reader: R) -> Result<Self, FromReaderError> {
+		let mut file = NitroFile::from_reader(reader).map_err(FromReaderError::ReadFile)?;
+		let magic = file.header().magic;
+		if magic != Self::MAGIC {
+			return Err(FromReaderError::WrongMagic { magic });
+		}
+
+		// Read the palette section
+		let palette_idx = file
+			.find_sub_section(Self::PALETTE_MAGIC)
+			.ok_or(FromReaderError::MissingPalette)?;
+		let mut palette = vec![];
+		file.open_sub_section(palette_idx)
+			.map_err(FromReaderError::OpenPalette)?
+			.read_to_end(&mut palette)
+			.map_err(FromReaderError::ReadPalette)?;
+		let header = match palette.get(..0x10) {
+			Some(header) => ndsz_bytes::array_split!(<&[u8; 0x10]>::try_from(header).expect("Slice had 16 bytes"),
+				format     : [0x4],
+				extended   : [0x4],
+				data_size  : [0x4],
+				data_offset: [0x4],
+			),
+			None => return Err(FromReaderError::PaletteTooSmall { size: palette.len() }),
+		};
+		let format = LittleEndian::read_u32(header.format);
+		let depth = Depth::from_format(format).ok_or(FromReaderError::UnknownFormat { format })?;
+		let data_offset = LittleEndian::read_u32(header.data_offset);
+		let data = palette
+			.get(data_offset as usize..)
+			.ok_or(FromReaderError::InvalidDataOffset { offset: data_offset })?;
+
+		// Note: Some files have a data size larger than the section, so we ignore any missing colors.
+		let data_size = usize::min(LittleEndian::read_u32(header.data_size) as usize, data.len());
+		let colors = data[..data_size]
+			.chunks_exact(2)
+			.map(|bytes| Bgr555(LittleEndian::read_u16(bytes)))
+			.collect::<Vec<_>>();
+		let palettes = colors
+			.chunks(depth.colors_len())
+			.map(|colors| Palette(colors.to_vec()))
+			.collect();
+
+		// Then read the palette ids, if any
+		let palette_ids = match file.find_sub_section(Self::COMPRESSION_MAGIC) {
+			Some(compression_idx) => {
+				let mut compression = vec![];
+				file.open_sub_section(compression_idx)
+					.map_err(FromReaderError::OpenCompression)?
+					.read_to_end(&mut compression)
+					.map_err(FromReaderError::ReadCompression)?;
+				Some(self::parse_palette_ids(&compression)?)
+			},
+			None => None,
+		};
+
+		Ok(Self {
+			depth,
+			extended: LittleEndian::read_u32(header.extended) != 0,
+			palettes,
+			palette_ids,
+		})
+	}
+
+	/// Writes this nclr to a writer
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), ToWriterError> {
+		// Build the palette section
+		let colors = self.palettes.iter().flat_map(|palette| &palette.0);
+		let data_size = u32::try_from(colors.clone().count() * 2).map_err(|_| ToWriterError::TooManyColors)?;
+		let mut header = [0; 0x10];
+		let header_mut = ndsz_bytes::array_split_mut!(&mut header,
+			format     : [0x4],
+			extended   : [0x4],
+			data_size  : [0x4],
+			data_offset: [0x4],
+		);
+		LittleEndian::write_u32(header_mut.format, self.depth.format());
+		LittleEndian::write_u32(header_mut.extended, u32::from(self.extended));
+		LittleEndian::write_u32(header_mut.data_size, data_size);
+		LittleEndian::write_u32(header_mut.data_offset, 0x10);
+		let mut palette = Vec::with_capacity(header.len() + data_size as usize);
+		palette.extend_from_slice(&header);
+		for color in colors {
+			palette
+				.write_u16::<LittleEndian>(color.0)
+				.expect("Writing to a `Vec` can't fail");
+		}
+
+		// Then the palette compression section, if we have any ids
+		let compression = match &self.palette_ids {
+			Some(palette_ids) => {
+				let palettes_len = u16::try_from(palette_ids.len()).map_err(|_| ToWriterError::TooManyPaletteIds)?;
+				let mut header = [0; 0x8];
+				let header_mut = ndsz_bytes::array_split_mut!(&mut header,
+					palettes_len: [0x2],
+					padding     : [0x2],
+					ids_offset  : [0x4],
+				);
+				LittleEndian::write_u16(header_mut.palettes_len, palettes_len);
+				LittleEndian::write_u16(header_mut.padding, 0xbeef);
+				LittleEndian::write_u32(header_mut.ids_offset, 0x8);
+				let mut compression = Vec::with_capacity(header.len() + palette_ids.len() * 2);
+				compression.extend_from_slice(&header);
+				for &id in palette_ids {
+					compression
+						.write_u16::<LittleEndian>(id)
+						.expect("Writing to a `Vec` can't fail");
+				}
+				Some(compression)
+			},
+			None => None,
+		};
+
+		let mut sub_sections = vec![(Self::PALETTE_MAGIC, palette.as_slice())];
+		if let Some(compression) = &compression {
+			sub_sections.push((Self::COMPRESSION_MAGIC, compression.as_slice()));
+		}
+		nitro_file::write(writer, Self::MAGIC, Self::VERSION, &sub_sections).map_err(ToWriterError::WriteFile)
+	}
+
+	/// Returns a palette by it's id
+	#[must_use]
+	pub fn palette(&self, id: u16) -> Option<&Palette> {
+		let idx = match &self.palette_ids {
+			Some(palette_ids) => palette_ids.iter().position(|&palette_id| palette_id == id)?,
+			None => usize::from(id),
+		};
+
+		self.palettes.get(idx)
+	}
+}
+
+/// Parses the palette ids of the palette compression section.
+///
+/// It contains the number of palettes and the offset of their ids, from the start of the section.
+fn parse_palette_ids(compression: &[u8]) -> Result<Vec<u16>, FromReaderError> {
+	if compression.len() < 0x8 {
+		return Err(FromReaderError::CompressionTooSmall {
+			size: compression.len(),
+		});
+	}
+	let palettes_len = LittleEndian::read_u16(&compression[0x0..0x2]);
+	let offset = LittleEndian::read_u32(&compression[0x4..0x8]);
+	let ids = compression
+		.get(offset as usize..)
+		.and_then(|ids| ids.get(..usize::from(palettes_len) * 2))
+		.ok_or(FromReaderError::InvalidPaletteIds { palettes_len, offset })?;
+
+	Ok(ids.chunks_exact(2).map(LittleEndian::read_u16).collect())
+}
@@ -0,0 +1,82 @@
+//! Arm9 binary
+//!
+//! Retail arm9 binaries may be compressed with [`blz`], in which case their
+//! [`ModuleParams`] hold the address the compressed data ends at.
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{CompressError, DecompressError};
+
+// Imports
+use {
+	crate::ModuleParams,
+	ndsz_compress::blz,
+	std::convert::{TryFrom, TryInto},
+};
+
+/// Minimum size left uncompressed.
+///
+/// Covers the secure area, which is never compressed.
+pub const MIN_UNCOMPRESSED_SIZE: usize = 0x4000;
+
+/// Decompresses the arm9 binary, loaded at `load_address`, clearing the compressed end in it's module params.
+///
+/// Returns `None` if it isn't compressed.
+pub fn decompress(arm9: &[u8], load_address: u32) -> Result<Option<Vec<u8>>, DecompressError> {
+	let (params_offset, mut params) = self::module_params(arm9).ok_or(DecompressError::MissingModuleParams)?;
+	if params.compressed_static_end == 0 {
+		return Ok(None);
+	}
+
+	let compressed_end = params.compressed_static_end.wrapping_sub(load_address);
+	if usize::try_from(compressed_end).ok() != Some(arm9.len()) {
+		return Err(DecompressError::CompressedEndMismatch {
+			compressed_end,
+			size: arm9.len(),
+		});
+	}
+
+	let mut decompressed = blz::decompress(arm9)
+		.map_err(DecompressError::Decompress)?
+		.ok_or(DecompressError::NotCompressed)?;
+	params.compressed_static_end = 0;
+	decompressed[params_offset..][..ModuleParams::SIZE].copy_from_slice(&params.to_bytes());
+
+	Ok(Some(decompressed))
+}
+
+/// Compresses the arm9 binary, loaded at `load_address`, setting the compressed end in it's module params.
+///
+/// Returns `None` if it isn't compressible.
+pub fn compress(arm9: &[u8], load_address: u32) -> Result<Option<Vec<u8>>, CompressError> {
+	let (params_offset, mut params) = self::module_params(arm9).ok_or(CompressError::MissingModuleParams)?;
+	if params.compressed_static_end != 0 {
+		return Err(CompressError::AlreadyCompressed);
+	}
+
+	// Note: The module params are read before decompressing, so they must be left uncompressed.
+	let min_uncompressed_size = MIN_UNCOMPRESSED_SIZE.max(params_offset + ModuleParams::SIZE);
+	let mut compressed = match blz::compress(arm9, min_uncompressed_size).map_err(CompressError::Compress)? {
+		Some(compressed) => compressed,
+		None => return Ok(None),
+	};
+	params.compressed_static_end = u32::try_from(compressed.len())
+		.ok()
+		.and_then(|size| load_address.checked_add(size))
+		.ok_or(CompressError::TooBig)?;
+	compressed[params_offset..][..ModuleParams::SIZE].copy_from_slice(&params.to_bytes());
+
+	Ok(Some(compressed))
+}
+
+/// Finds and parses the module params in the arm9 binary, returning their offset
+fn module_params(arm9: &[u8]) -> Option<(usize, ModuleParams)> {
+	let offset = ModuleParams::find(arm9)?;
+	let bytes = arm9[offset..][..ModuleParams::SIZE]
+		.try_into()
+		.expect("Slice had the module params size");
+
+	Some((offset, ModuleParams::from_bytes(bytes)))
+}
@@ -0,0 +1,44 @@
+//! Errors
+
+// Imports
+use ndsz_compress::blz;
+
+/// Error for [`decompress`](super::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Missing module params
+	#[error("Missing module params")]
+	MissingModuleParams,
+
+	/// Compressed end doesn't match the binary size
+	#[error("Compressed end {compressed_end:#x} doesn't match the binary size {size:#x}")]
+	CompressedEndMismatch { compressed_end: u32, size: usize },
+
+	/// Binary wasn't compressed, despite the module params
+	#[error("Binary wasn't compressed, despite the module params")]
+	NotCompressed,
+
+	/// Unable to decompress
+	#[error("Unable to decompress")]
+	Decompress(#[source] blz::DecompressError),
+}
+
+/// Error for [`compress`](super::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Missing module params
+	#[error("Missing module params")]
+	MissingModuleParams,
+
+	/// Binary already compressed
+	#[error("Binary already compressed")]
+	AlreadyCompressed,
+
+	/// Unable to compress
+	#[error("Unable to compress")]
+	Compress(#[source] blz::CompressError),
+
+	/// Compressed binary too big
+	#[error("Compressed binary too big")]
+	TooBig,
+}
@@ -0,0 +1,518 @@
+//! Icon / Banner
+//!
+//! The banner holds the game titles in every language, alongside
+//! the icon shown in the menu, and, on DSi, an animated icon.
+
+// Modules
+mod error;
+
+// Exports
+pub use error::{FromBytesError, ToBytesError};
+
+// Imports
+use {
+	crate::crc16::crc16,
+	byteorder::{ByteOrder, LittleEndian},
+	std::convert::{TryFrom, TryInto},
+};
+
+/// Banner
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Banner {
+	/// Titles
+	pub titles: Titles,
+
+	/// Icon
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub icon: Icon,
+
+	/// DSi animated icon
+	pub animation: Option<Animation>,
+}
+
+impl Banner {
+	/// Parses a banner from bytes.
+	///
+	/// `bytes` must be at least [`Version::size`] bytes long, any bytes after
+	/// that are ignored.
+	///
+	/// Note: The crcs and reserved areas are not kept.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, FromBytesError> {
+		let version = bytes
+			.get(..0x2)
+			.map(LittleEndian::read_u16)
+			.ok_or(FromBytesError::TooShort { len: bytes.len() })?;
+		let version = Version::from_u16(version).ok_or(FromBytesError::UnknownVersion(version))?;
+		let bytes = bytes
+			.get(..version.size())
+			.ok_or(FromBytesError::TooShort { len: bytes.len() })?;
+
+		let title = |offset: usize, language: &'static str| {
+			self::title_from_bytes(&bytes[offset..(offset + 0x100)])
+				.map_err(|source| FromBytesError::Title { language, source })
+		};
+		let titles = Titles {
+			japanese: title(0x240, "japanese")?,
+			english:  title(0x340, "english")?,
+			french:   title(0x440, "french")?,
+			german:   title(0x540, "german")?,
+			italian:  title(0x640, "italian")?,
+			spanish:  title(0x740, "spanish")?,
+			chinese:  match version >= Version::Chinese {
+				true => Some(title(0x840, "chinese")?),
+				false => None,
+			},
+			korean:   match version >= Version::Korean {
+				true => Some(title(0x940, "korean")?),
+				false => None,
+			},
+		};
+
+		let icon = Icon::from_bytes(bytes[0x20..0x240].try_into().expect("Slice had wrong size"));
+
+		let animation = match version {
+			Version::Dsi => {
+				let mut bitmaps = <[Bitmap; 8]>::default();
+				for (bitmap, bytes) in bitmaps.iter_mut().zip(bytes[0x1240..0x2240].chunks_exact(0x200)) {
+					*bitmap = Bitmap::from_bytes(bytes.try_into().expect("Chunk had wrong size"));
+				}
+				let mut palettes = <[Palette; 8]>::default();
+				for (palette, bytes) in palettes.iter_mut().zip(bytes[0x2240..0x2340].chunks_exact(0x20)) {
+					*palette = Palette::from_bytes(bytes.try_into().expect("Chunk had wrong size"));
+				}
+				let sequence = bytes[0x2340..0x23c0]
+					.chunks_exact(0x2)
+					.map(LittleEndian::read_u16)
+					.take_while(|&frame| frame != 0)
+					.map(AnimationFrame::from_u16)
+					.collect();
+
+				Some(Animation {
+					bitmaps,
+					palettes,
+					sequence,
+				})
+			},
+			_ => None,
+		};
+
+		Ok(Self {
+			titles,
+			icon,
+			animation,
+		})
+	}
+
+	/// Returns the version of this banner.
+	///
+	/// This is the lowest version able to hold all titles and the animation.
+	#[must_use]
+	pub const fn version(&self) -> Version {
+		match (&self.animation, &self.titles.korean, &self.titles.chinese) {
+			(Some(_), ..) => Version::Dsi,
+			(None, Some(_), _) => Version::Korean,
+			(None, None, Some(_)) => Version::Chinese,
+			(None, None, None) => Version::Original,
+		}
+	}
+
+	/// Writes this banner to bytes, computing all crcs
+	pub fn to_bytes(&self) -> Result<Vec<u8>, ToBytesError> {
+		let version = self.version();
+		let mut bytes = vec![0; version.size()];
+		LittleEndian::write_u16(&mut bytes[0x0..0x2], version as u16);
+
+		bytes[0x20..0x240].copy_from_slice(&self.icon.to_bytes());
+
+		let mut write_title = |offset: usize, title: Option<&str>, language: &'static str| {
+			self::title_to_bytes(title.unwrap_or(""), &mut bytes[offset..(offset + 0x100)])
+				.map_err(|len| ToBytesError::TitleTooLong { language, len })
+		};
+		write_title(0x240, Some(&self.titles.japanese), "japanese")?;
+		write_title(0x340, Some(&self.titles.english), "english")?;
+		write_title(0x440, Some(&self.titles.french), "french")?;
+		write_title(0x540, Some(&self.titles.german), "german")?;
+		write_title(0x640, Some(&self.titles.italian), "italian")?;
+		write_title(0x740, Some(&self.titles.spanish), "spanish")?;
+		if version >= Version::Chinese {
+			write_title(0x840, self.titles.chinese.as_deref(), "chinese")?;
+		}
+		if version >= Version::Korean {
+			write_title(0x940, self.titles.korean.as_deref(), "korean")?;
+		}
+
+		if let Some(animation) = &self.animation {
+			for (bitmap, bytes) in animation
+				.bitmaps
+				.iter()
+				.zip(bytes[0x1240..0x2240].chunks_exact_mut(0x200))
+			{
+				bytes.copy_from_slice(&bitmap.0);
+			}
+			for (palette, bytes) in animation
+				.palettes
+				.iter()
+				.zip(bytes[0x2240..0x2340].chunks_exact_mut(0x20))
+			{
+				bytes.copy_from_slice(&palette.to_bytes());
+			}
+			if animation.sequence.len() > 0x40 {
+				return Err(ToBytesError::SequenceTooLong {
+					len: animation.sequence.len(),
+				});
+			}
+			for (frame, bytes) in animation
+				.sequence
+				.iter()
+				.zip(bytes[0x2340..0x23c0].chunks_exact_mut(0x2))
+			{
+				LittleEndian::write_u16(bytes, frame.to_u16());
+			}
+		}
+
+		// Finally compute all crcs
+		// Note: Each version also includes the crcs of the previous versions
+		let crcs = [
+			(Version::Original, 0x2, 0x20..0x840),
+			(Version::Chinese, 0x4, 0x20..0x940),
+			(Version::Korean, 0x6, 0x20..0xa40),
+			(Version::Dsi, 0x8, 0x1240..0x23c0),
+		];
+		for (crc_version, offset, range) in crcs {
+			if version >= crc_version {
+				let crc = crc16(&bytes[range]);
+				LittleEndian::write_u16(&mut bytes[offset..(offset + 2)], crc);
+			}
+		}
+
+		Ok(bytes)
+	}
+}
+
+/// Banner version
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Version {
+	/// Original
+	Original = 0x0001,
+
+	/// With chinese title
+	Chinese  = 0x0002,
+
+	/// With chinese and korean titles
+	Korean   = 0x0003,
+
+	/// With chinese and korean titles and DSi animated icon
+	Dsi      = 0x0103,
+}
+
+impl Version {
+	/// Parses a version
+	#[must_use]
+	pub const fn from_u16(version: u16) -> Option<Self> {
+		match version {
+			0x0001 => Some(Self::Original),
+			0x0002 => Some(Self::Chinese),
+			0x0003 => Some(Self::Korean),
+			0x0103 => Some(Self::Dsi),
+			_ => None,
+		}
+	}
+
+	/// Returns the size of a banner with this version
+	#[must_use]
+	pub const fn size(self) -> usize {
+		match self {
+			Self::Original => 0x840,
+			Self::Chinese => 0x940,
+			Self::Korean => 0xa40,
+			Self::Dsi => 0x23c0,
+		}
+	}
+}
+
+/// Titles
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Titles {
+	/// Japanese
+	pub japanese: String,
+
+	/// English
+	pub english: String,
+
+	/// French
+	pub french: String,
+
+	/// German
+	pub german: String,
+
+	/// Italian
+	pub italian: String,
+
+	/// Spanish
+	pub spanish: String,
+
+	/// Chinese
+	pub chinese: Option<String>,
+
+	/// Korean
+	pub korean: Option<String>,
+}
+
+/// Icon
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Icon {
+	/// Bitmap
+	pub bitmap: Bitmap,
+
+	/// Palette
+	pub palette: Palette,
+}
+
+impl Icon {
+	/// Parses an icon from bytes
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8; 0x220]) -> Self {
+		let bytes = ndsz_bytes::array_split!(bytes,
+			bitmap  : [0x200], // 0x0
+			palette : [0x20],  // 0x200
+		);
+
+		Self {
+			bitmap:  Bitmap::from_bytes(bytes.bitmap),
+			palette: Palette::from_bytes(bytes.palette),
+		}
+	}
+
+	/// Writes this icon to bytes
+	#[must_use]
+	pub fn to_bytes(&self) -> [u8; 0x220] {
+		let mut bytes = [0; 0x220];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				bitmap  : [0x200], // 0x0
+				palette : [0x20],  // 0x200
+			);
+
+			*bytes.bitmap = self.bitmap.0;
+			*bytes.palette = self.palette.to_bytes();
+		}
+
+		bytes
+	}
+}
+
+/// Icon bitmap.
+///
+/// 32x32 pixels, with 4 bits per pixel, stored as 4x4 tiles of 8x8 pixels.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Bitmap(pub [u8; 0x200]);
+
+impl Bitmap {
+	/// Height
+	pub const HEIGHT: usize = 32;
+	/// Width
+	pub const WIDTH: usize = 32;
+
+	/// Parses a bitmap from bytes
+	#[must_use]
+	pub const fn from_bytes(bytes: &[u8; 0x200]) -> Self {
+		Self(*bytes)
+	}
+
+	/// Creates a bitmap from linear pixels, in rows.
+	///
+	/// Only the lower 4 bits of each pixel are used.
+	#[must_use]
+	pub fn from_pixels(pixels: &[u8; 0x400]) -> Self {
+		let mut bytes = [0; 0x200];
+		for (idx, &pixel) in pixels.iter().enumerate() {
+			let (byte_idx, shift) = Self::pixel_pos(idx % Self::WIDTH, idx / Self::WIDTH);
+			bytes[byte_idx] |= (pixel & 0xf) << shift;
+		}
+
+		Self(bytes)
+	}
+
+	/// Returns all pixels of this bitmap linearly, in rows
+	#[must_use]
+	pub fn to_pixels(&self) -> [u8; 0x400] {
+		let mut pixels = [0; 0x400];
+		for (idx, pixel) in pixels.iter_mut().enumerate() {
+			*pixel = self.pixel(idx % Self::WIDTH, idx / Self::WIDTH);
+		}
+
+		pixels
+	}
+
+	/// Returns the palette index of the pixel at `(x, y)`
+	#[must_use]
+	pub fn pixel(&self, x: usize, y: usize) -> u8 {
+		let (byte_idx, shift) = Self::pixel_pos(x, y);
+		(self.0[byte_idx] >> shift) & 0xf
+	}
+
+	/// Returns the byte index and shift of the pixel at `(x, y)`
+	const fn pixel_pos(x: usize, y: usize) -> (usize, u32) {
+		let tile_idx = (y / 8) * (Self::WIDTH / 8) + (x / 8);
+		let pixel_idx = tile_idx * 64 + (y % 8) * 8 + (x % 8);
+
+		// Note: The first pixel of each byte is stored in the lower nibble
+		let shift = match pixel_idx % 2 {
+			0 => 0,
+			_ => 4,
+		};
+
+		(pixel_idx / 2, shift)
+	}
+}
+
+impl Default for Bitmap {
+	fn default() -> Self {
+		Self([0; 0x200])
+	}
+}
+
+/// Icon palette.
+///
+/// 16 BGR555 colors, where the first is transparent.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct Palette(pub [u16; 16]);
+
+impl Palette {
+	/// Parses a palette from bytes
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8; 0x20]) -> Self {
+		let mut colors = [0; 16];
+		LittleEndian::read_u16_into(bytes, &mut colors);
+		Self(colors)
+	}
+
+	/// Writes this palette to bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x20] {
+		let mut bytes = [0; 0x20];
+		LittleEndian::write_u16_into(&self.0, &mut bytes);
+		bytes
+	}
+
+	/// Creates a palette from RGB888 colors.
+	///
+	/// Note: The lower 3 bits of each component are discarded.
+	#[must_use]
+	pub fn from_rgb(colors: &[[u8; 3]; 16]) -> Self {
+		let mut palette = [0; 16];
+		for (color, &[r, g, b]) in palette.iter_mut().zip(colors) {
+			*color = u16::from(r >> 3) | (u16::from(g >> 3) << 5) | (u16::from(b >> 3) << 10);
+		}
+
+		Self(palette)
+	}
+
+	/// Returns all colors as RGB888.
+	///
+	/// Each 5-bit component is scaled to 8 bits, so this round-trips with [`Self::from_rgb`],
+	/// aside from the unused upper bit of each color.
+	#[must_use]
+	pub fn to_rgb(self) -> [[u8; 3]; 16] {
+		let mut colors = [[0; 3]; 16];
+		for (rgb, &color) in colors.iter_mut().zip(&self.0) {
+			let scale = |component: u16| {
+				let component = u8::try_from(component & 0x1f).expect("Component didn't fit into a `u8`");
+				(component << 3) | (component >> 2)
+			};
+			*rgb = [scale(color), scale(color >> 5), scale(color >> 10)];
+		}
+
+		colors
+	}
+}
+
+/// DSi animated icon
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Animation {
+	/// Bitmaps
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub bitmaps: [Bitmap; 8],
+
+	/// Palettes
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub palettes: [Palette; 8],
+
+	/// Sequence
+	// Note: Up to `0x40` frames
+	pub sequence: Vec<AnimationFrame>,
+}
+
+/// DSi animated icon frame
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimationFrame {
+	/// Duration, in 60 Hz frames
+	pub duration: u8,
+
+	/// Bitmap index
+	pub bitmap: u8,
+
+	/// Palette index
+	pub palette: u8,
+
+	/// If flipped horizontally
+	pub flip_horizontal: bool,
+
+	/// If flipped vertically
+	pub flip_vertical: bool,
+}
+
+impl AnimationFrame {
+	/// Parses a frame from it's raw value
+	#[must_use]
+	pub const fn from_u16(value: u16) -> Self {
+		Self {
+			duration:        (value & 0xff) as u8,
+			bitmap:          ((value >> 8) & 0x7) as u8,
+			palette:         ((value >> 11) & 0x7) as u8,
+			flip_horizontal: value & (1 << 14) != 0,
+			flip_vertical:   value & (1 << 15) != 0,
+		}
+	}
+
+	/// Returns the raw value of this frame.
+	///
+	/// Note: Only the lower 3 bits of the bitmap and palette indices are used.
+	#[must_use]
+	pub const fn to_u16(self) -> u16 {
+		(self.duration as u16) |
+			((self.bitmap as u16 & 0x7) << 8) |
+			((self.palette as u16 & 0x7) << 11) |
+			((self.flip_horizontal as u16) << 14) |
+			((self.flip_vertical as u16) << 15)
+	}
+}
+
+/// Parses a title from it's null-terminated UTF-16 bytes
+fn title_from_bytes(bytes: &[u8]) -> Result<String, std::string::FromUtf16Error> {
+	let chars = bytes
+		.chunks_exact(0x2)
+		.map(LittleEndian::read_u16)
+		.take_while(|&ch| ch != 0)
+		.collect::<Vec<_>>();
+
+	String::from_utf16(&chars)
+}
+
+/// Writes a title as UTF-16 into `bytes`, returning it's length in UTF-16 units if too long
+fn title_to_bytes(title: &str, bytes: &mut [u8]) -> Result<(), usize> {
+	let chars = title.encode_utf16().collect::<Vec<_>>();
+	if chars.len() * 2 > bytes.len() {
+		return Err(chars.len());
+	}
+
+	for (ch, bytes) in chars.into_iter().zip(bytes.chunks_exact_mut(0x2)) {
+		LittleEndian::write_u16(bytes, ch);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,36 @@
+//! Errors
+
+// Imports
+use std::string::FromUtf16Error;
+
+/// Error for [`Banner::from_bytes`](super::Banner::from_bytes)
+#[derive(Debug, thiserror::Error)]
+pub enum FromBytesError {
+	/// Too short
+	#[error("Banner too short ({len:#x} bytes)")]
+	TooShort { len: usize },
+
+	/// Unknown version
+	#[error("Unknown banner version {0:#x}")]
+	UnknownVersion(u16),
+
+	/// Unable to read title
+	#[error("Unable to read {language} title")]
+	Title {
+		language: &'static str,
+		#[source]
+		source:   FromUtf16Error,
+	},
+}
+
+/// Error for [`Banner::to_bytes`](super::Banner::to_bytes)
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+pub enum ToBytesError {
+	/// Title too long
+	#[error("The {language} title is too long ({len} UTF-16 units, max is 128)")]
+	TitleTooLong { language: &'static str, len: usize },
+
+	/// Animation sequence too long
+	#[error("Animation sequence is too long ({len} frames, max is 64)")]
+	SequenceTooLong { len: usize },
+}
@@ -0,0 +1,14 @@
+//! Crc16
+//!
+//! Crc used throughout the header, with polynomial `0xa001` (reflected) and initial value `0xffff`.
+
+/// Calculates the crc16 of `bytes`
+#[must_use]
+pub fn crc16(bytes: &[u8]) -> u16 {
+	bytes.iter().fold(0xffff, |crc, &byte| {
+		(0..8).fold(crc ^ u16::from(byte), |crc, _| match crc & 0x1 {
+			0 => crc >> 1,
+			_ => (crc >> 1) ^ 0xa001,
+		})
+	})
+}
@@ -0,0 +1,271 @@
+//! DSi header
+
+// Imports
+use {
+	crate::header::TableLoadData,
+	byteorder::{ByteOrder, LittleEndian},
+};
+
+/// DSi header.
+///
+/// Extended header present on [`UnitCode::NdsDsi`](crate::UnitCode::NdsDsi) and [`UnitCode::Dsi`](crate::UnitCode::Dsi)
+/// roms, right after the [`Header`](crate::Header), from `0x180` to `0x1000`.
+// From `http://dsibrew.org/wiki/DSi_cartridge_header`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DsiHeader {
+	/// Global MBK1 to MBK5 settings
+	pub global_mbk_settings: [u32; 5],
+
+	/// Local MBK6 to MBK8 settings for arm9
+	pub arm9_local_mbk_settings: [u32; 3],
+
+	/// Local MBK6 to MBK8 settings for arm7
+	pub arm7_local_mbk_settings: [u32; 3],
+
+	/// Global MBK9 settings
+	pub global_mbk9_settings: [u8; 3],
+
+	/// Global WRAMCNT settings
+	pub global_wramcnt_settings: u8,
+
+	/// Region flags
+	pub region_flags: u32,
+
+	/// Access control
+	pub access_control: u32,
+
+	/// Arm7 SCFG EXT mask
+	pub arm7_scfg_ext_mask: u32,
+
+	/// Reserved 1
+	pub reserved1: [u8; 3],
+
+	/// Flags
+	pub flags: u8,
+
+	/// Arm9i load data
+	pub arm9i_load_data: DsiArmLoadData,
+
+	/// Reserved 2
+	pub reserved2: u32,
+
+	/// Arm7i load data
+	pub arm7i_load_data: DsiArmLoadData,
+
+	/// SD/MMC device list arm7 address
+	pub sd_mmc_device_list_address: u32,
+
+	/// Digest NTR region
+	pub digest_ntr_region: TableLoadData,
+
+	/// Digest TWL region
+	pub digest_twl_region: TableLoadData,
+
+	/// Digest sector hashtable
+	pub digest_sector_hashtable: TableLoadData,
+
+	/// Digest block hashtable
+	pub digest_block_hashtable: TableLoadData,
+
+	/// Digest sector size
+	pub digest_sector_size: u32,
+
+	/// Digest block sector count
+	pub digest_block_sector_count: u32,
+
+	/// Icon banner size
+	pub icon_banner_size: u32,
+
+	/// Unknown 1
+	pub unknown1: u32,
+
+	/// TWL region rom size
+	pub twl_region_rom_size: u32,
+
+	/// Unknown 2
+	pub unknown2: u32,
+
+	/// Modcrypt area 1
+	pub modcrypt_area1: TableLoadData,
+
+	/// Modcrypt area 2
+	pub modcrypt_area2: TableLoadData,
+
+	/// Title id
+	pub title_id: u64,
+
+	/// Public save size
+	pub public_save_size: u32,
+
+	/// Private save size
+	pub private_save_size: u32,
+
+	/// Reserved 3
+	pub reserved3: [u8; 0xb8],
+
+	/// Parental control age ratings
+	pub age_ratings: [u8; 0x10],
+
+	/// Arm9 HMAC (with encrypted secure area)
+	pub arm9_hmac: [u8; 0x14],
+
+	/// Arm7 HMAC
+	pub arm7_hmac: [u8; 0x14],
+
+	/// Digest master HMAC
+	pub digest_master_hmac: [u8; 0x14],
+
+	/// Icon banner HMAC
+	pub icon_banner_hmac: [u8; 0x14],
+
+	/// Arm9i HMAC (decrypted)
+	pub arm9i_hmac: [u8; 0x14],
+
+	/// Arm7i HMAC (decrypted)
+	pub arm7i_hmac: [u8; 0x14],
+
+	/// Reserved 4
+	pub reserved4: [u8; 0x28],
+
+	/// Arm9 HMAC (without secure area)
+	pub arm9_no_secure_area_hmac: [u8; 0x14],
+
+	/// Reserved 5
+	pub reserved5: [u8; 0xa4c],
+
+	/// Reserved for debug arguments
+	pub reserved_debug_args: [u8; 0x180],
+
+	/// RSA signature
+	pub rsa_signature: [u8; 0x80],
+}
+
+impl DsiHeader {
+	/// Parses a dsi header from bytes.
+	///
+	/// Note: `bytes` should start at `0x180`, after the [`Header`](crate::Header).
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8; 0xe80]) -> Self {
+		let bytes = ndsz_bytes::array_split!(bytes,
+			global_mbk_settings        : [0x14],  // 0x180
+			arm9_local_mbk_settings    : [0xc],   // 0x194
+			arm7_local_mbk_settings    : [0xc],   // 0x1a0
+			global_mbk9_settings       : [0x3],   // 0x1ac
+			global_wramcnt_settings    :  0x1 ,   // 0x1af
+			region_flags               : [0x4],   // 0x1b0
+			access_control             : [0x4],   // 0x1b4
+			arm7_scfg_ext_mask         : [0x4],   // 0x1b8
+			reserved1                  : [0x3],   // 0x1bc
+			flags                      :  0x1 ,   // 0x1bf
+			arm9i_offset               : [0x4],   // 0x1c0
+			reserved2                  : [0x4],   // 0x1c4
+			arm9i_load_address         : [0x4],   // 0x1c8
+			arm9i_size                 : [0x4],   // 0x1cc
+			arm7i_offset               : [0x4],   // 0x1d0
+			sd_mmc_device_list_address : [0x4],   // 0x1d4
+			arm7i_load_address         : [0x4],   // 0x1d8
+			arm7i_size                 : [0x4],   // 0x1dc
+			digest_ntr_region          : [0x8],   // 0x1e0
+			digest_twl_region          : [0x8],   // 0x1e8
+			digest_sector_hashtable    : [0x8],   // 0x1f0
+			digest_block_hashtable     : [0x8],   // 0x1f8
+			digest_sector_size         : [0x4],   // 0x200
+			digest_block_sector_count  : [0x4],   // 0x204
+			icon_banner_size           : [0x4],   // 0x208
+			unknown1                   : [0x4],   // 0x20c
+			twl_region_rom_size        : [0x4],   // 0x210
+			unknown2                   : [0x4],   // 0x214
+			modcrypt_area1             : [0x8],   // 0x218
+			modcrypt_area2             : [0x8],   // 0x220
+			title_id                   : [0x8],   // 0x228
+			public_save_size           : [0x4],   // 0x230
+			private_save_size          : [0x4],   // 0x234
+			reserved3                  : [0xb8],  // 0x238
+			age_ratings                : [0x10],  // 0x2f0
+			arm9_hmac                  : [0x14],  // 0x300
+			arm7_hmac                  : [0x14],  // 0x314
+			digest_master_hmac         : [0x14],  // 0x328
+			icon_banner_hmac           : [0x14],  // 0x33c
+			arm9i_hmac                 : [0x14],  // 0x350
+			arm7i_hmac                 : [0x14],  // 0x364
+			reserved4                  : [0x28],  // 0x378
+			arm9_no_secure_area_hmac   : [0x14],  // 0x3a0
+			reserved5                  : [0xa4c], // 0x3b4
+			reserved_debug_args        : [0x180], // 0xe00
+			rsa_signature              : [0x80],  // 0xf80
+		);
+
+		let mut global_mbk_settings = [0; 5];
+		LittleEndian::read_u32_into(bytes.global_mbk_settings, &mut global_mbk_settings);
+		let mut arm9_local_mbk_settings = [0; 3];
+		LittleEndian::read_u32_into(bytes.arm9_local_mbk_settings, &mut arm9_local_mbk_settings);
+		let mut arm7_local_mbk_settings = [0; 3];
+		LittleEndian::read_u32_into(bytes.arm7_local_mbk_settings, &mut arm7_local_mbk_settings);
+
+		Self {
+			global_mbk_settings,
+			arm9_local_mbk_settings,
+			arm7_local_mbk_settings,
+			global_mbk9_settings: *bytes.global_mbk9_settings,
+			global_wramcnt_settings: *bytes.global_wramcnt_settings,
+			region_flags: LittleEndian::read_u32(bytes.region_flags),
+			access_control: LittleEndian::read_u32(bytes.access_control),
+			arm7_scfg_ext_mask: LittleEndian::read_u32(bytes.arm7_scfg_ext_mask),
+			reserved1: *bytes.reserved1,
+			flags: *bytes.flags,
+			arm9i_load_data: DsiArmLoadData {
+				offset:       LittleEndian::read_u32(bytes.arm9i_offset),
+				load_address: LittleEndian::read_u32(bytes.arm9i_load_address),
+				size:         LittleEndian::read_u32(bytes.arm9i_size),
+			},
+			reserved2: LittleEndian::read_u32(bytes.reserved2),
+			arm7i_load_data: DsiArmLoadData {
+				offset:       LittleEndian::read_u32(bytes.arm7i_offset),
+				load_address: LittleEndian::read_u32(bytes.arm7i_load_address),
+				size:         LittleEndian::read_u32(bytes.arm7i_size),
+			},
+			sd_mmc_device_list_address: LittleEndian::read_u32(bytes.sd_mmc_device_list_address),
+			digest_ntr_region: TableLoadData::from_bytes(bytes.digest_ntr_region),
+			digest_twl_region: TableLoadData::from_bytes(bytes.digest_twl_region),
+			digest_sector_hashtable: TableLoadData::from_bytes(bytes.digest_sector_hashtable),
+			digest_block_hashtable: TableLoadData::from_bytes(bytes.digest_block_hashtable),
+			digest_sector_size: LittleEndian::read_u32(bytes.digest_sector_size),
+			digest_block_sector_count: LittleEndian::read_u32(bytes.digest_block_sector_count),
+			icon_banner_size: LittleEndian::read_u32(bytes.icon_banner_size),
+			unknown1: LittleEndian::read_u32(bytes.unknown1),
+			twl_region_rom_size: LittleEndian::read_u32(bytes.twl_region_rom_size),
+			unknown2: LittleEndian::read_u32(bytes.unknown2),
+			modcrypt_area1: TableLoadData::from_bytes(bytes.modcrypt_area1),
+			modcrypt_area2: TableLoadData::from_bytes(bytes.modcrypt_area2),
+			title_id: LittleEndian::read_u64(bytes.title_id),
+			public_save_size: LittleEndian::read_u32(bytes.public_save_size),
+			private_save_size: LittleEndian::read_u32(bytes.private_save_size),
+			reserved3: *bytes.reserved3,
+			age_ratings: *bytes.age_ratings,
+			arm9_hmac: *bytes.arm9_hmac,
+			arm7_hmac: *bytes.arm7_hmac,
+			digest_master_hmac: *bytes.digest_master_hmac,
+			icon_banner_hmac: *bytes.icon_banner_hmac,
+			arm9i_hmac: *bytes.arm9i_hmac,
+			arm7i_hmac: *bytes.arm7i_hmac,
+			reserved4: *bytes.reserved4,
+			arm9_no_secure_area_hmac: *bytes.arm9_no_secure_area_hmac,
+			reserved5: *bytes.reserved5,
+			reserved_debug_args: *bytes.reserved_debug_args,
+			rsa_signature: *bytes.rsa_signature,
+		}
+	}
+}
+
+/// DSi arm load data
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DsiArmLoadData {
+	/// Offset
+	pub offset: u32,
+
+	/// Load address
+	pub load_address: u32,
+
+	/// Size
+	pub size: u32,
+}
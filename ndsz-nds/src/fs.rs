@@ -0,0 +1,31 @@
+//! Filesystem
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::OpenFileError;
+
+// Imports
+use {
+	ndsz_fat::{FileAllocationTable, FileNameTable, FileRef},
+	ndsz_util::IoSlice,
+	std::io,
+};
+
+/// Opens a file of a rom, by id or path.
+///
+/// Note: `rom` must be the whole rom, as the file pointers in the fat are absolute.
+pub fn open_file<'a, R: io::Seek, F: Into<FileRef<'a>>>(
+	rom: R,
+	fat: &FileAllocationTable,
+	fnt: &FileNameTable,
+	file: F,
+) -> Result<IoSlice<R>, OpenFileError> {
+	let file = file.into();
+	let id = file
+		.resolve(fnt)
+		.ok_or_else(|| OpenFileError::NotFound { file: file.to_string() })?;
+
+	fat.open_file(rom, id).map_err(OpenFileError::Open)
+}
@@ -0,0 +1,16 @@
+//! Errors
+
+// Imports
+use ndsz_fat::fat;
+
+/// Error for [`open_file`](super::open_file)
+#[derive(Debug, thiserror::Error)]
+pub enum OpenFileError {
+	/// File not found
+	#[error("File {file} not found")]
+	NotFound { file: String },
+
+	/// Unable to open file
+	#[error("Unable to open file")]
+	Open(#[source] fat::OpenFileError),
+}
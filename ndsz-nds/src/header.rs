@@ -4,18 +4,20 @@
 mod error;
 
 // Exports
-pub use error::FromBytesError;
+pub use error::{FromBytesError, ValidateError, ValidatePart, ValidateWarning};
 
 // Imports
 use {
-	crate::UnitCode,
+	crate::{crc16, UnitCode},
 	byteorder::{ByteOrder, LittleEndian},
+	ndsz_bytes::{Validate, ValidateVisitor},
 	ndsz_util::{ascii_str_arr::AsciiChar, AsciiStrArr},
 };
 
 /// Header
 // From `http://dsibrew.org/wiki/DSi_cartridge_header`.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
 	/// Game title
 	pub game_title: AsciiStrArr<0xc>,
@@ -36,6 +38,7 @@ pub struct Header {
 	pub device_capacity: u8,
 
 	/// Reserved 1
+	#[cfg_attr(feature = "serde", serde(with = "ndsz_util::serde_hex"))]
 	pub reserved1: [u8; 7],
 
 	/// Game revision
@@ -96,9 +99,11 @@ pub struct Header {
 	pub header_size: u32,
 
 	/// Reserved 2
+	#[cfg_attr(feature = "serde", serde(with = "ndsz_util::serde_hex"))]
 	pub reserved2: [u8; 0x38],
 
 	/// Nintendo logo
+	#[cfg_attr(feature = "serde", serde(with = "ndsz_util::serde_hex"))]
 	pub nintendo_logo: [u8; 0x9c],
 
 	/// Nintendo logo crc
@@ -108,10 +113,24 @@ pub struct Header {
 	pub header_crc: u16,
 
 	/// Reserved for debugger
+	#[cfg_attr(feature = "serde", serde(with = "ndsz_util::serde_hex"))]
 	pub reserved_debugger: [u8; 0x20],
 }
 
 impl Header {
+	/// Nintendo logo
+	pub const NINTENDO_LOGO: [u8; 0x9c] = [
+		0x24, 0xff, 0xae, 0x51, 0x69, 0x9a, 0xa2, 0x21, 0x3d, 0x84, 0x82, 0x0a, 0x84, 0xe4, 0x09, 0xad, 0x11, 0x24,
+		0x8b, 0x98, 0xc0, 0x81, 0x7f, 0x21, 0xa3, 0x52, 0xbe, 0x19, 0x93, 0x09, 0xce, 0x20, 0x10, 0x46, 0x4a, 0x4a,
+		0xf8, 0x27, 0x31, 0xec, 0x58, 0xc7, 0xe8, 0x33, 0x82, 0xe3, 0xce, 0xbf, 0x85, 0xf4, 0xdf, 0x94, 0xce, 0x4b,
+		0x09, 0xc1, 0x94, 0x56, 0x8a, 0xc0, 0x13, 0x72, 0xa7, 0xfc, 0x9f, 0x84, 0x4d, 0x73, 0xa3, 0xca, 0x9a, 0x61,
+		0x58, 0x97, 0xa3, 0x27, 0xfc, 0x03, 0x98, 0x76, 0x23, 0x1d, 0xc7, 0x61, 0x03, 0x04, 0xae, 0x56, 0xbf, 0x38,
+		0x84, 0x00, 0x40, 0xa7, 0x0e, 0xfd, 0xff, 0x52, 0xfe, 0x03, 0x6f, 0x95, 0x30, 0xf1, 0x97, 0xfb, 0xc0, 0x85,
+		0x60, 0xd6, 0x80, 0x25, 0xa9, 0x63, 0xbe, 0x03, 0x01, 0x4e, 0x38, 0xe2, 0xf9, 0xa2, 0x34, 0xff, 0xbb, 0x3e,
+		0x03, 0x44, 0x78, 0x00, 0x90, 0xcb, 0x88, 0x11, 0x3a, 0x94, 0x65, 0xc0, 0x7c, 0x63, 0x87, 0xf0, 0x3c, 0xaf,
+		0xd6, 0x25, 0xe4, 0x8b, 0x38, 0x0a, 0xac, 0x72, 0x21, 0xd4, 0xf8, 0x07,
+	];
+
 	/// Parses a header data from bytes
 	pub fn from_bytes(bytes: &[u8; 0x180]) -> Result<Self, FromBytesError> {
 		let bytes = ndsz_bytes::array_split!(bytes,
@@ -138,7 +157,7 @@ impl Header {
 			secure_transfer_timeout               : [0x2],  // 0x6e
 			arm9_auto_load                        : [0x4],  // 0x70
 			arm7_auto_load                        : [0x4],  // 0x74
-			secure_disable                        : [0x8],  // 0x7c
+			secure_disable                        : [0x8],  // 0x78
 			ntr_region_rom_size                   : [0x4],  // 0x80
 			header_size                           : [0x4],  // 0x84
 			reserved2                             : [0x38], // 0x88
@@ -166,7 +185,7 @@ impl Header {
 			file_name_table: TableLoadData::from_bytes(bytes.file_name_table),
 			file_allocation_table: TableLoadData::from_bytes(bytes.file_allocation_table),
 			arm9_overlay_table: TableLoadData::from_bytes(bytes.arm9_overlay_table),
-			arm7_overlay_table: TableLoadData::from_bytes(bytes.arm9_overlay_table),
+			arm7_overlay_table: TableLoadData::from_bytes(bytes.arm7_overlay_table),
 			normal_card_control_register_settings: LittleEndian::read_u32(bytes.normal_card_control_register_settings),
 			secure_card_control_register_settings: LittleEndian::read_u32(bytes.secure_card_control_register_settings),
 			icon_banner_offset: LittleEndian::read_u32(bytes.icon_banner_offset),
@@ -184,10 +203,231 @@ impl Header {
 			reserved_debugger: *bytes.reserved_debugger,
 		})
 	}
+
+	/// Writes this header to bytes.
+	///
+	/// Note: The crcs are written as-is, see [`Self::update_crcs`] to update them first.
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x180] {
+		let mut bytes = [0; 0x180];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				game_title                            : [0xc],  // 0x0
+				game_code                             : [0x4],  // 0xc
+				maker_code                            : [0x2],  // 0x10
+				unit_code                             :  0x1 ,  // 0x12
+				encryption_seed_select                :  0x1 ,  // 0x13
+				device_capacity                       :  0x1 ,  // 0x14
+				reserved1                             : [0x7],  // 0x15
+				game_revision                         : [0x2],  // 0x1c
+				rom_version                           :  0x1 ,  // 0x1e
+				internal_flags                        :  0x1 ,  // 0x1f
+				arm9_load_data                        : [0x10], // 0x20
+				arm7_load_data                        : [0x10], // 0x30
+				file_name_table                       : [0x8],  // 0x40
+				file_allocation_table                 : [0x8],  // 0x48
+				arm9_overlay_table                    : [0x8],  // 0x50
+				arm7_overlay_table                    : [0x8],  // 0x58
+				normal_card_control_register_settings : [0x4],  // 0x60
+				secure_card_control_register_settings : [0x4],  // 0x64
+				icon_banner_offset                    : [0x4],  // 0x68
+				secure_area_crc                       : [0x2],  // 0x6c
+				secure_transfer_timeout               : [0x2],  // 0x6e
+				arm9_auto_load                        : [0x4],  // 0x70
+				arm7_auto_load                        : [0x4],  // 0x74
+				secure_disable                        : [0x8],  // 0x78
+				ntr_region_rom_size                   : [0x4],  // 0x80
+				header_size                           : [0x4],  // 0x84
+				reserved2                             : [0x38], // 0x88
+				nintendo_logo                         : [0x9c], // 0xc0
+				nintendo_logo_crc                     : [0x2],  // 0x15c
+				header_crc                            : [0x2],  // 0x15e
+				reserved_debugger                     : [0x20], // 0x160
+			);
+
+			// Note: Strings are padded with nulls
+			bytes.game_title[..self.game_title.len()].copy_from_slice(self.game_title.as_bytes());
+			bytes.game_code[..self.game_code.len()].copy_from_slice(self.game_code.as_bytes());
+			bytes.maker_code[..self.maker_code.len()].copy_from_slice(self.maker_code.as_bytes());
+			*bytes.unit_code = self.unit_code.to_bytes();
+			*bytes.encryption_seed_select = self.encryption_seed_select;
+			*bytes.device_capacity = self.device_capacity;
+			*bytes.reserved1 = self.reserved1;
+			LittleEndian::write_u16(bytes.game_revision, self.game_revision);
+			*bytes.rom_version = self.rom_version;
+			*bytes.internal_flags = self.internal_flags;
+			*bytes.arm9_load_data = self.arm9_load_data.to_bytes();
+			*bytes.arm7_load_data = self.arm7_load_data.to_bytes();
+			*bytes.file_name_table = self.file_name_table.to_bytes();
+			*bytes.file_allocation_table = self.file_allocation_table.to_bytes();
+			*bytes.arm9_overlay_table = self.arm9_overlay_table.to_bytes();
+			*bytes.arm7_overlay_table = self.arm7_overlay_table.to_bytes();
+			LittleEndian::write_u32(
+				bytes.normal_card_control_register_settings,
+				self.normal_card_control_register_settings,
+			);
+			LittleEndian::write_u32(
+				bytes.secure_card_control_register_settings,
+				self.secure_card_control_register_settings,
+			);
+			LittleEndian::write_u32(bytes.icon_banner_offset, self.icon_banner_offset);
+			LittleEndian::write_u16(bytes.secure_area_crc, self.secure_area_crc);
+			LittleEndian::write_u16(bytes.secure_transfer_timeout, self.secure_transfer_timeout);
+			LittleEndian::write_u32(bytes.arm9_auto_load, self.arm9_auto_load);
+			LittleEndian::write_u32(bytes.arm7_auto_load, self.arm7_auto_load);
+			LittleEndian::write_u64(bytes.secure_disable, self.secure_disable);
+			LittleEndian::write_u32(bytes.ntr_region_rom_size, self.ntr_region_rom_size);
+			LittleEndian::write_u32(bytes.header_size, self.header_size);
+			*bytes.reserved2 = self.reserved2;
+			*bytes.nintendo_logo = self.nintendo_logo;
+			LittleEndian::write_u16(bytes.nintendo_logo_crc, self.nintendo_logo_crc);
+			LittleEndian::write_u16(bytes.header_crc, self.header_crc);
+			*bytes.reserved_debugger = self.reserved_debugger;
+		}
+
+		bytes
+	}
+
+	/// Computes the crc of the nintendo logo
+	#[must_use]
+	pub fn compute_nintendo_logo_crc(&self) -> u16 {
+		crc16::crc16(&self.nintendo_logo)
+	}
+
+	/// Computes the crc of the header.
+	///
+	/// Note: The header crc covers everything before it, including the
+	///       nintendo logo crc, so that one should be updated first.
+	#[must_use]
+	pub fn compute_header_crc(&self) -> u16 {
+		crc16::crc16(&self.to_bytes()[..0x15e])
+	}
+
+	/// Returns the size of the device capacity, in bytes.
+	///
+	/// Returns `None` if the size doesn't fit in a `u64`.
+	#[must_use]
+	pub const fn device_capacity_size(&self) -> Option<u64> {
+		let shift = self.device_capacity as u32;
+		match 0x20000_u64.checked_shl(shift) {
+			Some(size) if size >> shift == 0x20000 => Some(size),
+			_ => None,
+		}
+	}
+
+	/// Updates the nintendo logo crc and header crc
+	pub fn update_crcs(&mut self) {
+		self.nintendo_logo_crc = self.compute_nintendo_logo_crc();
+		self.header_crc = self.compute_header_crc();
+	}
+
+	/// Verifies this header against the rom it was read from.
+	///
+	/// Performs all checks from [`Validate`], and if `secure_area` (`0x4000..0x8000`
+	/// on the rom) is given, checks the secure area crc.
+	pub fn verify<'a, V: ValidateVisitor<'a, Self>>(&'a self, secure_area: Option<&[u8; 0x4000]>, mut visitor: V) {
+		self.validate_with(&mut visitor);
+
+		// Note: Decrypted dumps won't match the crc, as it's calculated over
+		//       the encrypted secure area, so this is only a warning.
+		if let Some(secure_area) = secure_area {
+			let computed = crc16::crc16(secure_area);
+			if computed != self.secure_area_crc {
+				visitor.visit_warning(ValidateWarning::SecureAreaCrc {
+					stored: self.secure_area_crc,
+					computed,
+				});
+			}
+		}
+	}
+
+	/// Validates this header with a visitor
+	fn validate_with<'a, V: ValidateVisitor<'a, Self>>(&'a self, visitor: &mut V) {
+		// Check the crcs
+		let computed = self.compute_nintendo_logo_crc();
+		if computed != self.nintendo_logo_crc {
+			visitor.visit_error(ValidateError::NintendoLogoCrc {
+				stored: self.nintendo_logo_crc,
+				computed,
+			});
+		}
+		let computed = self.compute_header_crc();
+		if computed != self.header_crc {
+			visitor.visit_error(ValidateError::HeaderCrc {
+				stored: self.header_crc,
+				computed,
+			});
+		}
+
+		// Check the logo itself
+		if self.nintendo_logo != Self::NINTENDO_LOGO {
+			visitor.visit_error(ValidateError::NintendoLogo);
+		}
+
+		// And that all parts are within the rom
+		let parts = [
+			(
+				ValidatePart::Arm9LoadData,
+				self.arm9_load_data.offset,
+				self.arm9_load_data.size,
+			),
+			(
+				ValidatePart::Arm7LoadData,
+				self.arm7_load_data.offset,
+				self.arm7_load_data.size,
+			),
+			(
+				ValidatePart::FileNameTable,
+				self.file_name_table.offset,
+				self.file_name_table.length,
+			),
+			(
+				ValidatePart::FileAllocationTable,
+				self.file_allocation_table.offset,
+				self.file_allocation_table.length,
+			),
+			(
+				ValidatePart::Arm9OverlayTable,
+				self.arm9_overlay_table.offset,
+				self.arm9_overlay_table.length,
+			),
+			(
+				ValidatePart::Arm7OverlayTable,
+				self.arm7_overlay_table.offset,
+				self.arm7_overlay_table.length,
+			),
+		];
+		for (part, offset, size) in parts {
+			// Note: Empty parts may have any offset
+			if size == 0 {
+				continue;
+			}
+
+			let end = u64::from(offset) + u64::from(size);
+			if end > u64::from(self.ntr_region_rom_size) {
+				visitor.visit_error(ValidateError::OutOfBounds {
+					part,
+					offset,
+					size,
+					rom_size: self.ntr_region_rom_size,
+				});
+			}
+		}
+	}
+}
+
+impl<'a> Validate<'a> for Header {
+	type Error = ValidateError;
+	type Warning = ValidateWarning;
+
+	fn validate<V: ValidateVisitor<'a, Self>>(&'a self, mut visitor: V) {
+		self.validate_with(&mut visitor);
+	}
 }
 
 /// Table load data
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableLoadData {
 	/// Offset
 	pub offset: u32,
@@ -209,10 +449,28 @@ impl TableLoadData {
 			length: LittleEndian::read_u32(bytes.length),
 		}
 	}
+
+	/// Writes this table load data to bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 8] {
+		let mut bytes = [0; 8];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				offset: [0x4],
+				length: [0x4],
+			);
+
+			LittleEndian::write_u32(bytes.offset, self.offset);
+			LittleEndian::write_u32(bytes.length, self.length);
+		}
+
+		bytes
+	}
 }
 
 /// Arm load data
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArmLoadData {
 	/// Offset
 	pub offset: u32,
@@ -244,4 +502,25 @@ impl ArmLoadData {
 			size:          LittleEndian::read_u32(bytes.size),
 		}
 	}
+
+	/// Writes this load data to bytes
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 16] {
+		let mut bytes = [0; 16];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				offset       : [0x4],
+				entry_address: [0x4],
+				load_address : [0x4],
+				size         : [0x4],
+			);
+
+			LittleEndian::write_u32(bytes.offset, self.offset);
+			LittleEndian::write_u32(bytes.entry_address, self.entry_address);
+			LittleEndian::write_u32(bytes.load_address, self.load_address);
+			LittleEndian::write_u32(bytes.size, self.size);
+		}
+
+		bytes
+	}
 }
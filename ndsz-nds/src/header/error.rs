@@ -1,7 +1,7 @@
 //! Errors
 
 // Imports
-use ndsz_util::ascii_str_arr;
+use {ndsz_util::ascii_str_arr, std::fmt};
 
 /// Error for [`Header::from_bytes`](super::Header::from_bytes)
 #[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
@@ -22,3 +22,73 @@ pub enum FromBytesError {
 	#[error("Unable to read unit code")]
 	UnitCode,
 }
+
+/// Error for [`Header::validate`](ndsz_bytes::Validate::validate)
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+pub enum ValidateError {
+	/// Nintendo logo crc mismatch
+	#[error("Nintendo logo crc mismatch: stored {stored:#06x}, computed {computed:#06x}")]
+	NintendoLogoCrc { stored: u16, computed: u16 },
+
+	/// Header crc mismatch
+	#[error("Header crc mismatch: stored {stored:#06x}, computed {computed:#06x}")]
+	HeaderCrc { stored: u16, computed: u16 },
+
+	/// Nintendo logo differs from the known logo
+	#[error("Nintendo logo differs from the known logo")]
+	NintendoLogo,
+
+	/// Part is outside of the rom
+	#[error("{part} ({offset:#x}..{:#x}) is outside of the rom size {rom_size:#x}", u64::from(*offset) + u64::from(*size))]
+	OutOfBounds {
+		part:     ValidatePart,
+		offset:   u32,
+		size:     u32,
+		rom_size: u32,
+	},
+}
+
+/// Warning for [`Header::validate`](ndsz_bytes::Validate::validate)
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+pub enum ValidateWarning {
+	/// Secure area crc mismatch
+	#[error("Secure area crc mismatch: stored {stored:#06x}, computed {computed:#06x}")]
+	SecureAreaCrc { stored: u16, computed: u16 },
+}
+
+/// Header part for [`ValidateError`]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ValidatePart {
+	/// Arm9 load data
+	Arm9LoadData,
+
+	/// Arm7 load data
+	Arm7LoadData,
+
+	/// File name table
+	FileNameTable,
+
+	/// File allocation table
+	FileAllocationTable,
+
+	/// Arm9 overlay table
+	Arm9OverlayTable,
+
+	/// Arm7 overlay table
+	Arm7OverlayTable,
+}
+
+impl fmt::Display for ValidatePart {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = match self {
+			Self::Arm9LoadData => "Arm9 load data",
+			Self::Arm7LoadData => "Arm7 load data",
+			Self::FileNameTable => "File name table",
+			Self::FileAllocationTable => "File allocation table",
+			Self::Arm9OverlayTable => "Arm9 overlay table",
+			Self::Arm7OverlayTable => "Arm7 overlay table",
+		};
+
+		f.write_str(name)
+	}
+}
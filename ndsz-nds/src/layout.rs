@@ -0,0 +1,116 @@
+//! Rom layout
+//!
+//! Describes where each part of a rom was placed, so that
+//! it may be rebuilt in the same order and at the same offsets.
+
+// Imports
+use std::{cmp::Reverse, collections::HashMap};
+
+/// Rom layout
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+	/// Alignment of all parts
+	pub alignment: u32,
+
+	/// All parts, sorted by offset
+	pub parts: Vec<Part>,
+}
+
+impl Layout {
+	/// Maximum alignment
+	pub const MAX_ALIGNMENT: u32 = 0x200;
+
+	/// Creates a layout from all parts of a rom.
+	///
+	/// Parts are sorted by offset, with empty parts first, and the
+	/// alignment is the largest power of two, up to [`Self::MAX_ALIGNMENT`],
+	/// that all non-empty parts are aligned to.
+	#[must_use]
+	pub fn new(mut parts: Vec<Part>) -> Self {
+		parts.sort_by_key(|part| (part.offset, part.size != 0));
+
+		let alignment = parts
+			.iter()
+			.filter(|part| part.size != 0)
+			.map(|part| match part.offset {
+				0 => Self::MAX_ALIGNMENT,
+				offset => 1 << offset.trailing_zeros().min(Self::MAX_ALIGNMENT.trailing_zeros()),
+			})
+			.min()
+			.unwrap_or(Self::MAX_ALIGNMENT);
+
+		Self { alignment, parts }
+	}
+
+	/// Sorts `items` by the order their parts appear in this layout.
+	///
+	/// Items whose parts aren't in this layout are kept after all others, in their current order.
+	pub fn sort_by_order<T>(&self, items: &mut [T], mut kind: impl FnMut(&T) -> &PartKind) {
+		let positions = self
+			.parts
+			.iter()
+			.enumerate()
+			.map(|(pos, part)| (&part.kind, pos))
+			.collect::<HashMap<_, _>>();
+
+		items.sort_by_cached_key(|item| {
+			let pos = positions.get(kind(item)).copied();
+			(Reverse(pos.is_some()), pos)
+		});
+	}
+}
+
+/// Rom part
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part {
+	/// Kind
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub kind: PartKind,
+
+	/// Offset
+	pub offset: u32,
+
+	/// Size
+	pub size: u32,
+}
+
+/// Rom part kind
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum PartKind {
+	/// Arm9 binary
+	Arm9,
+
+	/// Arm9 overlay table
+	Arm9OverlayTable,
+
+	/// Arm7 binary
+	Arm7,
+
+	/// Arm7 overlay table
+	Arm7OverlayTable,
+
+	/// File name table
+	FileNameTable,
+
+	/// File allocation table
+	FileAllocationTable,
+
+	/// Icon / Banner
+	Banner,
+
+	/// File not present in the filesystem, by id
+	File {
+		/// File id
+		id: u16,
+	},
+
+	/// File in the filesystem, by path
+	FsFile {
+		/// Path, separated by `/`
+		path: String,
+	},
+}
@@ -1,8 +1,25 @@
 //! Common library for `.nds` interaction
 
 // Modules
+pub mod arm9;
+pub mod banner;
+pub mod crc16;
+pub mod dsi_header;
+pub mod fs;
 pub mod header;
+pub mod layout;
+pub mod module_params;
+pub mod overlay;
+pub mod rom;
 pub mod unit_code;
 
 // Exports
-pub use self::{header::Header, unit_code::UnitCode};
+pub use self::{
+	banner::Banner,
+	dsi_header::DsiHeader,
+	header::Header,
+	module_params::ModuleParams,
+	overlay::{Overlay, OverlayTable},
+	rom::Rom,
+	unit_code::UnitCode,
+};
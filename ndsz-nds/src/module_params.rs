@@ -0,0 +1,104 @@
+//! Arm9 module params
+//!
+//! Located within the arm9 binary, right before the `0xdec00621` and `0x2106c0de` magics.
+
+// Imports
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Module params
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ModuleParams {
+	/// Autoload list start address
+	pub autoload_list_start: u32,
+
+	/// Autoload list end address
+	pub autoload_list_end: u32,
+
+	/// Autoload start address
+	pub autoload_start: u32,
+
+	/// Static bss start address
+	pub static_bss_start: u32,
+
+	/// Static bss end address
+	pub static_bss_end: u32,
+
+	/// Compressed static end address.
+	///
+	/// `0` if the arm9 binary isn't compressed.
+	pub compressed_static_end: u32,
+
+	/// Sdk version
+	pub sdk_version: u32,
+}
+
+impl ModuleParams {
+	/// Magic, after the params
+	pub const MAGIC: [u8; 8] = [0x21, 0x06, 0xc0, 0xde, 0xde, 0xc0, 0x06, 0x21];
+	/// Size, including the magic
+	pub const SIZE: usize = 0x24;
+
+	/// Finds the offset of the module params within the arm9 binary
+	#[must_use]
+	pub fn find(arm9: &[u8]) -> Option<usize> {
+		arm9.windows(Self::MAGIC.len())
+			.position(|bytes| bytes == Self::MAGIC)
+			.and_then(|magic_offset| magic_offset.checked_sub(Self::SIZE - Self::MAGIC.len()))
+	}
+
+	/// Parses module params from bytes.
+	///
+	/// Note: The magic isn't checked.
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8; 0x24]) -> Self {
+		let bytes = ndsz_bytes::array_split!(bytes,
+			autoload_list_start  : [0x4], // 0x0
+			autoload_list_end    : [0x4], // 0x4
+			autoload_start       : [0x4], // 0x8
+			static_bss_start     : [0x4], // 0xc
+			static_bss_end       : [0x4], // 0x10
+			compressed_static_end: [0x4], // 0x14
+			sdk_version          : [0x4], // 0x18
+			magic                : [0x8], // 0x1c
+		);
+
+		Self {
+			autoload_list_start:   LittleEndian::read_u32(bytes.autoload_list_start),
+			autoload_list_end:     LittleEndian::read_u32(bytes.autoload_list_end),
+			autoload_start:        LittleEndian::read_u32(bytes.autoload_start),
+			static_bss_start:      LittleEndian::read_u32(bytes.static_bss_start),
+			static_bss_end:        LittleEndian::read_u32(bytes.static_bss_end),
+			compressed_static_end: LittleEndian::read_u32(bytes.compressed_static_end),
+			sdk_version:           LittleEndian::read_u32(bytes.sdk_version),
+		}
+	}
+
+	/// Writes these module params to bytes, including the magic
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x24] {
+		let mut bytes = [0; 0x24];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				autoload_list_start  : [0x4], // 0x0
+				autoload_list_end    : [0x4], // 0x4
+				autoload_start       : [0x4], // 0x8
+				static_bss_start     : [0x4], // 0xc
+				static_bss_end       : [0x4], // 0x10
+				compressed_static_end: [0x4], // 0x14
+				sdk_version          : [0x4], // 0x18
+				magic                : [0x8], // 0x1c
+			);
+
+			LittleEndian::write_u32(bytes.autoload_list_start, self.autoload_list_start);
+			LittleEndian::write_u32(bytes.autoload_list_end, self.autoload_list_end);
+			LittleEndian::write_u32(bytes.autoload_start, self.autoload_start);
+			LittleEndian::write_u32(bytes.static_bss_start, self.static_bss_start);
+			LittleEndian::write_u32(bytes.static_bss_end, self.static_bss_end);
+			LittleEndian::write_u32(bytes.compressed_static_end, self.compressed_static_end);
+			LittleEndian::write_u32(bytes.sdk_version, self.sdk_version);
+			*bytes.magic = Self::MAGIC;
+		}
+
+		bytes
+	}
+}
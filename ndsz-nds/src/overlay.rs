@@ -0,0 +1,200 @@
+//! Overlay table
+
+// Modules
+mod error;
+
+// Exports
+pub use error::{CompressError, DecompressError, FromReaderError};
+
+// Imports
+use {
+	byteorder::{ByteOrder, LittleEndian},
+	itertools::Itertools,
+	ndsz_compress::blz,
+	std::{convert::TryFrom, io, iter},
+};
+
+/// Overlay table
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct OverlayTable {
+	/// All overlays
+	pub overlays: Vec<Overlay>,
+}
+
+impl OverlayTable {
+	/// Reads an overlay table from a reader
+	pub fn from_reader<R: io::Read>(reader: &mut R) -> Result<Self, FromReaderError> {
+		let overlays = iter::from_fn(move || {
+			let mut bytes = [0; 0x20];
+			match reader.read_exact(&mut bytes) {
+				Ok(()) => Some(Ok(Overlay::from_bytes(&bytes))),
+				Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+				Err(err) => Some(Err(err)),
+			}
+		})
+		.try_collect::<_, Vec<_>, _>()
+		.map_err(FromReaderError::ReadOverlay)?;
+
+		Ok(Self { overlays })
+	}
+
+	/// Writes this overlay table to a writer
+	pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), io::Error> {
+		for overlay in &self.overlays {
+			writer.write_all(&overlay.to_bytes())?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Overlay
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Overlay {
+	/// Overlay id
+	pub id: u32,
+
+	/// Ram address
+	pub ram_address: u32,
+
+	/// Ram size
+	pub ram_size: u32,
+
+	/// Bss size
+	pub bss_size: u32,
+
+	/// Static initializers start address
+	pub static_init_start: u32,
+
+	/// Static initializers end address
+	pub static_init_end: u32,
+
+	/// File id
+	pub file_id: u32,
+
+	/// Compressed size
+	// Note: Only 24 bits, the upper 8 are used by `flags`.
+	pub compressed_size: u32,
+
+	/// Flags
+	pub flags: u8,
+}
+
+impl Overlay {
+	/// Authenticated flag
+	pub const FLAG_AUTHENTICATED: u8 = 1 << 1;
+	/// Compressed flag
+	pub const FLAG_COMPRESSED: u8 = 1 << 0;
+
+	/// Parses an overlay from bytes
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8; 0x20]) -> Self {
+		let bytes = ndsz_bytes::array_split!(bytes,
+			id                : [0x4], // 0x0
+			ram_address       : [0x4], // 0x4
+			ram_size          : [0x4], // 0x8
+			bss_size          : [0x4], // 0xc
+			static_init_start : [0x4], // 0x10
+			static_init_end   : [0x4], // 0x14
+			file_id           : [0x4], // 0x18
+			compressed_size   : [0x3], // 0x1c
+			flags             :  0x1 , // 0x1f
+		);
+
+		Self {
+			id:                LittleEndian::read_u32(bytes.id),
+			ram_address:       LittleEndian::read_u32(bytes.ram_address),
+			ram_size:          LittleEndian::read_u32(bytes.ram_size),
+			bss_size:          LittleEndian::read_u32(bytes.bss_size),
+			static_init_start: LittleEndian::read_u32(bytes.static_init_start),
+			static_init_end:   LittleEndian::read_u32(bytes.static_init_end),
+			file_id:           LittleEndian::read_u32(bytes.file_id),
+			compressed_size:   LittleEndian::read_u24(bytes.compressed_size),
+			flags:             *bytes.flags,
+		}
+	}
+
+	/// Writes this overlay to bytes.
+	///
+	/// Note: Only the lower 24 bits of `compressed_size` are written.
+	#[must_use]
+	pub fn to_bytes(self) -> [u8; 0x20] {
+		let mut bytes = [0; 0x20];
+		{
+			let bytes = ndsz_bytes::array_split_mut!(&mut bytes,
+				id                : [0x4], // 0x0
+				ram_address       : [0x4], // 0x4
+				ram_size          : [0x4], // 0x8
+				bss_size          : [0x4], // 0xc
+				static_init_start : [0x4], // 0x10
+				static_init_end   : [0x4], // 0x14
+				file_id           : [0x4], // 0x18
+				compressed_size   : [0x3], // 0x1c
+				flags             :  0x1 , // 0x1f
+			);
+
+			LittleEndian::write_u32(bytes.id, self.id);
+			LittleEndian::write_u32(bytes.ram_address, self.ram_address);
+			LittleEndian::write_u32(bytes.ram_size, self.ram_size);
+			LittleEndian::write_u32(bytes.bss_size, self.bss_size);
+			LittleEndian::write_u32(bytes.static_init_start, self.static_init_start);
+			LittleEndian::write_u32(bytes.static_init_end, self.static_init_end);
+			LittleEndian::write_u32(bytes.file_id, self.file_id);
+			LittleEndian::write_u24(bytes.compressed_size, self.compressed_size & 0xff_ffff);
+			*bytes.flags = self.flags;
+		}
+
+		bytes
+	}
+
+	/// Returns if this overlay is compressed
+	#[must_use]
+	pub const fn is_compressed(&self) -> bool {
+		self.flags & Self::FLAG_COMPRESSED != 0
+	}
+
+	/// Returns if this overlay is authenticated
+	#[must_use]
+	pub const fn is_authenticated(&self) -> bool {
+		self.flags & Self::FLAG_AUTHENTICATED != 0
+	}
+
+	/// Decompresses this overlay's data, clearing the compressed flag and size.
+	///
+	/// Returns `None` if this overlay isn't compressed.
+	pub fn decompress(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, DecompressError> {
+		if !self.is_compressed() {
+			return Ok(None);
+		}
+
+		let decompressed = blz::decompress(data)
+			.map_err(DecompressError::Decompress)?
+			.ok_or(DecompressError::NotCompressed)?;
+		self.flags &= !Self::FLAG_COMPRESSED;
+		self.compressed_size = 0;
+
+		Ok(Some(decompressed))
+	}
+
+	/// Compresses this overlay's data, setting the compressed flag and size.
+	///
+	/// Returns `None` if the data isn't compressible.
+	pub fn compress(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, CompressError> {
+		if self.is_compressed() {
+			return Err(CompressError::AlreadyCompressed);
+		}
+
+		let compressed = match blz::compress(data, 0).map_err(CompressError::Compress)? {
+			Some(compressed) => compressed,
+			None => return Ok(None),
+		};
+		self.compressed_size = u32::try_from(compressed.len())
+			.ok()
+			.filter(|&size| size < 1 << 24)
+			.ok_or(CompressError::TooBig)?;
+		self.flags |= Self::FLAG_COMPRESSED;
+
+		Ok(Some(compressed))
+	}
+}
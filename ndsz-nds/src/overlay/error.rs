@@ -0,0 +1,40 @@
+//! Errors
+
+// Imports
+use {ndsz_compress::blz, std::io};
+
+/// Error for [`OverlayTable::from_reader`](super::OverlayTable::from_reader)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to read overlay
+	#[error("Unable to read overlay")]
+	ReadOverlay(#[source] io::Error),
+}
+
+/// Error for [`Overlay::decompress`](super::Overlay::decompress)
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressError {
+	/// Overlay wasn't compressed, despite it's flags
+	#[error("Overlay wasn't compressed, despite it's flags")]
+	NotCompressed,
+
+	/// Unable to decompress
+	#[error("Unable to decompress")]
+	Decompress(#[source] blz::DecompressError),
+}
+
+/// Error for [`Overlay::compress`](super::Overlay::compress)
+#[derive(Debug, thiserror::Error)]
+pub enum CompressError {
+	/// Overlay already compressed
+	#[error("Overlay already compressed")]
+	AlreadyCompressed,
+
+	/// Unable to compress
+	#[error("Unable to compress")]
+	Compress(#[source] blz::CompressError),
+
+	/// Compressed overlay too big
+	#[error("Compressed overlay too big")]
+	TooBig,
+}
@@ -0,0 +1,415 @@
+//! Rom
+
+// Modules
+mod error;
+
+// Exports
+pub use self::error::{FromReaderError, ReadBannerError, ReplaceFileError};
+
+// Imports
+use {
+	crate::{banner, fs, header::TableLoadData, Banner, DsiHeader, Header, Overlay, OverlayTable},
+	byteorder::{LittleEndian, ReadBytesExt},
+	ndsz_fat::{fat::Allocator, FileAllocationTable, FileNameTable, FilePtr, FileRef},
+	ndsz_util::{IoSlice, ReadByteArray},
+	std::{
+		convert::TryFrom,
+		io::{self, SeekFrom},
+	},
+};
+
+/// Nds rom
+#[derive(Clone, Debug)]
+pub struct Rom<R> {
+	/// Header
+	pub header: Header,
+
+	/// Dsi header
+	pub dsi_header: Option<DsiHeader>,
+
+	/// Fat
+	pub fat: FileAllocationTable,
+
+	/// Fnt
+	pub fnt: FileNameTable,
+
+	/// Arm9 overlay table
+	pub arm9_overlay_table: OverlayTable,
+
+	/// Arm7 overlay table
+	pub arm7_overlay_table: OverlayTable,
+
+	/// Reader
+	pub reader: R,
+}
+
+impl<R> Rom<R> {
+	/// Size of the download play rsa signature, stored at the end of the ntr region
+	pub const RSA_SIGNATURE_SIZE: u32 = 0x88;
+
+	/// Reads the rom from a reader
+	pub fn from_reader(mut reader: R) -> Result<Self, FromReaderError>
+	where
+		R: io::Read + io::Seek,
+	{
+		// Read the header
+		reader.seek(SeekFrom::Start(0)).map_err(FromReaderError::SeekHeader)?;
+		let header = {
+			let bytes = reader.read_byte_array().map_err(FromReaderError::ReadHeader)?;
+			Header::from_bytes(&bytes).map_err(FromReaderError::ParseHeader)?
+		};
+
+		// Then the dsi header, if the rom has one
+		let dsi_header = match header.unit_code.has_dsi_header() {
+			true => {
+				let bytes = reader.read_byte_array().map_err(FromReaderError::ReadDsiHeader)?;
+				Some(DsiHeader::from_bytes(&bytes))
+			},
+			false => None,
+		};
+
+		// Then the fat and fnt
+		let fat = {
+			let mut slice =
+				self::table_slice(&mut reader, header.file_allocation_table).map_err(FromReaderError::FatSlice)?;
+			FileAllocationTable::from_reader(&mut slice).map_err(FromReaderError::ReadFat)?
+		};
+		let fnt = {
+			let mut slice =
+				self::table_slice(&mut reader, header.file_name_table).map_err(FromReaderError::FntSlice)?;
+			FileNameTable::from_reader(&mut slice).map_err(FromReaderError::ReadFnt)?
+		};
+
+		// And finally the overlay tables
+		let arm9_overlay_table = {
+			let mut slice = self::table_slice(&mut reader, header.arm9_overlay_table)
+				.map_err(FromReaderError::Arm9OverlayTableSlice)?;
+			OverlayTable::from_reader(&mut slice).map_err(FromReaderError::ReadArm9OverlayTable)?
+		};
+		let arm7_overlay_table = {
+			let mut slice = self::table_slice(&mut reader, header.arm7_overlay_table)
+				.map_err(FromReaderError::Arm7OverlayTableSlice)?;
+			OverlayTable::from_reader(&mut slice).map_err(FromReaderError::ReadArm7OverlayTable)?
+		};
+
+		Ok(Self {
+			header,
+			dsi_header,
+			fat,
+			fnt,
+			arm9_overlay_table,
+			arm7_overlay_table,
+			reader,
+		})
+	}
+
+	/// Opens the arm9 binary
+	pub fn arm9(&mut self) -> Result<IoSlice<&mut R>, io::Error>
+	where
+		R: io::Seek,
+	{
+		let load_data = self.header.arm9_load_data;
+		IoSlice::new_with_offset_len(&mut self.reader, u64::from(load_data.offset), u64::from(load_data.size))
+	}
+
+	/// Opens the arm7 binary
+	pub fn arm7(&mut self) -> Result<IoSlice<&mut R>, io::Error>
+	where
+		R: io::Seek,
+	{
+		let load_data = self.header.arm7_load_data;
+		IoSlice::new_with_offset_len(&mut self.reader, u64::from(load_data.offset), u64::from(load_data.size))
+	}
+
+	/// Opens a file, by id or path
+	pub fn open_file<'a, F: Into<FileRef<'a>>>(&mut self, file: F) -> Result<IoSlice<&mut R>, fs::OpenFileError>
+	where
+		R: io::Seek,
+	{
+		fs::open_file(&mut self.reader, &self.fat, &self.fnt, file)
+	}
+
+	/// Reads the raw banner.
+	///
+	/// Returns `None` if the rom has no banner.
+	pub fn banner_bytes(&mut self) -> Result<Option<Vec<u8>>, ReadBannerError>
+	where
+		R: io::Read + io::Seek,
+	{
+		// If there's no banner, return
+		let offset = self.header.icon_banner_offset;
+		if offset == 0 {
+			return Ok(None);
+		}
+
+		// Else read the version to get the size of the banner
+		self.reader
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReadBannerError::Seek)?;
+		let version = self
+			.reader
+			.read_u16::<LittleEndian>()
+			.map_err(ReadBannerError::ReadVersion)?;
+		let size = banner::Version::from_u16(version)
+			.ok_or(ReadBannerError::UnknownVersion { version })?
+			.size();
+
+		// Then read it
+		let mut bytes = vec![0; size];
+		self.reader
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReadBannerError::Seek)?;
+		self.reader.read_exact(&mut bytes).map_err(ReadBannerError::Read)?;
+
+		Ok(Some(bytes))
+	}
+
+	/// Reads and parses the banner.
+	///
+	/// Returns `None` if the rom has no banner.
+	pub fn banner(&mut self) -> Result<Option<Banner>, ReadBannerError>
+	where
+		R: io::Read + io::Seek,
+	{
+		self.banner_bytes()?
+			.map(|bytes| Banner::from_bytes(&bytes).map_err(ReadBannerError::Parse))
+			.transpose()
+	}
+
+	/// Replaces the contents of a file, by id or path, without rebuilding the rom.
+	///
+	/// If the new contents fit within the file's slot (see [`FileAllocationTable::slot_end`]),
+	/// the file is overwritten in-place, else it is relocated to the end of the rom.
+	/// Afterwards, the fat entry of the file and the header are updated, as well as the
+	/// overlay table entry, if the file is an overlay.
+	///
+	/// For overlays, `compressed` sets if the new contents are compressed. If `None`, the
+	/// compressed flag is kept, and the new contents must be compressed if it's set.
+	/// It is ignored for all other files.
+	///
+	/// If the rom has a download play rsa signature at the end of the ntr region, it is never
+	/// overwritten, and is moved to the new end of the ntr region when relocating.
+	///
+	/// Note: Files in roms with a [`DsiHeader`] can't be relocated, as the end of the
+	///       ntr region is followed by the twl region.
+	pub fn replace_file<'a, F: Into<FileRef<'a>>>(
+		&mut self,
+		file: F,
+		data: &[u8],
+		compressed: Option<bool>,
+	) -> Result<(), ReplaceFileError>
+	where
+		R: io::Read + io::Write + io::Seek,
+	{
+		let file = file.into();
+		let not_found = || ReplaceFileError::NotFound { file: file.to_string() };
+		let id = file.resolve(&self.fnt).ok_or_else(not_found)?;
+		let ptr = *self.fat.ptrs.get(usize::from(id)).ok_or_else(not_found)?;
+		let data_len = u32::try_from(data.len()).map_err(|_| ReplaceFileError::TooBig)?;
+		let rom_end = self.header.ntr_region_rom_size;
+		let rsa_signature = self.read_rsa_signature()?;
+
+		// Get the end of the slot, making sure to not overwrite any of the other parts of the rom
+		let header = &self.header;
+		let part_offsets = [
+			header.arm9_load_data.offset,
+			header.arm7_load_data.offset,
+			header.file_name_table.offset,
+			header.file_allocation_table.offset,
+			header.arm9_overlay_table.offset,
+			header.arm7_overlay_table.offset,
+			header.icon_banner_offset,
+		];
+		let slot_end = part_offsets
+			.iter()
+			.copied()
+			.chain(rsa_signature.map(|_| rom_end))
+			.filter(|&offset| offset >= ptr.end_address)
+			.fold(
+				self.fat
+					.slot_end(id, Allocator::ROM_ALIGNMENT)
+					.expect("File id was just checked"),
+				u32::min,
+			);
+
+		// Then write the file, either in-place or at the end of the rom
+		let new_ptr = match ptr.start_address.checked_add(data_len) {
+			Some(end_address) if end_address <= slot_end => {
+				self.write_at(ptr.start_address, data)?;
+
+				// Note: If the new file is smaller, pad the rest of the old file
+				if end_address < ptr.end_address {
+					self.write_padding_at(end_address, ptr.end_address - end_address)?;
+				}
+
+				FilePtr {
+					start_address: ptr.start_address,
+					end_address,
+				}
+			},
+			_ => {
+				if self.dsi_header.is_some() {
+					return Err(ReplaceFileError::CannotRelocateDsi);
+				}
+
+				// Note: The rsa signature is overwritten by padding, and moved after the file.
+				let signature_end = match rsa_signature {
+					Some(_) => rom_end
+						.checked_add(Self::RSA_SIGNATURE_SIZE)
+						.ok_or(ReplaceFileError::TooBig)?,
+					None => rom_end,
+				};
+				let mut allocator = Allocator::new(Allocator::ROM_ALIGNMENT, signature_end);
+				let new_ptr = allocator.alloc(data_len).map_err(|_| ReplaceFileError::TooBig)?;
+				self.write_padding_at(rom_end, new_ptr.start_address - rom_end)?;
+				self.write_at(new_ptr.start_address, data)?;
+				if let Some(rsa_signature) = &rsa_signature {
+					self.reader
+						.write_all(rsa_signature)
+						.map_err(ReplaceFileError::WriteRsaSignature)?;
+				}
+
+				new_ptr
+			},
+		};
+
+		// Update the fat
+		let fat_entry_offset = usize::from(id)
+			.checked_mul(0x8)
+			.and_then(|offset| u32::try_from(offset).ok())
+			.and_then(|offset| self.header.file_allocation_table.offset.checked_add(offset))
+			.ok_or(ReplaceFileError::TooBig)?;
+		self.reader
+			.seek(SeekFrom::Start(u64::from(fat_entry_offset)))
+			.map_err(ReplaceFileError::SeekFatEntry)?;
+		self.reader
+			.write_all(&new_ptr.to_bytes())
+			.map_err(ReplaceFileError::WriteFatEntry)?;
+		self.fat.ptrs[usize::from(id)] = new_ptr;
+
+		// Then the overlay, if the file is one
+		self.update_overlay(id, data, compressed)?;
+
+		// And finally the header
+		self.header.ntr_region_rom_size = self.header.ntr_region_rom_size.max(new_ptr.end_address);
+		let rom_size = match rsa_signature {
+			Some(_) => u64::from(self.header.ntr_region_rom_size) + u64::from(Self::RSA_SIGNATURE_SIZE),
+			None => u64::from(self.header.ntr_region_rom_size),
+		};
+		loop {
+			let size = self
+				.header
+				.device_capacity_size()
+				.ok_or(ReplaceFileError::InvalidDeviceCapacity {
+					device_capacity: self.header.device_capacity,
+				})?;
+			if size >= rom_size {
+				break;
+			}
+			self.header.device_capacity += 1;
+		}
+		self.header.update_crcs();
+		self.reader
+			.seek(SeekFrom::Start(0))
+			.map_err(ReplaceFileError::SeekHeader)?;
+		self.reader
+			.write_all(&self.header.to_bytes())
+			.map_err(ReplaceFileError::WriteHeader)?;
+
+		Ok(())
+	}
+
+	/// Reads the download play rsa signature at the end of the ntr region.
+	///
+	/// Returns `None` if the rom ends before it, or it's only padding.
+	fn read_rsa_signature(&mut self) -> Result<Option<[u8; 0x88]>, ReplaceFileError>
+	where
+		R: io::Read + io::Seek,
+	{
+		self.reader
+			.seek(SeekFrom::Start(u64::from(self.header.ntr_region_rom_size)))
+			.map_err(ReplaceFileError::SeekRsaSignature)?;
+		match self.reader.read_byte_array::<0x88>() {
+			Ok(bytes) if bytes.iter().all(|&byte| byte == 0xff) => Ok(None),
+			Ok(bytes) => Ok(Some(bytes)),
+			Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+			Err(err) => Err(ReplaceFileError::ReadRsaSignature(err)),
+		}
+	}
+
+	/// Updates the overlay table entry of the overlay with file id `id`, if any, with it's new `data`.
+	///
+	/// See [`Self::replace_file`] for details on `compressed`.
+	fn update_overlay(&mut self, id: u16, data: &[u8], compressed: Option<bool>) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		let is_file = |overlay: &Overlay| overlay.file_id == u32::from(id);
+		let (overlays, table_load_data) = match self.arm9_overlay_table.overlays.iter().any(is_file) {
+			true => (&mut self.arm9_overlay_table.overlays, self.header.arm9_overlay_table),
+			false => (&mut self.arm7_overlay_table.overlays, self.header.arm7_overlay_table),
+		};
+		let idx = match overlays.iter().position(is_file) {
+			Some(idx) => idx,
+			None => return Ok(()),
+		};
+		let overlay = &mut overlays[idx];
+
+		match compressed.unwrap_or_else(|| overlay.is_compressed()) {
+			true => {
+				overlay.compressed_size = u32::try_from(data.len())
+					.ok()
+					.filter(|&size| size < 1 << 24)
+					.ok_or(ReplaceFileError::TooBig)?;
+				overlay.flags |= Overlay::FLAG_COMPRESSED;
+			},
+			false => {
+				overlay.compressed_size = 0;
+				overlay.flags &= !Overlay::FLAG_COMPRESSED;
+			},
+		}
+
+		let entry_offset = idx
+			.checked_mul(0x20)
+			.and_then(|offset| u32::try_from(offset).ok())
+			.and_then(|offset| table_load_data.offset.checked_add(offset))
+			.ok_or(ReplaceFileError::TooBig)?;
+		let entry_bytes = overlay.to_bytes();
+		self.reader
+			.seek(SeekFrom::Start(u64::from(entry_offset)))
+			.map_err(ReplaceFileError::SeekOverlayEntry)?;
+		self.reader
+			.write_all(&entry_bytes)
+			.map_err(ReplaceFileError::WriteOverlayEntry)
+	}
+
+	/// Writes `data` at `offset`
+	fn write_at(&mut self, offset: u32, data: &[u8]) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		self.reader
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReplaceFileError::SeekFile)?;
+		self.reader.write_all(data).map_err(ReplaceFileError::WriteFile)
+	}
+
+	/// Writes `len` bytes of padding at `offset`
+	fn write_padding_at(&mut self, offset: u32, len: u32) -> Result<(), ReplaceFileError>
+	where
+		R: io::Write + io::Seek,
+	{
+		self.reader
+			.seek(SeekFrom::Start(u64::from(offset)))
+			.map_err(ReplaceFileError::SeekFile)?;
+		Allocator::new(Allocator::ROM_ALIGNMENT, offset)
+			.with_padding(0xff)
+			.pad(&mut self.reader, u64::from(len))
+			.map_err(ReplaceFileError::WritePadding)
+	}
+}
+
+/// Creates a slice over a table
+fn table_slice<R: io::Seek>(reader: R, table: TableLoadData) -> Result<IoSlice<R>, io::Error> {
+	IoSlice::new_with_offset_len(reader, u64::from(table.offset), u64::from(table.length))
+}
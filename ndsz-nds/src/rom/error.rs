@@ -0,0 +1,152 @@
+//! Errors
+
+// Imports
+use {
+	crate::{banner, header, overlay},
+	ndsz_fat::{fat, fnt},
+	std::io,
+};
+
+/// Error for [`Rom::from_reader`](super::Rom::from_reader)
+#[derive(Debug, thiserror::Error)]
+pub enum FromReaderError {
+	/// Unable to seek to header
+	#[error("Unable to seek to header")]
+	SeekHeader(#[source] io::Error),
+
+	/// Unable to read header
+	#[error("Unable to read header")]
+	ReadHeader(#[source] io::Error),
+
+	/// Unable to parse header
+	#[error("Unable to parse header")]
+	ParseHeader(#[source] header::FromBytesError),
+
+	/// Unable to read dsi header
+	#[error("Unable to read dsi header")]
+	ReadDsiHeader(#[source] io::Error),
+
+	/// Unable to create fat slice
+	#[error("Unable to create fat slice")]
+	FatSlice(#[source] io::Error),
+
+	/// Unable to read fat
+	#[error("Unable to read fat")]
+	ReadFat(#[source] fat::FromReaderError),
+
+	/// Unable to create fnt slice
+	#[error("Unable to create fnt slice")]
+	FntSlice(#[source] io::Error),
+
+	/// Unable to read fnt
+	#[error("Unable to read fnt")]
+	ReadFnt(#[source] fnt::FromReaderError),
+
+	/// Unable to create arm9 overlay table slice
+	#[error("Unable to create arm9 overlay table slice")]
+	Arm9OverlayTableSlice(#[source] io::Error),
+
+	/// Unable to read arm9 overlay table
+	#[error("Unable to read arm9 overlay table")]
+	ReadArm9OverlayTable(#[source] overlay::FromReaderError),
+
+	/// Unable to create arm7 overlay table slice
+	#[error("Unable to create arm7 overlay table slice")]
+	Arm7OverlayTableSlice(#[source] io::Error),
+
+	/// Unable to read arm7 overlay table
+	#[error("Unable to read arm7 overlay table")]
+	ReadArm7OverlayTable(#[source] overlay::FromReaderError),
+}
+
+/// Error for [`Rom::banner`](super::Rom::banner) and [`Rom::banner_bytes`](super::Rom::banner_bytes)
+#[derive(Debug, thiserror::Error)]
+pub enum ReadBannerError {
+	/// Unable to seek to banner
+	#[error("Unable to seek to banner")]
+	Seek(#[source] io::Error),
+
+	/// Unable to read version
+	#[error("Unable to read version")]
+	ReadVersion(#[source] io::Error),
+
+	/// Unknown version
+	#[error("Unknown banner version {version:#x}")]
+	UnknownVersion { version: u16 },
+
+	/// Unable to read banner
+	#[error("Unable to read banner")]
+	Read(#[source] io::Error),
+
+	/// Unable to parse banner
+	#[error("Unable to parse banner")]
+	Parse(#[source] banner::FromBytesError),
+}
+
+/// Error for [`Rom::replace_file`](super::Rom::replace_file)
+#[derive(Debug, thiserror::Error)]
+pub enum ReplaceFileError {
+	/// File not found
+	#[error("File {file} not found")]
+	NotFound { file: String },
+
+	/// Rom too big
+	#[error("Rom too big")]
+	TooBig,
+
+	/// Cannot relocate files in dsi roms
+	#[error("New file doesn't fit in it's slot, and files in dsi roms cannot be relocated")]
+	CannotRelocateDsi,
+
+	/// Unable to seek to file
+	#[error("Unable to seek to file")]
+	SeekFile(#[source] io::Error),
+
+	/// Unable to write file
+	#[error("Unable to write file")]
+	WriteFile(#[source] io::Error),
+
+	/// Unable to write padding
+	#[error("Unable to write padding")]
+	WritePadding(#[source] io::Error),
+
+	/// Unable to seek to rsa signature
+	#[error("Unable to seek to rsa signature")]
+	SeekRsaSignature(#[source] io::Error),
+
+	/// Unable to read rsa signature
+	#[error("Unable to read rsa signature")]
+	ReadRsaSignature(#[source] io::Error),
+
+	/// Unable to write rsa signature
+	#[error("Unable to write rsa signature")]
+	WriteRsaSignature(#[source] io::Error),
+
+	/// Unable to seek to fat entry
+	#[error("Unable to seek to fat entry")]
+	SeekFatEntry(#[source] io::Error),
+
+	/// Unable to write fat entry
+	#[error("Unable to write fat entry")]
+	WriteFatEntry(#[source] io::Error),
+
+	/// Unable to seek to overlay table entry
+	#[error("Unable to seek to overlay table entry")]
+	SeekOverlayEntry(#[source] io::Error),
+
+	/// Unable to write overlay table entry
+	#[error("Unable to write overlay table entry")]
+	WriteOverlayEntry(#[source] io::Error),
+
+	/// Invalid device capacity
+	#[error("Invalid device capacity {device_capacity}")]
+	InvalidDeviceCapacity { device_capacity: u8 },
+
+	/// Unable to seek to header
+	#[error("Unable to seek to header")]
+	SeekHeader(#[source] io::Error),
+
+	/// Unable to write header
+	#[error("Unable to write header")]
+	WriteHeader(#[source] io::Error),
+}
@@ -2,6 +2,8 @@
 
 /// Unit code
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum UnitCode {
 	Nds,
 	NdsDsi,
@@ -20,4 +22,20 @@ impl UnitCode {
 
 		Some(code)
 	}
+
+	/// Returns if this unit code has a [`DsiHeader`](crate::DsiHeader)
+	#[must_use]
+	pub const fn has_dsi_header(self) -> bool {
+		matches!(self, Self::NdsDsi | Self::Dsi)
+	}
+
+	/// Writes this unit code to bytes
+	#[must_use]
+	pub const fn to_bytes(self) -> u8 {
+		match self {
+			Self::Nds => 0x0,
+			Self::NdsDsi => 0x2,
+			Self::Dsi => 0x3,
+		}
+	}
 }
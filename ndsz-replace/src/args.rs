@@ -0,0 +1,28 @@
+//! Arguments
+
+// Imports
+use std::path::PathBuf;
+
+/// Arguments
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(clap::Parser)]
+pub struct Args {
+	/// Archive path.
+	///
+	/// Either a `.nds` or a `.narc`, detected by it's magic.
+	pub archive_path: PathBuf,
+
+	/// File to replace.
+	///
+	/// Either a path within the archive, or `#{id}` for a file id
+	pub file: String,
+
+	/// Path of the new file
+	pub new_file_path: PathBuf,
+
+	/// If the new file is compressed, for overlays.
+	///
+	/// If not given, the overlay's compressed flag is kept.
+	#[clap(long = "compressed")]
+	pub compressed: Option<bool>,
+}
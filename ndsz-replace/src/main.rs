@@ -0,0 +1,66 @@
+//! Replaces a file within a `.nds` or `.narc`
+
+// Modules
+mod args;
+
+// Imports
+use {
+	self::args::Args,
+	anyhow::Context,
+	clap::Parser,
+	ndsz_fat::FileRef,
+	ndsz_narc::Narc,
+	ndsz_nds::Rom,
+	ndsz_util::ReadByteArray,
+	std::{
+		fs,
+		io::{Seek, SeekFrom},
+	},
+	tracing_subscriber::prelude::*,
+};
+
+
+fn main() -> Result<(), anyhow::Error> {
+	// Initialize the logger
+	tracing_subscriber::registry()
+		.with(tracing_subscriber::fmt::layer().with_filter(tracing_subscriber::EnvFilter::from_default_env()))
+		.init();
+
+	// Get the arguments
+	let args = Args::parse();
+
+	// Parse the file
+	let file = match args.file.strip_prefix('#') {
+		Some(id) => FileRef::Id(id.parse().with_context(|| format!("Invalid file id {id:?}"))?),
+		None => FileRef::Path(&args.file),
+	};
+
+	// Read the new file
+	let data = fs::read(&args.new_file_path).context("Unable to read new file")?;
+
+	// Open the archive and check if it's a narc
+	let mut archive = fs::OpenOptions::new()
+		.read(true)
+		.write(true)
+		.open(&args.archive_path)
+		.context("Unable to open archive")?;
+	let magic: [u8; 4] = archive.read_byte_array().context("Unable to read archive magic")?;
+	archive
+		.seek(SeekFrom::Start(0))
+		.context("Unable to seek to archive start")?;
+
+	// Then replace the file
+	match &magic {
+		b"NARC" => {
+			let mut narc = Narc::from_reader(archive).context("Unable to read narc")?;
+			narc.replace_file(file, &data).context("Unable to replace file")?;
+		},
+		_ => {
+			let mut rom = Rom::from_reader(archive).context("Unable to read rom")?;
+			rom.replace_file(file, &data, args.compressed)
+				.context("Unable to replace file")?;
+		},
+	}
+
+	Ok(())
+}
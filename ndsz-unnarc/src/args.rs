@@ -23,4 +23,29 @@ pub struct Args {
 	/// Extract fat on empty fnt
 	#[clap(long = "extract-fat-on-empty-fnt")]
 	pub extract_fat_on_empty_fnt: bool,
+
+	/// Recursively extracts all narcs within the narc.
+	///
+	/// Narcs are detected by their header, and extracted to `{path}.d/`,
+	/// while the original file is kept.
+	/// All extracted narcs are recorded in `nested_narcs.yaml`, and are repacked
+	/// from their directory when rebuilding, replacing the original file.
+	#[clap(long = "recursive")]
+	pub recursive: bool,
+
+	/// Decompresses all compressed files within the narc.
+	///
+	/// Files compressed with any of the bios compressions (lz10, lz11, huffman, rle or diff)
+	/// are decompressed in-place.
+	/// All decompressed files are recorded in `compressed_files.yaml`.
+	#[clap(long = "decompress")]
+	pub decompress: bool,
+
+	/// Adds extensions to all files within the narc, based on their file type.
+	///
+	/// File types are detected by their magic and structure, after decompressing, if requested.
+	/// Only files without an extension, or with a `.bin` extension, are renamed, and
+	/// all renamed files are recorded in `detected_files.yaml`.
+	#[clap(long = "extensions")]
+	pub extensions: bool,
 }
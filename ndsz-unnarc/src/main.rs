@@ -1,8 +1,5 @@
 //! Unpacks a `.narc`
 
-// Features
-#![feature(fs_try_exists)]
-
 // Modules
 mod args;
 
@@ -11,7 +8,8 @@ use {
 	self::args::Args,
 	anyhow::Context,
 	clap::Parser,
-	ndsz_fat::{dir, Dir, FileAllocationTable},
+	ndsz_fat::{dir, fnt, Dir, FileAllocationTable},
+	ndsz_manifest::{compressed::Decompressor, detected::Detector, nested::NestedExtractor},
 	ndsz_narc::Narc,
 	ndsz_util::{AsciiStrArr, IoSlice},
 	std::{fs, io, path::PathBuf},
@@ -43,15 +41,84 @@ fn main() -> Result<(), anyhow::Error> {
 		None => args.input_path.with_extension(""),
 	};
 
+	// Make sure no files in the root would be overwritten by the manifests
+	if let Some(entry) = narc
+		.fnt
+		.root
+		.entries
+		.iter()
+		.find(|entry| ndsz_manifest::NARC_MANIFEST_NAMES.contains(&entry.name.as_str()))
+	{
+		anyhow::bail!(
+			"Narc root contains {:?}, which would be overwritten by a manifest",
+			entry.name.as_str()
+		);
+	}
+
 	// Create the output directory if it doesn't exist
 	fs::create_dir_all(&output_path).context("Unable to create directory")?;
 
 	// Extract the filesystem
-	match args.extract_fat_on_empty_fnt && narc.fnt.root.entries.is_empty() {
-		true => self::extract_fat_entries::<IoSlice<fs::File>>(&narc.fat, &narc.data.0, output_path)
+	let flat = args.extract_fat_on_empty_fnt && narc.fnt.root.entries.is_empty();
+	match flat {
+		true => self::extract_fat_entries::<IoSlice<fs::File>>(&narc.fat, &narc.data.0, output_path.clone())
 			.context("Unable to extract entries of fat")?,
-		false => self::extract_fat_dir::<IoSlice<fs::File>>(&narc.fnt.root, &narc.data.0, &narc.fat, output_path)
-			.context("Unable to extract fat")?,
+		false =>
+			self::extract_fat_dir::<IoSlice<fs::File>>(&narc.fnt.root, &narc.data.0, &narc.fat, output_path.clone())
+				.context("Unable to extract fat")?,
+	}
+	if !flat {
+		ndsz_manifest::fnt_order::output_yaml(&output_path, &narc.fnt.root)
+			.context("Unable to output fnt order manifest")?;
+	}
+
+	// Then detect the file types, decompress and extract all narcs within, if requested
+	let mut decompressor = args.decompress.then(|| Decompressor::new(&output_path));
+	let mut nested_extractor = args.recursive.then(|| NestedExtractor::new(&output_path));
+	let mut detector = args
+		.extensions
+		.then(|| Detector::new(&output_path, decompressor.is_some()));
+	let paths = match flat {
+		true => (0..narc.fat.ptrs.len())
+			.map(|idx| output_path.join(format!("{idx}.bin")))
+			.collect::<Vec<_>>(),
+		false => fnt::Index::new(&narc.fnt)
+			.files()
+			.map(|(_, path)| output_path.join(path))
+			.collect(),
+	};
+	for path in paths {
+		let path = match &mut detector {
+			Some(detector) => detector
+				.rename(&path)
+				.with_context(|| format!("Unable to detect file type of {path:?}"))?,
+			None => path,
+		};
+		if let Some(decompressor) = &mut decompressor {
+			decompressor
+				.decompress(&path)
+				.with_context(|| format!("Unable to decompress {path:?}"))?;
+		}
+		if let Some(nested_extractor) = &mut nested_extractor {
+			nested_extractor
+				.extract(&path, decompressor.as_mut(), detector.as_mut())
+				.with_context(|| format!("Unable to extract nested narcs in {path:?}"))?;
+		}
+	}
+	if let Some(decompressor) = &decompressor {
+		decompressor
+			.output_yaml()
+			.context("Unable to output compressed files manifest")?;
+	}
+	if let Some(nested_extractor) = &nested_extractor {
+		nested_extractor
+			.output_yaml()
+			.context("Unable to output nested narcs manifest")?;
+	}
+	if let Some(detector) = &detector {
+		detector
+			.output_yaml()
+			.context("Unable to output detected files manifest")?;
 	}
 
 	Ok(())
@@ -77,7 +144,8 @@ where
 	&'reader R: io::Read + io::Seek,
 {
 	type Error = anyhow::Error;
-	type SubDirVisitor<'visitor, 'entry> = DirVisitor<'fat, 'reader, R>
+	type SubDirVisitor<'visitor, 'entry>
+		= DirVisitor<'fat, 'reader, R>
 	where
 		Self: 'visitor;
 
@@ -143,7 +211,7 @@ where
 	&'a R: io::Read + io::Seek,
 {
 	for (idx, rom_file_ptr) in fat.ptrs.iter().enumerate() {
-		let path = path.join(&format!("{idx}.bin"));
+		let path = path.join(format!("{idx}.bin"));
 		println!("{}", path.display());
 
 		// Get the file on the rom
@@ -15,4 +15,39 @@ pub struct Args {
 	/// Defaults to `input_path` without an extension
 	#[clap(long = "output", short = 'o')]
 	pub output_path: Option<PathBuf>,
+
+	/// Only verify the header, without extracting anything.
+	///
+	/// Checks the header crcs, nintendo logo and that all parts
+	/// are within the rom.
+	#[clap(long = "verify")]
+	pub verify: bool,
+
+	/// Recursively extracts all narcs within the rom.
+	///
+	/// Narcs are detected by their header, and extracted to `{path}.d/`,
+	/// while the original file is kept.
+	/// All extracted narcs are recorded in `nested_narcs.yaml`, and are repacked
+	/// from their directory when rebuilding, replacing the original file.
+	#[clap(long = "recursive")]
+	pub recursive: bool,
+
+	/// Decompresses all compressed files within the rom.
+	///
+	/// Files compressed with any of the bios compressions (lz10, lz11, huffman, rle or diff)
+	/// are decompressed in-place.
+	/// All decompressed files are recorded in `compressed_files.yaml`.
+	///
+	/// The arm9 binary and overlays compressed with blz are also decompressed in-place,
+	/// and recorded in `compressed_code.yaml`.
+	#[clap(long = "decompress")]
+	pub decompress: bool,
+
+	/// Adds extensions to all files within the rom, based on their file type.
+	///
+	/// File types are detected by their magic and structure, after decompressing, if requested.
+	/// Only files without an extension, or with a `.bin` extension, are renamed, and
+	/// all renamed files are recorded in `detected_files.yaml`.
+	#[clap(long = "extensions")]
+	pub extensions: bool,
 }
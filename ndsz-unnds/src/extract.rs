@@ -3,10 +3,13 @@
 // Imports
 use {
 	anyhow::Context,
-	ndsz_fat::{dir, Dir, FileAllocationTable, FileNameTable},
+	ndsz_fat::{dir, fnt, Dir, FileAllocationTable},
+	ndsz_manifest::compressed::Decompressor,
+	ndsz_nds::{banner, Banner, OverlayTable, Rom},
 	ndsz_util::{AsciiStrArr, IoSlice},
 	std::{
 		collections::HashSet,
+		convert::TryFrom,
 		fs,
 		io,
 		path::{Path, PathBuf},
@@ -30,7 +33,8 @@ struct ExtractorVisitor<'fat, 'reader, R> {
 
 impl<'fat, 'reader, R: io::Read + io::Seek> dir::Visitor for ExtractorVisitor<'fat, 'reader, R> {
 	type Error = anyhow::Error;
-	type SubDirVisitor<'visitor, 'entry> = ExtractorVisitor<'fat, 'visitor, R>
+	type SubDirVisitor<'visitor, 'entry>
+		= ExtractorVisitor<'fat, 'visitor, R>
 	where
 		Self: 'visitor;
 
@@ -72,39 +76,6 @@ impl<'fat, 'reader, R: io::Read + io::Seek> dir::Visitor for ExtractorVisitor<'f
 	}
 }
 
-/// Visitor that collects all files
-struct CollectFilesVisitor<'fat, 'files> {
-	/// The fat
-	fat: &'fat FileAllocationTable,
-
-	/// All file ids visited
-	files_visited: &'files mut HashSet<u16>,
-}
-
-impl<'fat, 'files> dir::Visitor for CollectFilesVisitor<'fat, 'files> {
-	type Error = !;
-	type SubDirVisitor<'visitor, 'entry> = CollectFilesVisitor<'fat, 'visitor>
-	where
-		Self: 'visitor;
-
-	fn visit_file(&mut self, _name: &AsciiStrArr<0x80>, id: u16) -> Result<(), Self::Error> {
-		self.files_visited.insert(id);
-
-		Ok(())
-	}
-
-	fn visit_dir<'visitor, 'entry>(
-		&'visitor mut self,
-		_name: &'entry AsciiStrArr<0x80>,
-		_id: u16,
-	) -> Result<Self::SubDirVisitor<'visitor, 'entry>, Self::Error> {
-		Ok(CollectFilesVisitor {
-			fat:           self.fat,
-			files_visited: self.files_visited,
-		})
-	}
-}
-
 /// Extracts all files from a fat directory
 pub fn extract_fat_dir<R: io::Read + io::Seek>(
 	dir: &Dir,
@@ -120,27 +91,22 @@ pub fn extract_fat_dir<R: io::Read + io::Seek>(
 	dir.walk(&mut visitor).context("Unable to extract root directory")
 }
 
-/// Extracts the hidden fat files not mentioned in the fnt
+/// Extracts the hidden fat files not mentioned in the fnt, nor in `overlay_file_ids`
 pub fn extract_fat_hidden<R: io::Read + io::Seek>(
 	fat: &FileAllocationTable,
-	fnt: &FileNameTable,
+	fnt_index: &fnt::Index,
+	overlay_file_ids: impl IntoIterator<Item = u16>,
 	rom_file: &mut R,
 	output_path: &Path,
 ) -> Result<Vec<u16>, anyhow::Error> {
-	let mut fnt_files = HashSet::new();
-	fnt.root
-		.walk(&mut CollectFilesVisitor {
-			fat,
-			files_visited: &mut fnt_files,
-		})
-		.into_ok();
+	let overlay_file_ids = overlay_file_ids.into_iter().collect::<HashSet<_>>();
 
 	let mut extracted = vec![];
 	let fat_dir = output_path.join("fat");
 	fs::create_dir_all(&fat_dir).context("Unable to create fat output directory")?;
 	for (ptr, idx) in fat.ptrs.iter().zip(0..) {
 		// If this file isn't hidden, continue
-		if fnt_files.contains(&idx) {
+		if fnt_index.contains_file(idx) || overlay_file_ids.contains(&idx) {
 			continue;
 		}
 		extracted.push(idx);
@@ -180,15 +146,89 @@ pub fn extract_all_parts<R: io::Read + io::Seek>(
 			header.arm7_load_data.size,
 			"arm7_load_data",
 		),
+	];
+
+	for (offset, size, name) in parts {
+		self::extract_part(rom_file, offset, size, name, path).with_context(|| format!("Unable to extract {name}"))?;
+	}
+
+	Ok(())
+}
+
+/// Extracts all overlays of an overlay table, alongside a manifest with all their metadata
+pub fn extract_overlays<R: io::Read + io::Seek>(
+	rom_file: &mut R,
+	fat: &FileAllocationTable,
+	overlay_table: &OverlayTable,
+	path: &Path,
+	mut decompressor: Option<&mut Decompressor>,
+) -> Result<(), anyhow::Error> {
+	// If there are no overlays, we have nothing to extract
+	if overlay_table.overlays.is_empty() {
+		return Ok(());
+	}
+
+	fs::create_dir_all(path).context("Unable to create overlay output directory")?;
+	let mut overlays = overlay_table.overlays.clone();
+	for overlay in &mut overlays {
+		let ptr = usize::try_from(overlay.file_id)
+			.ok()
+			.and_then(|file_id| fat.ptrs.get(file_id))
+			.with_context(|| format!("Overlay {} has an invalid file id {}", overlay.id, overlay.file_id))?;
+		let size = ptr
+			.end_address
+			.checked_sub(ptr.start_address)
+			.with_context(|| format!("Overlay {} ends before it starts ({ptr:?})", overlay.id))?;
+
+		let name = format!("overlay_{:04}", overlay.id);
+		self::extract_part(rom_file, ptr.start_address, size, &name, path)
+			.with_context(|| format!("Unable to extract overlay {} ({ptr:?})", overlay.id))?;
+
+		// Note: Decompressing clears the overlay's compressed flag, so we output the manifest afterwards
+		if let Some(decompressor) = decompressor.as_deref_mut() {
+			let overlay_path = path.join(format!("{name}.bin"));
+			decompressor
+				.decompress_overlay(&overlay_path, overlay)
+				.with_context(|| format!("Unable to decompress overlay {}", overlay.id))?;
+		}
+	}
+
+	// Then write the manifest
+	let manifest_path = path.join("overlays.yaml");
+	println!("{}", manifest_path.display());
+	let manifest_file = fs::File::create(manifest_path).context("Unable to create overlays manifest")?;
+	serde_yaml::to_writer(manifest_file, &overlays).context("Unable to write overlays manifest")?;
+
+	Ok(())
+}
+
+/// Extract all parts of the dsi header
+pub fn extract_dsi_parts<R: io::Read + io::Seek>(
+	rom_file: &mut R,
+	dsi_header: &ndsz_nds::DsiHeader,
+	path: &Path,
+) -> Result<(), anyhow::Error> {
+	let parts = [
+		(0x180, 0xe80, "dsi_header"),
 		(
-			header.arm9_overlay_table.offset,
-			header.arm9_overlay_table.length,
-			"arm9_overlay_table",
+			dsi_header.arm9i_load_data.offset,
+			dsi_header.arm9i_load_data.size,
+			"arm9i_load_data",
 		),
 		(
-			header.arm7_overlay_table.offset,
-			header.arm7_overlay_table.length,
-			"arm7_overlay_table",
+			dsi_header.arm7i_load_data.offset,
+			dsi_header.arm7i_load_data.size,
+			"arm7i_load_data",
+		),
+		(
+			dsi_header.digest_sector_hashtable.offset,
+			dsi_header.digest_sector_hashtable.length,
+			"digest_sector_hashtable",
+		),
+		(
+			dsi_header.digest_block_hashtable.offset,
+			dsi_header.digest_block_hashtable.length,
+			"digest_block_hashtable",
 		),
 	];
 
@@ -199,6 +239,85 @@ pub fn extract_all_parts<R: io::Read + io::Seek>(
 	Ok(())
 }
 
+/// Extracts the icon / banner.
+///
+/// The banner is extracted as `banner.yaml`, alongside all icons as pngs, if it can be
+/// rebuilt exactly from them, else it is extracted as-is.
+///
+/// Returns the size of the banner.
+pub fn extract_banner<R: io::Read + io::Seek>(rom: &mut Rom<R>, path: &Path) -> Result<u32, anyhow::Error> {
+	// Read the banner, if the rom has one
+	let bytes = match rom.banner_bytes().context("Unable to read banner")? {
+		Some(bytes) => bytes,
+		None => return Ok(0),
+	};
+	let raw_size = u32::try_from(bytes.len()).expect("Banner size didn't fit into a `u32`");
+
+	// Then parse it
+	let banner = match Banner::from_bytes(&bytes) {
+		Ok(banner) => banner,
+		Err(err) => {
+			tracing::warn!(
+				"Unable to parse banner, extracting it as-is: {:?}",
+				anyhow::Error::new(err)
+			);
+			self::extract_part(&mut rom.reader, rom.header.icon_banner_offset, raw_size, "banner", path)?;
+			return Ok(raw_size);
+		},
+	};
+	tracing::trace!(?banner);
+
+	// Note: The crcs and reserved areas aren't kept, so if they don't match
+	//       what we'd write, keep the original banner.
+	if banner.to_bytes().ok().as_deref() != Some(&*bytes) {
+		tracing::warn!("Banner can't be rebuilt exactly, extracting it as-is");
+		self::extract_part(&mut rom.reader, rom.header.icon_banner_offset, raw_size, "banner", path)?;
+		return Ok(raw_size);
+	}
+
+	let yaml_path = path.join("banner.yaml");
+	println!("{}", yaml_path.display());
+	let yaml_file = fs::File::create(&yaml_path).context("Unable to create banner manifest")?;
+	serde_yaml::to_writer(yaml_file, &banner).context("Unable to write banner manifest")?;
+
+	self::write_icon_png(&path.join("icon.png"), &banner.icon.bitmap, banner.icon.palette)?;
+	if let Some(animation) = &banner.animation {
+		for (idx, (bitmap, &palette)) in animation.bitmaps.iter().zip(&animation.palettes).enumerate() {
+			self::write_icon_png(&path.join(format!("icon_{idx}.png")), bitmap, palette)?;
+		}
+	}
+
+	Ok(raw_size)
+}
+
+/// Writes an icon as an indexed png.
+///
+/// The first palette color is written as transparent.
+fn write_icon_png(path: &Path, bitmap: &banner::Bitmap, palette: banner::Palette) -> Result<(), anyhow::Error> {
+	println!("{}", path.display());
+	let file = fs::File::create(path).with_context(|| format!("Unable to create {path:?}"))?;
+
+	let width = u32::try_from(banner::Bitmap::WIDTH).expect("Width didn't fit into a `u32`");
+	let height = u32::try_from(banner::Bitmap::HEIGHT).expect("Height didn't fit into a `u32`");
+	let mut encoder = png::Encoder::new(io::BufWriter::new(file), width, height);
+	encoder.set_color(png::ColorType::Indexed);
+	encoder.set_depth(png::BitDepth::Four);
+	encoder.set_palette(palette.to_rgb().concat());
+	encoder.set_trns(vec![0]);
+	let mut writer = encoder.write_header().context("Unable to write png header")?;
+
+	// Note: Png stores the first pixel of each byte in the upper nibble
+	let data = bitmap
+		.to_pixels()
+		.chunks_exact(2)
+		.map(|pixels| (pixels[0] << 4) | pixels[1])
+		.collect::<Vec<_>>();
+	writer.write_image_data(&data).context("Unable to write png data")?;
+	writer.finish().context("Unable to finish png")?;
+
+	Ok(())
+}
+
 /// Extracts a part given it's offset and size from the game file
 fn extract_part<R: io::Read + io::Seek>(
 	rom_file: &mut R,
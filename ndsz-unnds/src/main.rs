@@ -1,24 +1,33 @@
 //! Unpacks a `.nds`
 
-// Features
-#![feature(fs_try_exists, never_type, unwrap_infallible)]
-
 // Modules
 mod args;
 mod extract;
+mod verify;
+mod yaml;
 
 // Imports
 use {
 	self::{
 		args::Args,
-		extract::{extract_all_parts, extract_fat_dir, extract_fat_hidden},
+		extract::{
+			extract_all_parts,
+			extract_banner,
+			extract_dsi_parts,
+			extract_fat_dir,
+			extract_fat_hidden,
+			extract_overlays,
+		},
+		verify::verify_header,
 		yaml::output_yaml,
 	},
 	anyhow::Context,
 	clap::Parser,
-	ndsz_fat::{FileAllocationTable, FileNameTable},
-	ndsz_util::{IoSlice, ReadByteArray},
-	std::{fs, io},
+	ndsz_fat::fnt,
+	ndsz_manifest::{compressed::Decompressor, detected::Detector, nested::NestedExtractor},
+	ndsz_nds::Rom,
+	ndsz_util::ReadByteArray,
+	std::{convert::TryFrom, fs, io},
 	tracing_subscriber::prelude::*,
 };
 
@@ -41,21 +50,117 @@ fn main() -> Result<(), anyhow::Error> {
 	// Open the rom
 	let mut input_file = fs::File::open(&args.input_path).context("Unable to open input file")?;
 
-	// Read the header
-	let header = self::parse_header(&mut input_file)?;
-	tracing::trace!(?header);
+	// If we're only verifying, verify and return
+	if args.verify {
+		let header = self::parse_header(&mut input_file)?;
+		tracing::trace!(?header);
+		return self::verify_header(&mut input_file, &header);
+	}
 
-	// Parses the fat and fnt
-	let fat = self::parse_fat(&mut input_file, &header)?;
-	let fnt = self::parse_fnt(&mut input_file, &header)?;
+	// Read the rom
+	let mut rom = Rom::from_reader(input_file).context("Unable to read rom")?;
+	tracing::trace!(header = ?rom.header, dsi_header = ?rom.dsi_header);
 
-	// Then extract all parts, as well as files not mentioned in the fnt
+	// Then extract all parts, as well as files not mentioned in the fnt, decompressing the code, if requested
 	fs::create_dir_all(&output_path).context("Unable to create output directory")?;
-	self::extract_all_parts(&mut input_file, &header, &output_path).context("Unable to extract parts")?;
-	let hidden_fat_files = self::extract_fat_hidden(&fat, &fnt, &mut input_file, &output_path)?;
+	let mut decompressor = args.decompress.then(|| Decompressor::new(&output_path));
+	self::extract_all_parts(&mut rom.reader, &rom.header, &output_path).context("Unable to extract parts")?;
+	if let Some(decompressor) = &mut decompressor {
+		decompressor
+			.decompress_arm9(
+				&output_path.join("arm9_load_data.bin"),
+				rom.header.arm9_load_data.load_address,
+			)
+			.context("Unable to decompress arm9 binary")?;
+	}
+	let banner_size = self::extract_banner(&mut rom, &output_path).context("Unable to extract banner")?;
+	if let Some(dsi_header) = &rom.dsi_header {
+		self::extract_dsi_parts(&mut rom.reader, dsi_header, &output_path).context("Unable to extract dsi parts")?;
+	}
+	self::extract_overlays(
+		&mut rom.reader,
+		&rom.fat,
+		&rom.arm9_overlay_table,
+		&output_path.join("overlay9"),
+		decompressor.as_mut(),
+	)
+	.context("Unable to extract arm9 overlays")?;
+	self::extract_overlays(
+		&mut rom.reader,
+		&rom.fat,
+		&rom.arm7_overlay_table,
+		&output_path.join("overlay7"),
+		decompressor.as_mut(),
+	)
+	.context("Unable to extract arm7 overlays")?;
+	let overlay_file_ids = rom
+		.arm9_overlay_table
+		.overlays
+		.iter()
+		.chain(&rom.arm7_overlay_table.overlays)
+		.filter_map(|overlay| u16::try_from(overlay.file_id).ok());
+	let fnt_index = fnt::Index::new(&rom.fnt);
+	let hidden_fat_files =
+		self::extract_fat_hidden(&rom.fat, &fnt_index, overlay_file_ids, &mut rom.reader, &output_path)?;
 
 	let fs_dir = output_path.join("fs");
-	self::extract_fat_dir(&fnt.root, &mut input_file, &fat, fs_dir).context("Unable to extract fat")?;
+	self::extract_fat_dir(&rom.fnt.root, &mut rom.reader, &rom.fat, fs_dir.clone()).context("Unable to extract fat")?;
+
+	// Then detect the file types, decompress and extract all narcs within the
+	// filesystem and hidden files, if requested
+	let mut nested_extractor = args.recursive.then(|| NestedExtractor::new(&output_path));
+	let mut detector = args
+		.extensions
+		.then(|| Detector::new(&output_path, decompressor.is_some()));
+	let fs_files = fnt_index.files().map(|(_, path)| fs_dir.join(path));
+	let hidden_files = hidden_fat_files
+		.iter()
+		.map(|idx| output_path.join("fat").join(format!("{idx}.bin")));
+	for path in fs_files.chain(hidden_files) {
+		let path = match &mut detector {
+			Some(detector) => detector
+				.rename(&path)
+				.with_context(|| format!("Unable to detect file type of {path:?}"))?,
+			None => path,
+		};
+		if let Some(decompressor) = &mut decompressor {
+			decompressor
+				.decompress(&path)
+				.with_context(|| format!("Unable to decompress {path:?}"))?;
+		}
+		if let Some(nested_extractor) = &mut nested_extractor {
+			nested_extractor
+				.extract(&path, decompressor.as_mut(), detector.as_mut())
+				.with_context(|| format!("Unable to extract nested narcs in {path:?}"))?;
+		}
+	}
+	if let Some(decompressor) = &decompressor {
+		decompressor
+			.output_yaml()
+			.context("Unable to output compressed files manifest")?;
+	}
+	if let Some(nested_extractor) = &nested_extractor {
+		nested_extractor
+			.output_yaml()
+			.context("Unable to output nested narcs manifest")?;
+	}
+	if let Some(detector) = &detector {
+		detector
+			.output_yaml()
+			.context("Unable to output detected files manifest")?;
+	}
+
+	// Finally output the manifest
+	self::output_yaml(
+		&rom.header,
+		&rom.fat,
+		&rom.fnt.root,
+		&fnt_index,
+		&hidden_fat_files,
+		banner_size,
+		&output_path,
+	)
+	.context("Unable to output header manifest")?;
 
 	Ok(())
 }
@@ -65,37 +170,3 @@ fn parse_header<R: io::Read>(rom_file: &mut R) -> Result<ndsz_nds::Header, anyho
 	let header_bytes = rom_file.read_byte_array().context("Unable to read header")?;
 	ndsz_nds::Header::from_bytes(&header_bytes).context("Unable to parse header")
 }
-
-/// Parses the fat
-fn parse_fat<R: io::Read + io::Seek>(
-	rom_file: &mut R,
-	header: &ndsz_nds::Header,
-) -> Result<FileAllocationTable, anyhow::Error> {
-	// Create the slice
-	let mut fat_slice = IoSlice::new_with_offset_len(
-		rom_file,
-		u64::from(header.file_allocation_table.offset),
-		u64::from(header.file_allocation_table.length),
-	)
-	.context("Unable to create fat slice")?;
-
-	// Then parse it
-	FileAllocationTable::from_reader(&mut fat_slice).context("Unable to get fat")
-}
-
-/// Parses the fnt
-fn parse_fnt<R: io::Read + io::Seek>(
-	rom_file: &mut R,
-	header: &ndsz_nds::Header,
-) -> Result<FileNameTable, anyhow::Error> {
-	// Create the slice
-	let mut fnt_slice = IoSlice::new_with_offset_len(
-		rom_file,
-		u64::from(header.file_name_table.offset),
-		u64::from(header.file_name_table.length),
-	)
-	.context("Unable to create fnt slice")?;
-
-	// Then parse it
-	FileNameTable::from_reader(&mut fnt_slice).context("Unable to read fnt")
-}
@@ -0,0 +1,71 @@
+//! Verification
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_bytes::ValidateVisitor,
+	ndsz_nds::{header, Header},
+	ndsz_util::ReadByteArray,
+	std::io,
+};
+
+/// Verify visitor.
+///
+/// Prints all warnings and errors, while keeping count of them.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+struct VerifyVisitor {
+	/// Number of warnings
+	warnings: usize,
+
+	/// Number of errors
+	errors: usize,
+}
+
+impl<'a> ValidateVisitor<'a, Header> for &mut VerifyVisitor {
+	fn visit_warning(&mut self, warning: header::ValidateWarning) {
+		println!("Warning: {warning}");
+		self.warnings += 1;
+	}
+
+	fn visit_error(&mut self, error: header::ValidateError) {
+		println!("Error: {error}");
+		self.errors += 1;
+	}
+}
+
+/// Verifies the header against the rom.
+///
+/// Returns an error if any errors were found.
+pub fn verify_header<R: io::Read + io::Seek>(rom_file: &mut R, header: &Header) -> Result<(), anyhow::Error> {
+	let mut visitor = VerifyVisitor::default();
+
+	// Check if the rom was truncated
+	let rom_len = rom_file
+		.seek(io::SeekFrom::End(0))
+		.context("Unable to get rom length")?;
+	if rom_len < u64::from(header.ntr_region_rom_size) {
+		println!(
+			"Warning: Rom length {rom_len:#x} is smaller than the ntr region rom size {:#x}",
+			header.ntr_region_rom_size
+		);
+		visitor.warnings += 1;
+	}
+
+	// Read the secure area, if the rom has one
+	let secure_area = match rom_len >= 0x8000 {
+		true => {
+			rom_file
+				.seek(io::SeekFrom::Start(0x4000))
+				.context("Unable to seek to secure area")?;
+			let secure_area: [u8; 0x4000] = rom_file.read_byte_array().context("Unable to read secure area")?;
+			Some(secure_area)
+		},
+		false => None,
+	};
+
+	header.verify(secure_area.as_ref(), &mut visitor);
+	println!("{} warning(s), {} error(s)", visitor.warnings, visitor.errors);
+
+	anyhow::ensure!(visitor.errors == 0, "Found {} error(s) in the header", visitor.errors);
+	Ok(())
+}
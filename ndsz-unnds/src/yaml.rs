@@ -0,0 +1,102 @@
+//! Yaml manifest
+
+// Imports
+use {
+	anyhow::Context,
+	ndsz_fat::{fnt, Dir, FileAllocationTable},
+	ndsz_nds::{
+		header::TableLoadData,
+		layout::{Layout, Part, PartKind},
+		Header,
+	},
+	std::{fs, path::Path},
+};
+
+/// Manifest
+#[derive(Debug)]
+#[derive(serde::Serialize)]
+struct Manifest<'a> {
+	/// Header
+	header: &'a Header,
+
+	/// All hidden fat files
+	hidden_fat_files: &'a [u16],
+
+	/// All filesystem paths, in their original order.
+	///
+	/// Directories end with a `/`.
+	fnt_order: &'a [String],
+
+	/// Layout of the rom
+	layout: &'a Layout,
+}
+
+/// Outputs the `header.yaml` manifest
+pub fn output_yaml(
+	header: &Header,
+	fat: &FileAllocationTable,
+	fnt_root: &Dir,
+	fnt_index: &fnt::Index,
+	hidden_fat_files: &[u16],
+	banner_size: u32,
+	path: &Path,
+) -> Result<(), anyhow::Error> {
+	// Collect all paths in the filesystem
+	let fnt_order = ndsz_manifest::fnt_order::collect(fnt_root);
+
+	// Then build the layout
+	let table_part = |kind, table: TableLoadData| Part {
+		kind,
+		offset: table.offset,
+		size: table.length,
+	};
+	let mut parts = vec![
+		Part {
+			kind:   PartKind::Arm9,
+			offset: header.arm9_load_data.offset,
+			size:   header.arm9_load_data.size,
+		},
+		table_part(PartKind::Arm9OverlayTable, header.arm9_overlay_table),
+		Part {
+			kind:   PartKind::Arm7,
+			offset: header.arm7_load_data.offset,
+			size:   header.arm7_load_data.size,
+		},
+		table_part(PartKind::Arm7OverlayTable, header.arm7_overlay_table),
+		table_part(PartKind::FileNameTable, header.file_name_table),
+		table_part(PartKind::FileAllocationTable, header.file_allocation_table),
+		Part {
+			kind:   PartKind::Banner,
+			offset: header.icon_banner_offset,
+			size:   banner_size,
+		},
+	];
+	for (id, ptr) in (0..).zip(&fat.ptrs) {
+		let kind = match fnt_index.file_path(id) {
+			Some(path) => PartKind::FsFile { path: path.to_owned() },
+			None => PartKind::File { id },
+		};
+
+		parts.push(Part {
+			kind,
+			offset: ptr.start_address,
+			size: ptr.end_address.saturating_sub(ptr.start_address),
+		});
+	}
+	parts.retain(|part| (part.offset, part.size) != (0, 0));
+	let layout = Layout::new(parts);
+
+	// And finally write the manifest
+	let manifest = Manifest {
+		header,
+		hidden_fat_files,
+		fnt_order: &fnt_order,
+		layout: &layout,
+	};
+	let manifest_path = path.join("header.yaml");
+	println!("{}", manifest_path.display());
+	let manifest_file = fs::File::create(manifest_path).context("Unable to create header manifest")?;
+	serde_yaml::to_writer(manifest_file, &manifest).context("Unable to write header manifest")?;
+
+	Ok(())
+}
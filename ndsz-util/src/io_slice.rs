@@ -95,6 +95,32 @@ impl<T: Seek> IoSlice<T> {
 		self.inner
 	}
 
+	/// Returns the inner value mutably.
+	///
+	/// Note: Seeking the inner value also changes the position of the slice.
+	pub fn inner_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
+	/// Returns the start position of this slice within the inner value
+	pub fn start_pos(&self) -> u64 {
+		self.start_pos
+	}
+
+	/// Returns the end position of this slice within the inner value
+	pub fn end_pos(&self) -> u64 {
+		self.end_pos
+	}
+
+	/// Sets the end position of this slice within the inner value.
+	///
+	/// # Panics
+	/// Panics if `end_pos` is before the start position.
+	pub fn set_end_pos(&mut self, end_pos: u64) {
+		assert!(end_pos >= self.start_pos, "End position can't be before start position");
+		self.end_pos = end_pos;
+	}
+
 	/// Returns the len of this slice
 	#[allow(clippy::len_without_is_empty)] // Not applicable: we're not a collection
 	pub fn len(&mut self) -> u64 {
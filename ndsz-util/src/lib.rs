@@ -8,6 +8,7 @@
 // Modules
 pub mod ascii_str_arr;
 pub mod io_slice;
+pub mod serde_hex;
 pub mod write_take;
 
 // Exports
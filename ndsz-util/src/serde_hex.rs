@@ -0,0 +1,50 @@
+//! Serializes byte arrays as hex strings.
+//!
+//! Meant to be used with `#[serde(with = "ndsz_util::serde_hex")]`.
+
+// Imports
+use std::fmt;
+
+/// Serializes a byte array as a hex string
+pub fn serialize<S, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	let hex = bytes.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+	serializer.serialize_str(&hex)
+}
+
+/// Deserializes a byte array from a hex string
+pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	deserializer.deserialize_str(HexVisitor)
+}
+
+/// Visitor for [`deserialize`]
+struct HexVisitor<const N: usize>;
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for HexVisitor<N> {
+	type Value = [u8; N];
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "A hex string of {} bytes", N)
+	}
+
+	fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+		if value.len() != 2 * N {
+			return Err(E::invalid_length(value.len() / 2, &self));
+		}
+
+		let mut bytes = [0; N];
+		for (byte, idx) in bytes.iter_mut().zip((0..value.len()).step_by(2)) {
+			let digits = value
+				.get(idx..(idx + 2))
+				.ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))?;
+			*byte = u8::from_str_radix(digits, 16).map_err(E::custom)?;
+		}
+
+		Ok(bytes)
+	}
+}